@@ -0,0 +1,104 @@
+use anyhow::Result;
+use std::collections::HashMap;
+
+use crate::cli::output::{OutputFormat, OutputOptions};
+use crate::cli::renderer;
+use crate::cli::usage_cmd::{fetch_with_timeout, finish_cost_scan, spawn_cost_scan};
+use crate::core::budget;
+use crate::core::config::AppConfig;
+use crate::core::http::HttpClient;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::providers::Provider;
+
+/// Exit status when at least one budget is exceeded.
+const EXIT_OVER_BUDGET: i32 = 2;
+
+/// Evaluate configured budgets for the current period.
+pub async fn run(opts: &OutputOptions) -> Result<()> {
+    let config = AppConfig::load()?;
+    let http = HttpClient::from_config(&config)?;
+    if config.budgets.is_empty() {
+        eprintln!(
+            "No budgets configured. Add [[budgets]] entries to {}",
+            AppConfig::config_path().display()
+        );
+        return Ok(());
+    }
+
+    // Read session logs for cost budgets while credits are fetched, bound by
+    // the same --timeout
+    let deadline = opts.timeout.map(|t| tokio::time::Instant::now() + t);
+    let cost_scan = budget::CostQuery::new(&config, chrono::Local::now().date_naive())
+        .map(|query| spawn_cost_scan(Some(query)));
+
+    // Fetch credits only for providers that budgets are tracked against
+    let mut providers: Vec<Provider> = config
+        .budgets
+        .iter()
+        .filter_map(|b| b.credits_provider())
+        .collect();
+    providers.sort_by_key(|p| p.id());
+    providers.dedup();
+
+    let handles: Vec<_> = providers
+        .into_iter()
+        .map(|provider| {
+            let http = http.clone();
+            let timeout = opts.timeout;
+            tokio::spawn(async move {
+                (provider, fetch_with_timeout(provider, &http, timeout).await)
+            })
+        })
+        .collect();
+    let mut credits: HashMap<Provider, CreditsSnapshot> = HashMap::new();
+    for handle in handles {
+        let (provider, result) = handle.await?;
+        match result {
+            Ok(fetched) => {
+                if let Some(mut c) = fetched.credits {
+                    if let Some(code) = config.credits_currency(provider) {
+                        c.currency = Some(code.to_string());
+                    }
+                    credits.insert(provider, c);
+                }
+            }
+            Err(e) => {
+                if opts.verbose {
+                    eprintln!("Error fetching {}: {:#}", provider.display_name(), e);
+                }
+            }
+        }
+    }
+
+    let cost_spend = match cost_scan {
+        Some(scan) => match finish_cost_scan(scan, None, deadline).await {
+            Ok(Some(scanned)) => Ok(scanned.budget_spend),
+            Ok(None) => Err("Failed to scan session logs".to_string()),
+            Err(_) => Err(format!(
+                "Session logs not scanned within {}s",
+                opts.timeout.unwrap_or_default().as_secs()
+            )),
+        },
+        None => Ok(Vec::new()),
+    };
+    let statuses = budget::evaluate(&config, &credits, &cost_spend);
+
+    match opts.format {
+        OutputFormat::Json => {
+            let json = if opts.pretty {
+                serde_json::to_string_pretty(&statuses)?
+            } else {
+                serde_json::to_string(&statuses)?
+            };
+            println!("{}", json);
+        }
+        OutputFormat::Text => {
+            println!("{}", renderer::render_budgets(&statuses, opts.use_color));
+        }
+    }
+
+    if statuses.iter().any(|s| s.exceeded) {
+        std::process::exit(EXIT_OVER_BUDGET);
+    }
+    Ok(())
+}
@@ -142,6 +142,12 @@ pub fn add(provider_id: &str, _opts: &OutputOptions) -> Result<()> {
             enabled: true,
             source: "auto".to_string(),
             api_key: None,
+            timeout_secs: None,
+            cookie: None,
+            project: None,
+            region: None,
+            credentials: None,
+            currency: None,
         });
     }
 
@@ -0,0 +1,445 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+use crate::cli::output::{OutputFormat, OutputOptions};
+use crate::cli::usage_cmd::{
+    build_report, evaluate_budgets, fetch_with_timeout, finish_cost_scan, spawn_cost_scan,
+};
+use crate::core::budget::{self, BudgetStatus};
+use crate::core::config::AppConfig;
+use crate::core::currency::CurrencyConverter;
+use crate::core::daemon::{self, Request, Response, MAX_MESSAGE_BYTES};
+use crate::core::http::HttpClient;
+use crate::core::models::cost::CostSummary;
+use crate::core::models::report::{ProviderReport, UsageReport};
+use crate::core::providers::error::{ErrorKind, ErrorReport};
+use crate::core::providers::Provider;
+
+/// Latest snapshots kept in memory between refreshes.
+#[derive(Default)]
+struct DaemonState {
+    results: HashMap<Provider, Result<ProviderReport, ErrorReport>>,
+    cost: Option<HashMap<Provider, CostSummary>>,
+    budgets: Vec<BudgetStatus>,
+    last_refresh: Option<DateTime<Utc>>,
+}
+
+impl DaemonState {
+    /// Record a fetch outcome. A transient failure keeps the previous
+    /// snapshot (its `updated_at` shows its age) instead of replacing it.
+    fn record(&mut self, provider: Provider, result: Result<ProviderReport, ErrorReport>) {
+        if let (Err(e), Some(Ok(_))) = (&result, self.results.get(&provider)) {
+            if matches!(
+                e.kind,
+                ErrorKind::Network | ErrorKind::Timeout | ErrorKind::HttpStatus
+            ) {
+                return;
+            }
+        }
+        self.results.insert(provider, result);
+    }
+
+    /// Report for `requested` providers (all tracked ones when empty).
+    fn report(&self, tracked: &[Provider], requested: &[Provider]) -> UsageReport {
+        let requested = if requested.is_empty() {
+            tracked
+        } else {
+            requested
+        };
+        let mut results = Vec::new();
+        let mut errors = Vec::new();
+        for provider in requested {
+            match self.results.get(provider) {
+                Some(Ok(report)) => results.push(ProviderReport {
+                    cached: true,
+                    ..report.clone()
+                }),
+                Some(Err(error)) => errors.push((*provider, error.clone())),
+                None => {}
+            }
+        }
+        UsageReport {
+            budgets: self.budgets.clone(),
+            ..build_report(requested, results, errors, &[], "", self.cost.clone())
+        }
+    }
+}
+
+/// Run the daemon in the foreground until interrupted.
+pub async fn run(interval_secs: u64, opts: &OutputOptions) -> Result<()> {
+    let config = AppConfig::load().unwrap_or_default();
+    let http = HttpClient::from_config(&config)?;
+    let fx = CurrencyConverter::from_config(&config);
+    let interval = Duration::from_secs(interval_secs.max(1));
+
+    let providers: Vec<Provider> = config
+        .providers
+        .iter()
+        .filter(|p| p.enabled)
+        .filter_map(|p| Provider::from_id(&p.id))
+        .filter(|p| p.is_supported())
+        .collect();
+    if providers.is_empty() {
+        anyhow::bail!("No supported providers enabled. Run `ait config init` to set up providers.");
+    }
+
+    let path = daemon::socket_path();
+    let listener = bind(&path).await?;
+    eprintln!(
+        "ait daemon listening on {} (refresh every {}s)",
+        path.display(),
+        interval.as_secs()
+    );
+
+    let state = Arc::new(RwLock::new(DaemonState::default()));
+    let refresher = {
+        let state = Arc::clone(&state);
+        let providers = providers.clone();
+        let verbose = opts.verbose;
+        let config = config.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                refresh(&providers, &config, &http, fx.as_ref(), interval, &state, verbose).await;
+            }
+        })
+    };
+
+    let mut terminate =
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let Ok((stream, _)) = accepted else { continue };
+                let state = Arc::clone(&state);
+                let providers = providers.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle(stream, &state, &providers).await {
+                        eprintln!("ait daemon: {:#}", e);
+                    }
+                });
+            }
+            _ = tokio::signal::ctrl_c() => break,
+            _ = terminate.recv() => break,
+        }
+    }
+
+    refresher.abort();
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+/// Bind the socket, replacing a stale one left by a daemon that died.
+async fn bind(path: &Path) -> Result<UnixListener> {
+    if path.exists() {
+        if daemon::request(path, &Request::Ping).await.is_ok() {
+            anyhow::bail!("ait daemon is already running ({})", path.display());
+        }
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    // Usage data includes account emails; only the owner may connect. The
+    // socket is created with umask permissions, so the directory holding it is
+    // what keeps other users out until its own mode is set.
+    if let Some(parent) = path.parent() {
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+        std::fs::set_permissions(parent, std::fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("Failed to restrict {}", parent.display()))?;
+    }
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("Failed to bind {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Fetch every provider and rescan session logs, then publish the results.
+async fn refresh(
+    providers: &[Provider],
+    config: &AppConfig,
+    http: &HttpClient,
+    fx: Option<&CurrencyConverter>,
+    interval: Duration,
+    state: &RwLock<DaemonState>,
+    verbose: bool,
+) {
+    let cost_query = budget::CostQuery::new(config, chrono::Local::now().date_naive());
+    let scan_logs = providers.iter().any(|p| p.has_cost_logs()) || cost_query.is_some();
+    let cost_handle = scan_logs.then(|| spawn_cost_scan(cost_query));
+
+    // A refresh never runs into the next one
+    let mut tasks = tokio::task::JoinSet::new();
+    for &provider in providers {
+        let http = http.clone();
+        tasks.spawn(async move {
+            let fetched_at = Utc::now();
+            let result = fetch_with_timeout(provider, &http, Some(interval)).await;
+            (provider, fetched_at, result)
+        });
+    }
+    let mut outcomes = Vec::new();
+    let mut reported_cost = HashMap::new();
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((provider, fetched_at, result)) = joined else { continue };
+        let outcome = match result {
+            Ok(mut fetched) => {
+                let mut credits = fetched.credits;
+                if let (Some(code), Some(credits)) = (config.credits_currency(provider), &mut credits) {
+                    credits.currency = Some(code.to_string());
+                }
+                if let (Some(fx), Some(credits)) = (fx, &mut credits) {
+                    fx.convert_credits(credits);
+                }
+                if let Some(fx) = fx {
+                    fx.convert_endpoints(&mut fetched.usage);
+                }
+                if let Some(mut cost) = fetched.cost {
+                    if let Some(fx) = fx {
+                        fx.convert_cost(&mut cost);
+                    }
+                    reported_cost.insert(provider, cost);
+                }
+                Ok(ProviderReport::new(fetched.usage, credits, None, fetched_at, false))
+            }
+            Err(e) => {
+                let error = ErrorReport::from_error(&e);
+                if verbose {
+                    eprintln!("Error fetching {}: {}", provider.display_name(), error.message);
+                }
+                Err(error)
+            }
+        };
+        outcomes.push((provider, outcome));
+    }
+    let scanned = match cost_handle {
+        Some(handle) => finish_cost_scan(handle, fx, None).await.unwrap_or(None),
+        None => None,
+    };
+    let (cost, cost_spend) = match scanned {
+        Some(scanned) => (Some(scanned.costs), Ok(scanned.budget_spend)),
+        None if scan_logs => (None, Err("Failed to scan session logs".to_string())),
+        None => (None, Ok(Vec::new())),
+    };
+
+    let mut state = state.write().unwrap();
+    for (provider, outcome) in outcomes {
+        state.record(provider, outcome);
+    }
+    if let Some(mut cost) = cost {
+        // API-reported spend survives a rescan of the session logs
+        if let Some(previous) = state.cost.take() {
+            cost.extend(previous.into_iter().filter(|(p, _)| !p.has_cost_logs()));
+        }
+        state.cost = Some(cost);
+    }
+    if !reported_cost.is_empty() {
+        state.cost.get_or_insert_with(HashMap::new).extend(reported_cost);
+    }
+    let reports: Vec<ProviderReport> = state
+        .results
+        .values()
+        .filter_map(|r| r.as_ref().ok().cloned())
+        .collect();
+    state.budgets = evaluate_budgets(config, &reports, &cost_spend);
+    state.last_refresh = Some(Utc::now());
+}
+
+/// Answer one request on a client connection.
+async fn handle(
+    stream: UnixStream,
+    state: &RwLock<DaemonState>,
+    providers: &[Provider],
+) -> Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut line = String::new();
+    BufReader::new(read)
+        .take(MAX_MESSAGE_BYTES)
+        .read_line(&mut line)
+        .await?;
+
+    let response = match serde_json::from_str::<Request>(&line) {
+        Ok(Request::Ping) => Response::Pong {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            pid: std::process::id(),
+            providers: providers.to_vec(),
+            last_refresh: state.read().unwrap().last_refresh,
+        },
+        Ok(Request::Usage { providers: requested }) => Response::Usage {
+            report: state.read().unwrap().report(providers, &requested),
+        },
+        Err(e) => Response::Error {
+            message: format!("Invalid request: {}", e),
+        },
+    };
+    let mut json = serde_json::to_vec(&response)?;
+    json.push(b'\n');
+    write.write_all(&json).await?;
+    Ok(())
+}
+
+/// Show whether a daemon is running and when it last refreshed.
+pub async fn status(opts: &OutputOptions) -> Result<()> {
+    let path = daemon::socket_path();
+    let response = daemon::request(&path, &Request::Ping).await.ok();
+    match opts.format {
+        OutputFormat::Json => {
+            let json = serde_json::json!({
+                "running": response.is_some(),
+                "socket": path,
+                "daemon": response,
+            });
+            let json = if opts.pretty {
+                serde_json::to_string_pretty(&json)?
+            } else {
+                serde_json::to_string(&json)?
+            };
+            println!("{}", json);
+        }
+        OutputFormat::Text => match response {
+            Some(Response::Pong {
+                version,
+                pid,
+                providers,
+                last_refresh,
+            }) => {
+                println!("ait daemon {} running (pid {}) on {}", version, pid, path.display());
+                let names: Vec<&str> = providers.iter().map(|p| p.display_name()).collect();
+                println!("Providers: {}", names.join(", "));
+                match last_refresh {
+                    Some(at) => println!(
+                        "Last refresh: {}",
+                        crate::core::formatter::format_updated_ago(&at)
+                    ),
+                    None => println!("Last refresh: pending"),
+                }
+            }
+            _ => println!("ait daemon is not running ({})", path.display()),
+        },
+    }
+    Ok(())
+}
+
+/// Print the systemd user unit, or write it with `install`.
+pub fn unit(install: bool) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the ait executable")?;
+    let unit = daemon::systemd_unit(&exe);
+    if !install {
+        print!("{}", unit);
+        return Ok(());
+    }
+    let path = daemon::systemd_unit_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(&path, unit).with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("Wrote {}", path.display());
+    println!("Enable it with: systemctl --user daemon-reload && systemctl --user enable --now ait.service");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::usage::UsageSnapshot;
+
+    fn ok(provider: Provider) -> Result<ProviderReport, ErrorReport> {
+        let usage = UsageSnapshot {
+            provider,
+            source: "api".into(),
+            primary: None,
+            secondary: None,
+            tertiary: None,
+            identity: None,
+            local: None,
+            endpoints: Vec::new(),
+        };
+        Ok(ProviderReport::new(usage, None, None, Utc::now(), false))
+    }
+
+    fn err(kind: ErrorKind) -> Result<ProviderReport, ErrorReport> {
+        Err(ErrorReport {
+            kind,
+            message: "failed".into(),
+            status: None,
+        })
+    }
+
+    #[test]
+    fn transient_failure_keeps_previous_snapshot() {
+        let mut state = DaemonState::default();
+        state.record(Provider::Warp, ok(Provider::Warp));
+        state.record(Provider::Warp, err(ErrorKind::Network));
+        assert!(state.results[&Provider::Warp].is_ok());
+
+        state.record(Provider::Warp, err(ErrorKind::AuthExpired));
+        assert!(state.results[&Provider::Warp].is_err());
+    }
+
+    #[test]
+    fn report_marks_data_cached_and_filters() {
+        let mut state = DaemonState::default();
+        state.record(Provider::Warp, ok(Provider::Warp));
+        state.record(Provider::Kimi, err(ErrorKind::AuthMissing));
+        let tracked = [Provider::Warp, Provider::Kimi];
+
+        let all = state.report(&tracked, &[]);
+        assert_eq!(all.providers.len(), 1);
+        assert!(all.providers[0].cached);
+        assert_eq!(all.errors[0].provider, Provider::Kimi);
+
+        let one = state.report(&tracked, &[Provider::Warp]);
+        assert_eq!(one.providers.len(), 1);
+        assert!(one.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn serves_requests_over_socket() {
+        let dir = std::env::temp_dir().join("ait_test_daemon");
+        let path = dir.join("daemon.sock");
+        let listener = bind(&path).await.unwrap();
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&dir), 0o700);
+        assert_eq!(mode(&path), 0o600);
+        let state = Arc::new(RwLock::new(DaemonState::default()));
+        state.write().unwrap().record(Provider::Warp, ok(Provider::Warp));
+
+        let server_state = Arc::clone(&state);
+        let server = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                handle(stream, &server_state, &[Provider::Warp]).await.unwrap();
+            }
+        });
+
+        let pong = daemon::request(&path, &Request::Ping).await.unwrap();
+        assert!(matches!(pong, Response::Pong { pid, .. } if pid == std::process::id()));
+        let usage = daemon::request(
+            &path,
+            &Request::Usage {
+                providers: vec![Provider::Warp],
+            },
+        )
+        .await
+        .unwrap();
+        let Response::Usage { report } = usage else {
+            panic!("expected a usage response");
+        };
+        assert_eq!(report.providers[0].provider, Provider::Warp);
+
+        server.await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+}
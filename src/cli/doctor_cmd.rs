@@ -0,0 +1,51 @@
+use anyhow::Result;
+
+use crate::cli::output::{OutputFormat, OutputOptions};
+use crate::cli::renderer;
+use crate::core::config::AppConfig;
+use crate::core::doctor;
+use crate::core::providers::Provider;
+
+/// Report credential sources, overrides, log directories and cache health.
+pub fn run(provider_filter: Option<String>, opts: &OutputOptions) -> Result<()> {
+    // An unparsable config is itself a finding; fall back to defaults
+    let config = match AppConfig::load() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Failed to load config: {}", e);
+            AppConfig::default()
+        }
+    };
+
+    let providers: Vec<Provider> = match &provider_filter {
+        Some(id) => match Provider::from_id(id) {
+            Some(p) => vec![p],
+            None => {
+                eprintln!("Unknown provider: {}", id);
+                std::process::exit(1);
+            }
+        },
+        None => Provider::all()
+            .iter()
+            .copied()
+            .filter(|p| !p.is_stub())
+            .collect(),
+    };
+
+    let report = doctor::diagnose(&config, &providers);
+
+    match opts.format {
+        OutputFormat::Json => {
+            let json = if opts.pretty {
+                serde_json::to_string_pretty(&report)?
+            } else {
+                serde_json::to_string(&report)?
+            };
+            println!("{}", json);
+        }
+        OutputFormat::Text => {
+            println!("{}", renderer::render_doctor(&report, opts.use_color));
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,11 @@
+pub mod budget_cmd;
 pub mod config_cmd;
+pub mod daemon_cmd;
+pub mod doctor_cmd;
 pub mod output;
+pub mod pricing_cmd;
 pub mod renderer;
+pub mod schema_cmd;
 pub mod selector;
 pub mod usage_cmd;
+pub mod value_cmd;
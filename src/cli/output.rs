@@ -10,6 +10,8 @@ pub struct OutputOptions {
     pub pretty: bool,
     pub use_color: bool,
     pub verbose: bool,
+    /// Overall deadline for provider fetches (`--timeout`).
+    pub timeout: Option<std::time::Duration>,
 }
 
 pub fn detect_color(color_flag: bool) -> bool {
@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::cli::output::{OutputFormat, OutputOptions};
+use crate::core::cost::pricing::{self, PricingFile, PricingTable};
+use crate::core::cost::scanner;
+
+/// Import a pricing table from a TOML or JSON file into the data directory.
+pub fn update(from: &Path, _opts: &OutputOptions) -> Result<()> {
+    let content = std::fs::read_to_string(from)
+        .with_context(|| format!("Failed to read {}", from.display()))?;
+    let file = PricingFile::parse(&content, from)?;
+    let table = PricingTable::from_file(&file)
+        .with_context(|| format!("Invalid pricing table in {}", from.display()))?;
+    if table.len() == 0 {
+        anyhow::bail!("{} contains no [[models]] entries", from.display());
+    }
+
+    let path = pricing::pricing_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let serialized = toml::to_string_pretty(&file).context("Failed to serialize pricing table")?;
+    std::fs::write(&path, serialized)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    println!(
+        "Imported {} model prices (version {}) to {}",
+        table.len(),
+        table.version,
+        path.display()
+    );
+
+    let builtin = PricingTable::builtin();
+    if table.version < builtin.version {
+        eprintln!(
+            "Note: the built-in table (version {}) is newer; imported prices only apply to models it does not cover.",
+            builtin.version
+        );
+    }
+    Ok(())
+}
+
+/// List models seen in session logs that have no pricing entry.
+pub fn missing(days: Option<u32>, opts: &OutputOptions) -> Result<()> {
+    let models = scanner::scan_unpriced(days)?;
+
+    match opts.format {
+        OutputFormat::Json => {
+            let json = if opts.pretty {
+                serde_json::to_string_pretty(&models)?
+            } else {
+                serde_json::to_string(&models)?
+            };
+            println!("{}", json);
+        }
+        OutputFormat::Text => {
+            if models.is_empty() {
+                println!("All models seen in session logs have pricing.");
+                return Ok(());
+            }
+            println!(
+                "{:<12} {:<32} {:>12} {:>12} {:>12} {:>12}",
+                "PROVIDER", "MODEL", "INPUT", "OUTPUT", "CACHE READ", "CACHE WRITE"
+            );
+            for m in &models {
+                println!(
+                    "{:<12} {:<32} {:>12} {:>12} {:>12} {:>12}",
+                    m.provider.id(),
+                    m.model,
+                    m.input_tokens,
+                    m.output_tokens,
+                    m.cache_read_tokens,
+                    m.cache_creation_tokens
+                );
+            }
+            println!();
+            println!("Add prices with [[pricing]] entries in config.toml or `ait pricing update --from <file>`.");
+        }
+    }
+    Ok(())
+}
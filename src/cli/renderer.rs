@@ -1,13 +1,18 @@
+use chrono::{DateTime, Datelike, Utc};
 use colored::{control, ColoredString, Colorize};
 
+use crate::core::budget::BudgetStatus;
+use crate::core::currency::BASE_CURRENCY;
+use crate::core::doctor::{CheckKind, CheckStatus, DoctorReport};
 use crate::core::formatter::{
-    format_credits, format_remaining_percent, format_reset_countdown, format_reset_datetime,
-    format_usage_bar,
+    format_bytes, format_credits, format_money, format_remaining_percent, format_reset_countdown,
+    format_reset_datetime, format_units, format_updated_ago, format_usage_bar,
 };
 use crate::core::models::cost::CostSummary;
 use crate::core::models::credits::CreditsSnapshot;
 use crate::core::models::status::{StatusIndicator, StatusInfo};
-use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::models::usage::{LocalModels, RateWindow, UsageSnapshot};
+use crate::core::value::ValueReport;
 
 const BAR_WIDTH: usize = 12;
 
@@ -40,6 +45,7 @@ pub fn render_provider(
     credits: Option<&CreditsSnapshot>,
     cost: Option<&CostSummary>,
     status: Option<&StatusInfo>,
+    cached_at: Option<&DateTime<Utc>>,
     show_detailed_cost: bool,
     use_color: bool,
 ) -> String {
@@ -47,13 +53,20 @@ pub fn render_provider(
 
     let mut lines: Vec<String> = Vec::new();
 
-    // Header: " Claude (oauth)"
+    // Header: " Claude (oauth)", with the data age when served from cache
     let header = format!(
         " {} ({})",
         snapshot.provider.display_name(),
         snapshot.source
     );
-    lines.push(header.bold().to_string());
+    match cached_at {
+        Some(at) => lines.push(format!(
+            "{}  {}",
+            header.bold(),
+            format_updated_ago(at).dimmed()
+        )),
+        None => lines.push(header.bold().to_string()),
+    }
 
     // Rate windows — insert a blank line before a window when the previous
     // one had no sub-line (e.g. no "Resets in …") to keep visual spacing even.
@@ -82,6 +95,10 @@ pub fn render_provider(
         prev_had_subline = window.resets_at.is_some();
     }
 
+    if let Some(local) = &snapshot.local {
+        render_local_models(&mut lines, local);
+    }
+
     // Identity lines
     if let Some(identity) = &snapshot.identity {
         if let Some(email) = &identity.email {
@@ -100,38 +117,34 @@ pub fn render_provider(
         }
     }
 
-    // Credits
+    if !snapshot.endpoints.is_empty() {
+        render_endpoints(&mut lines, snapshot);
+    }
+
     if let Some(credits) = credits {
-        let credits_str = if credits.unlimited {
-            "Unlimited".to_string()
-        } else if let (Some(used), Some(limit)) = (credits.used, credits.limit) {
-            let period_suffix = credits
-                .period
-                .as_deref()
-                .map(|p| format!(" ({})", p))
-                .unwrap_or_default();
-            format!("${:.2} / ${:.2} used{}", used, limit, period_suffix)
-        } else if credits.has_credits {
-            format_credits(credits.remaining)
-        } else {
-            "No credits".to_string()
-        };
-        lines.push(format!("  {}   {}", "Credits".cyan(), credits_str));
+        lines.push(format!("  {}   {}", "Credits".cyan(), format_credits_line(credits)));
     }
 
     // Cost
     if let Some(cost) = cost {
+        let (currency, rate) = cost
+            .converted
+            .as_ref()
+            .map(|c| (c.currency.as_str(), c.rate))
+            .unwrap_or((BASE_CURRENCY, 1.0));
+        let money = |usd: f64| format_money(usd * rate, currency);
+
         if show_detailed_cost {
             // Detailed: header + totals + by-model + recent days
             lines.push(format!(
-                "  {} ${:.2}",
+                "  {} {}",
                 format!("Cost({}d)", cost.days).cyan(),
-                cost.total_cost
+                money(cost.total_cost)
             ));
             lines.push(format!(
-                "  {}     ${:.2}",
+                "  {}     {}",
                 "Today".cyan(),
-                cost.today_cost
+                money(cost.today_cost)
             ));
 
             if !cost.by_model.is_empty() {
@@ -139,9 +152,33 @@ pub fn render_provider(
                 for model in &cost.by_model {
                     let in_tok = format_tokens(model.input_tokens);
                     let out_tok = format_tokens(model.output_tokens);
+                    let cost_str = if model.priced {
+                        format!("{:<9}", money(model.total_cost))
+                    } else {
+                        format!("{:<9}", "n/a")
+                    };
                     lines.push(format!(
-                        "    {:<24} ${:<8.2} ({} in / {} out)",
-                        model.model, model.total_cost, in_tok, out_tok
+                        "    {:<24} {} ({} in / {} out)",
+                        model.model, cost_str, in_tok, out_tok
+                    ));
+                }
+            }
+
+            for (label, groups) in [
+                ("By Workspace", &cost.by_workspace),
+                ("By API Key", &cost.by_api_key),
+            ] {
+                if groups.is_empty() {
+                    continue;
+                }
+                lines.push(format!("  {}:", label.cyan()));
+                for group in groups {
+                    lines.push(format!(
+                        "    {:<24} {:<9} ({} in / {} out)",
+                        group.id,
+                        money(group.total_cost),
+                        format_tokens(group.input_tokens),
+                        format_tokens(group.output_tokens)
                     ));
                 }
             }
@@ -150,17 +187,18 @@ pub fn render_provider(
                 lines.push(format!("  {}:", "Recent Days".cyan()));
                 for day in cost.daily.iter().take(10) {
                     lines.push(format!(
-                        "    {:<12} ${:.2}",
+                        "    {:<12} {}",
                         day.date.format("%b %d"),
-                        day.total_cost
+                        money(day.total_cost)
                     ));
                 }
             }
         } else {
             // Compact one-liner
             let cost_str = format!(
-                "${:.2} total, ${:.2} today",
-                cost.total_cost, cost.today_cost
+                "{} total, {} today",
+                money(cost.total_cost),
+                money(cost.today_cost)
             );
             lines.push(format!(
                 "  {} {}",
@@ -168,6 +206,14 @@ pub fn render_provider(
                 cost_str
             ));
         }
+
+        if !cost.unpriced_models.is_empty() {
+            let warning = format!(
+                "no pricing for {} (not included in cost)",
+                cost.unpriced_models.join(", ")
+            );
+            lines.push(format!("  {}  {}", "Unpriced".cyan(), warning.yellow()));
+        }
     }
 
     // Status
@@ -186,6 +232,253 @@ pub fn render_provider(
     lines.join("\n")
 }
 
+/// Render the budgets block.
+///
+/// Layout:
+/// ```text
+///  Budgets
+///   team-claude  $412.30 / $500.00 (Monthly) [██░░░░░░░░░░]
+///                Projected $623.10 by Oct 31 (over budget)
+/// ```
+pub fn render_budgets(budgets: &[BudgetStatus], use_color: bool) -> String {
+    control::set_override(use_color);
+
+    let mut lines: Vec<String> = vec![" Budgets".bold().to_string()];
+    let width = budgets
+        .iter()
+        .map(|b| b.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let indent = " ".repeat(width + 4);
+
+    for budget in budgets {
+        let label = format!("{:<width$}", budget.name, width = width);
+        let (Some(spent), Some(used_percent)) = (budget.spent, budget.used_percent) else {
+            let error = budget.error.as_deref().unwrap_or("no data");
+            lines.push(format!("  {}  {}", label.cyan(), error.red()));
+            continue;
+        };
+
+        let amounts = format!(
+            "{} / {} ({})",
+            format_money(spent, &budget.currency),
+            format_money(budget.amount, &budget.currency),
+            budget.period.label()
+        );
+        let bar = format_usage_bar(used_percent, BAR_WIDTH);
+        lines.push(format!(
+            "  {}  {} {}",
+            label.cyan(),
+            color_by_remaining(used_percent, &amounts),
+            bar.magenta()
+        ));
+
+        if let Some(projected) = budget.projected {
+            let last_day = budget.resets_on.pred_opt().unwrap_or(budget.resets_on);
+            let projection = format!(
+                "Projected {} by {}",
+                format_money(projected, &budget.currency),
+                last_day.format("%b %-d")
+            );
+            let line = if budget.exceeded {
+                format!("{} {}", projection.dimmed(), "(over budget)".red())
+            } else if projected > budget.amount {
+                format!("{} {}", projection.dimmed(), "(on pace to exceed)".yellow())
+            } else {
+                projection.dimmed().to_string()
+            };
+            lines.push(format!("{}{}", indent, line));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Render a subscription value report.
+///
+/// Layout:
+/// ```text
+///  Claude (max, user@example.com)
+///   Plan      $100.00 / period
+///   Oct 2026  $612.40   6.12x  (projected $1054.90)
+///   Sep 2026  $540.10   5.40x
+///   Trend     +95% vs previous period
+/// ```
+pub fn render_value(report: &ValueReport, use_color: bool) -> String {
+    control::set_override(use_color);
+
+    let mut lines: Vec<String> = Vec::new();
+    let account: Vec<&str> = [report.plan.as_deref(), report.user.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect();
+    let header = if account.is_empty() {
+        format!(" {}", report.provider.display_name())
+    } else {
+        format!(" {} ({})", report.provider.display_name(), account.join(", "))
+    };
+    lines.push(header.bold().to_string());
+
+    let plan_str = match report.price {
+        Some(price) => format!("{} / period", format_money(price, &report.currency)),
+        None => "unknown price (add a [[plans]] entry)".yellow().to_string(),
+    };
+    lines.push(format!("  {}      {}", "Plan".cyan(), plan_str));
+
+    for period in &report.periods {
+        let label = if period.start.day() == 1 {
+            period.start.format("%b %Y").to_string()
+        } else {
+            let last = period.end.pred_opt().unwrap_or(period.end);
+            format!("{}–{}", period.start.format("%b %-d"), last.format("%b %-d"))
+        };
+        let mut line = format!(
+            "  {}  {:<10}",
+            format!("{:<8}", label).cyan(),
+            format_money(period.api_cost, &report.currency)
+        );
+        if let Some(ratio) = period.ratio {
+            let ratio_str = format!("{:>5.2}x", ratio);
+            let colored = if ratio >= 1.0 {
+                ratio_str.green()
+            } else {
+                ratio_str.yellow()
+            };
+            line.push_str(&format!(" {}", colored));
+        }
+        if let Some(projected) = period.projected_cost {
+            let projection = format!("(projected {})", format_money(projected, &report.currency));
+            line.push_str(&format!("  {}", projection.dimmed()));
+        }
+        lines.push(line);
+    }
+
+    if let Some(trend) = report.trend_percent {
+        lines.push(format!(
+            "  {}     {:+.0}% vs previous period",
+            "Trend".cyan(),
+            trend
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Render `ait doctor` diagnostics.
+///
+/// Layout:
+/// ```text
+///  Config
+///   /home/user/.config/ait/config.toml  valid
+///  Claude (enabled)
+///   ✓ file      /home/user/.claude/.credentials.json
+///   ! token     accessToken  expired 2h 5m ago
+///  Session logs
+///   Claude  /home/user/.claude/projects  124 files
+///  Cost cache
+///   /home/user/.cache/ait/cost-cache.json  1.2 MB, version 6, 130 files, 5400 records
+/// ```
+pub fn render_doctor(report: &DoctorReport, use_color: bool) -> String {
+    control::set_override(use_color);
+
+    let mut lines: Vec<String> = vec![" Config".bold().to_string()];
+    let config = &report.config;
+    let config_state = if !config.exists {
+        "not found, using defaults".yellow()
+    } else if config.issues.is_empty() {
+        "valid".green()
+    } else {
+        format!("{} issue(s)", config.issues.len()).red()
+    };
+    lines.push(format!("  {}  {}", config.path.display(), config_state));
+    for issue in &config.issues {
+        lines.push(format!("    - {}", issue));
+    }
+
+    for diag in &report.providers {
+        let state = if diag.enabled { "enabled" } else { "disabled" };
+        lines.push(
+            format!(" {} ({})", diag.provider.display_name(), state)
+                .bold()
+                .to_string(),
+        );
+        if !diag.implemented {
+            lines.push(format!("  {}", "not implemented yet".dimmed()));
+            continue;
+        }
+        for check in &diag.checks {
+            let mark = match check.status {
+                CheckStatus::Found => "✓".green(),
+                CheckStatus::Missing => "✗".red(),
+                CheckStatus::Expired | CheckStatus::Invalid => "!".yellow(),
+                CheckStatus::Info => "·".dimmed(),
+            };
+            let mut line = format!("  {} {:<9} {}", mark, check.kind.label(), check.name);
+            match (check.status, &check.detail) {
+                (_, Some(detail)) => line.push_str(&format!("  {}", detail.dimmed())),
+                (CheckStatus::Missing, None) => {
+                    let missing = if check.kind == CheckKind::Env {
+                        "not set"
+                    } else {
+                        "not found"
+                    };
+                    line.push_str(&format!("  {}", missing.dimmed()));
+                }
+                _ => {}
+            }
+            lines.push(line);
+        }
+        if !diag.has_credentials() {
+            lines.push(format!("  {}", "No credentials found".red()));
+        } else if diag.has_problems() {
+            lines.push(format!("  {}", "Credentials need attention".yellow()));
+        }
+    }
+
+    lines.push(" Session logs".bold().to_string());
+    for dir in &report.log_dirs {
+        let files = if dir.exists {
+            format!("{} files", dir.files).normal()
+        } else {
+            "not found".dimmed()
+        };
+        lines.push(format!(
+            "  {}  {}  {}",
+            format!("{:<6}", dir.provider.display_name()).cyan(),
+            dir.path.display(),
+            files
+        ));
+    }
+
+    lines.push(" Cost cache".bold().to_string());
+    let cache = &report.cache;
+    let cache_state = if let Some(error) = &cache.error {
+        error.red().to_string()
+    } else if !cache.exists {
+        "not created yet".dimmed().to_string()
+    } else {
+        let summary = format!(
+            "{:.1} MB, version {}, {} files, {} records",
+            cache.size_bytes.unwrap_or(0) as f64 / 1_000_000.0,
+            cache.version.unwrap_or(0),
+            cache.files,
+            cache.records
+        );
+        if cache.is_healthy() {
+            summary
+        } else {
+            format!(
+                "{} {}",
+                summary,
+                format!("(expected version {}, will be rebuilt)", cache.expected_version).yellow()
+            )
+        }
+    };
+    lines.push(format!("  {}  {}", cache.path.display(), cache_state));
+
+    lines.join("\n")
+}
+
 fn render_rate_window(lines: &mut Vec<String>, label: &str, window: &RateWindow) {
     let percent_str = format_remaining_percent(window.used_percent);
     let bar_str = format_usage_bar(window.used_percent, BAR_WIDTH);
@@ -215,6 +508,101 @@ fn render_rate_window(lines: &mut Vec<String>, label: &str, window: &RateWindow)
     }
 }
 
+/// Credits, shown in the display currency when converted.
+fn format_credits_line(credits: &CreditsSnapshot) -> String {
+    let (currency, remaining, used, limit) = match &credits.converted {
+        Some(c) => (c.currency.as_str(), c.remaining, c.used, c.limit),
+        None => (
+            credits.currency.as_deref().unwrap_or(BASE_CURRENCY),
+            credits.remaining,
+            credits.used,
+            credits.limit,
+        ),
+    };
+    let period_suffix = credits
+        .period
+        .as_deref()
+        .map(|p| format!(" ({})", p))
+        .unwrap_or_default();
+    // A vendor's own units are never shown as money
+    let amount = |value: f64| match credits.unit.as_deref() {
+        Some(unit) => format_units(value, unit),
+        None => format_money(value, currency),
+    };
+    if credits.unlimited {
+        "Unlimited".to_string()
+    } else if let (Some(used), Some(limit)) = (used, limit) {
+        format!("{} / {} used{}", amount(used), amount(limit), period_suffix)
+    } else if credits.has_credits {
+        match credits.unit.as_deref() {
+            Some(unit) => format!("{} remaining", format_units(remaining, unit)),
+            None => format_credits(remaining, currency),
+        }
+    } else {
+        "No credits".to_string()
+    }
+}
+
+/// One sub-section per `[[generic_http]]` endpoint: its name, then its
+/// windows, plan and credits, or the error that kept it from being read.
+fn render_endpoints(lines: &mut Vec<String>, snapshot: &UsageSnapshot) {
+    let provider = snapshot.provider;
+    for endpoint in &snapshot.endpoints {
+        if let Some(error) = &endpoint.error {
+            lines.push(format!("  {}  {}", endpoint.name.bold(), error.red()));
+            continue;
+        }
+        lines.push(format!("  {}", endpoint.name.bold()));
+        let windows = [
+            (provider.session_label(), &endpoint.primary),
+            (provider.weekly_label(), &endpoint.secondary),
+            (provider.tertiary_label(), &endpoint.tertiary),
+        ];
+        for (label, window) in windows {
+            if let Some(window) = window {
+                render_rate_window(lines, label, window);
+            }
+        }
+        if let Some(plan) = endpoint.identity.as_ref().and_then(|i| i.plan.as_ref()) {
+            lines.push(format!("  {}      {}", "Plan".cyan(), plan));
+        }
+        if let Some(credits) = &endpoint.credits {
+            lines.push(format!("  {}   {}", "Credits".cyan(), format_credits_line(credits)));
+        }
+    }
+}
+
+/// Loaded models with their memory split and unload countdown, then the
+/// installed model count.
+fn render_local_models(lines: &mut Vec<String>, local: &LocalModels) {
+    if local.loaded.is_empty() {
+        lines.push(format!("  {}   {}", "Loaded".cyan(), "none".dimmed()));
+    }
+    for (i, model) in local.loaded.iter().enumerate() {
+        let label = if i == 0 { "Loaded" } else { "" };
+        let mut memory = format!("{} VRAM", format_bytes(model.vram_bytes));
+        if model.ram_bytes > 0 {
+            memory.push_str(&format!(" + {} RAM", format_bytes(model.ram_bytes)));
+        }
+        lines.push(format!(
+            "  {}  {}  {}",
+            format!("{:<7}", label).cyan(),
+            model.name,
+            memory.dimmed()
+        ));
+        if let Some(expires_at) = &model.expires_at {
+            let countdown = format_reset_countdown(expires_at).replacen("Resets", "Unloads", 1);
+            lines.push(format!("           {}", countdown.dimmed()));
+        }
+    }
+    lines.push(format!(
+        "  {}   {} installed ({})",
+        "Models".cyan(),
+        local.installed,
+        format_bytes(local.installed_bytes)
+    ));
+}
+
 /// Color the percent string green/yellow/red based on remaining percentage.
 fn color_by_remaining(used_percent: f64, text: &str) -> ColoredString {
     let remaining = 100.0 - used_percent;
@@ -256,21 +644,82 @@ mod tests {
                 organization: None,
                 plan: Some("Pro".to_string()),
             }),
+            local: None,
+            endpoints: Vec::new(),
         }
     }
 
     #[test]
     fn render_contains_provider_name() {
         let snapshot = make_snapshot();
-        let output = render_provider(&snapshot, None, None, None, false, false);
+        let output = render_provider(&snapshot, None, None, None, None, false, false);
         assert!(output.contains("Claude"));
         assert!(output.contains("oauth"));
     }
 
+    #[test]
+    fn render_local_models() {
+        use crate::core::models::usage::LoadedModel;
+        let mut snapshot = make_snapshot();
+        snapshot.local = Some(LocalModels {
+            host: "http://127.0.0.1:11434".into(),
+            loaded: vec![LoadedModel {
+                name: "qwen3:32b".into(),
+                vram_bytes: 16_000_000_000,
+                ram_bytes: 6_000_000_000,
+                expires_at: Some(Utc::now() + chrono::Duration::minutes(4) + chrono::Duration::seconds(30)),
+            }],
+            installed: 3,
+            installed_bytes: 22_274_000_000,
+        });
+        let output = render_provider(&snapshot, None, None, None, None, false, false);
+        assert!(output.contains("Loaded   qwen3:32b  16.0 GB VRAM + 6.0 GB RAM"));
+        assert!(output.contains("Unloads in 4m"));
+        assert!(output.contains("Models   3 installed (22.3 GB)"));
+
+        snapshot.local.as_mut().unwrap().loaded.clear();
+        let output = render_provider(&snapshot, None, None, None, None, false, false);
+        assert!(output.contains("Loaded   none"));
+    }
+
+    #[test]
+    fn render_generic_http_endpoints() {
+        use crate::core::models::usage::EndpointUsage;
+        let mut snapshot = make_snapshot();
+        snapshot.provider = Provider::GenericHttp;
+        snapshot.primary = None;
+        snapshot.secondary = None;
+        snapshot.identity = None;
+        snapshot.endpoints = vec![
+            EndpointUsage {
+                name: "gateway".into(),
+                primary: Some(make_window(25.0)),
+                secondary: None,
+                tertiary: None,
+                identity: None,
+                credits: None,
+                error: None,
+            },
+            EndpointUsage {
+                name: "quota".into(),
+                primary: None,
+                secondary: None,
+                tertiary: None,
+                identity: None,
+                credits: None,
+                error: Some("HTTP 503".into()),
+            },
+        ];
+        let output = render_provider(&snapshot, None, None, None, None, false, false);
+        assert!(output.contains("  gateway\n"));
+        assert!(output.contains("75% remaining"));
+        assert!(output.contains("  quota  HTTP 503"));
+    }
+
     #[test]
     fn render_contains_labels() {
         let snapshot = make_snapshot();
-        let output = render_provider(&snapshot, None, None, None, false, false);
+        let output = render_provider(&snapshot, None, None, None, None, false, false);
         assert!(output.contains("Session"));
         assert!(output.contains("Weekly"));
     }
@@ -278,7 +727,7 @@ mod tests {
     #[test]
     fn render_contains_identity() {
         let snapshot = make_snapshot();
-        let output = render_provider(&snapshot, None, None, None, false, false);
+        let output = render_provider(&snapshot, None, None, None, None, false, false);
         assert!(output.contains("user@example.com"));
         assert!(output.contains("Pro"));
     }
@@ -290,10 +739,22 @@ mod tests {
             indicator: StatusIndicator::Operational,
             description: None,
         };
-        let output = render_provider(&snapshot, None, None, Some(&status), false, false);
+        let output = render_provider(&snapshot, None, None, Some(&status), None, false, false);
         assert!(output.contains("Operational"));
     }
 
+    #[test]
+    fn render_marks_cached_data_age() {
+        let snapshot = make_snapshot();
+        let fresh = render_provider(&snapshot, None, None, None, None, false, false);
+        assert!(!fresh.contains("updated"));
+
+        let cached_at = chrono::Utc::now() - chrono::Duration::seconds(40);
+        let output = render_provider(&snapshot, None, None, None, Some(&cached_at), false, false);
+        let header = output.lines().next().unwrap();
+        assert!(header.ends_with("updated 40s ago"), "{}", header);
+    }
+
     #[test]
     fn render_contains_credits() {
         let snapshot = make_snapshot();
@@ -304,9 +765,11 @@ mod tests {
             used: None,
             limit: None,
             currency: None,
+            unit: None,
             period: None,
+            converted: None,
         };
-        let output = render_provider(&snapshot, Some(&credits), None, None, false, false);
+        let output = render_provider(&snapshot, Some(&credits), None, None, None, false, false);
         assert!(output.contains("$42.50 remaining"));
     }
 
@@ -320,16 +783,248 @@ mod tests {
             used: Some(12.34),
             limit: Some(50.00),
             currency: Some("usd".to_string()),
+            unit: None,
             period: Some("Monthly".to_string()),
+            converted: None,
         };
-        let output = render_provider(&snapshot, Some(&credits), None, None, false, false);
+        let output = render_provider(&snapshot, Some(&credits), None, None, None, false, false);
         assert!(output.contains("$12.34 / $50.00 used (Monthly)"));
     }
 
+    #[test]
+    fn render_credits_in_own_currency() {
+        let snapshot = make_snapshot();
+        let credits = CreditsSnapshot {
+            remaining: 88.0,
+            has_credits: true,
+            unlimited: false,
+            used: None,
+            limit: None,
+            currency: Some("cny".to_string()),
+            unit: None,
+            period: None,
+            converted: None,
+        };
+        let output = render_provider(&snapshot, Some(&credits), None, None, None, false, false);
+        assert!(output.contains("¥88.00 remaining"));
+    }
+
+    #[test]
+    fn render_non_monetary_credits_without_currency() {
+        let snapshot = make_snapshot();
+        let credits = CreditsSnapshot {
+            remaining: 3420.0,
+            has_credits: true,
+            unlimited: false,
+            used: Some(580.0),
+            limit: Some(4000.0),
+            currency: None,
+            unit: Some("credits".to_string()),
+            period: Some("Billing cycle".to_string()),
+            converted: None,
+        };
+        let output = render_provider(&snapshot, Some(&credits), None, None, None, false, false);
+        assert!(output.contains("580 credits / 4000 credits used (Billing cycle)"), "{}", output);
+        assert!(!output.contains('$'));
+    }
+
+    #[test]
+    fn render_uses_converted_amounts() {
+        use crate::core::models::cost::ConvertedCost;
+        use crate::core::models::credits::ConvertedCredits;
+        let snapshot = make_snapshot();
+        let credits = CreditsSnapshot {
+            remaining: 72.0,
+            has_credits: true,
+            unlimited: false,
+            used: None,
+            limit: None,
+            currency: Some("cny".to_string()),
+            unit: None,
+            period: None,
+            converted: Some(ConvertedCredits {
+                currency: "EUR".to_string(),
+                rate: 0.125,
+                remaining: 9.0,
+                used: None,
+                limit: None,
+            }),
+        };
+        let cost = CostSummary {
+            total_cost: 10.0,
+            today_cost: 2.0,
+            days: 30,
+            by_model: vec![],
+            daily: vec![],
+            unpriced_models: vec![],
+            converted: Some(ConvertedCost {
+                currency: "EUR".to_string(),
+                rate: 0.9,
+                total_cost: 9.0,
+                today_cost: 1.8,
+            }),
+            by_workspace: vec![],
+            by_api_key: vec![],
+        };
+        let output = render_provider(&snapshot, Some(&credits), Some(&cost), None, None, false, false);
+        assert!(output.contains("€9.00 remaining"));
+        assert!(output.contains("€9.00 total, €1.80 today"));
+    }
+
+    #[test]
+    fn render_budgets_shows_bar_and_projection() {
+        use crate::core::budget::{BudgetPeriod, SpendSource};
+        use chrono::NaiveDate;
+        let budget = BudgetStatus {
+            name: "team".to_string(),
+            amount: 500.0,
+            currency: "USD".to_string(),
+            period: BudgetPeriod::Monthly,
+            provider: Some("claude".to_string()),
+            model: None,
+            project: None,
+            source: SpendSource::Cost,
+            period_start: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            resets_on: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            spent: Some(250.0),
+            used_percent: Some(50.0),
+            projected: Some(600.0),
+            exceeded: false,
+            error: None,
+        };
+        let mut failed = budget.clone();
+        failed.name = "router".to_string();
+        failed.spent = None;
+        failed.used_percent = None;
+        failed.projected = None;
+        failed.error = Some("no spend reported by OpenRouter".to_string());
+
+        let output = render_budgets(&[budget, failed], false);
+        assert!(output.contains("Budgets"));
+        assert!(output.contains("$250.00 / $500.00 (Monthly) [██████░░░░░░]"));
+        assert!(output.contains("Projected $600.00 by Oct 31 (on pace to exceed)"));
+        assert!(output.contains("no spend reported by OpenRouter"));
+    }
+
+    #[test]
+    fn render_value_report() {
+        use crate::core::value::PeriodValue;
+        use chrono::NaiveDate;
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let report = ValueReport {
+            provider: Provider::Claude,
+            user: Some("user@example.com".to_string()),
+            plan: Some("max".to_string()),
+            price: Some(100.0),
+            currency: "USD".to_string(),
+            periods: vec![
+                PeriodValue {
+                    start: date(10, 1),
+                    end: date(11, 1),
+                    api_cost: 250.0,
+                    ratio: Some(2.5),
+                    projected_cost: Some(430.0),
+                },
+                PeriodValue {
+                    start: date(9, 1),
+                    end: date(10, 1),
+                    api_cost: 80.0,
+                    ratio: Some(0.8),
+                    projected_cost: None,
+                },
+            ],
+            trend_percent: Some(437.5),
+        };
+        let output = render_value(&report, false);
+        assert!(output.contains("Claude (max, user@example.com)"));
+        assert!(output.contains("$100.00 / period"));
+        assert!(output.contains("Oct 2026"));
+        assert!(output.contains("2.50x"));
+        assert!(output.contains("(projected $430.00)"));
+        assert!(output.contains("0.80x"));
+        assert!(output.contains("+438% vs previous period"));
+
+        let mut unknown = report.clone();
+        unknown.price = None;
+        assert!(render_value(&unknown, false).contains("unknown price"));
+    }
+
+    #[test]
+    fn render_doctor_report() {
+        use crate::core::cost::scanner::LogDir;
+        use crate::core::doctor::*;
+        use std::path::PathBuf;
+
+        let report = DoctorReport {
+            config: ConfigDiagnosis {
+                path: PathBuf::from("/home/u/.config/ait/config.toml"),
+                exists: true,
+                issues: vec![],
+            },
+            providers: vec![
+                ProviderDiagnosis {
+                    provider: Provider::Claude,
+                    enabled: true,
+                    implemented: true,
+                    checks: vec![
+                        Check {
+                            kind: CheckKind::File,
+                            name: "/home/u/.claude/.credentials.json".to_string(),
+                            status: CheckStatus::Found,
+                            detail: None,
+                        },
+                        Check {
+                            kind: CheckKind::Token,
+                            name: "accessToken".to_string(),
+                            status: CheckStatus::Expired,
+                            detail: Some("expired 2h 5m ago".to_string()),
+                        },
+                    ],
+                },
+                ProviderDiagnosis {
+                    provider: Provider::Warp,
+                    enabled: false,
+                    implemented: true,
+                    checks: vec![Check {
+                        kind: CheckKind::Env,
+                        name: "WARP_TOKEN".to_string(),
+                        status: CheckStatus::Missing,
+                        detail: None,
+                    }],
+                },
+            ],
+            log_dirs: vec![LogDir {
+                provider: Provider::Codex,
+                path: PathBuf::from("/home/u/.codex/sessions"),
+                exists: true,
+                files: 12,
+            }],
+            cache: CacheDiagnosis {
+                path: PathBuf::from("/home/u/.cache/ait/cost-cache.json"),
+                exists: false,
+                size_bytes: None,
+                version: None,
+                expected_version: 6,
+                files: 0,
+                records: 0,
+                error: None,
+            },
+        };
+        let output = render_doctor(&report, false);
+        assert!(output.contains("config.toml  valid"));
+        assert!(output.contains("Claude (enabled)"));
+        assert!(output.contains("! token     accessToken  expired 2h 5m ago"));
+        assert!(output.contains("✗ env       WARP_TOKEN  not set"));
+        assert!(output.contains("Credentials need attention"));
+        assert!(output.contains("No credentials found"));
+        assert!(output.contains("/home/u/.codex/sessions  12 files"));
+        assert!(output.contains("not created yet"));
+    }
+
     #[test]
     fn render_no_ansi_when_color_false() {
         let snapshot = make_snapshot();
-        let output = render_provider(&snapshot, None, None, None, false, false);
+        let output = render_provider(&snapshot, None, None, None, None, false, false);
         // ANSI escape sequences start with ESC (0x1b)
         assert!(!output.contains('\x1b'), "output should not contain ANSI codes");
     }
@@ -343,17 +1038,89 @@ mod tests {
             days: 30,
             by_model: vec![],
             daily: vec![],
+            unpriced_models: vec![],
+            converted: None,
+            by_workspace: Vec::new(),
+            by_api_key: Vec::new(),
         };
         // Compact mode (default)
-        let output = render_provider(&snapshot, None, Some(&cost), None, false, false);
+        let output = render_provider(&snapshot, None, Some(&cost), None, None, false, false);
         assert!(output.contains("Cost(30d)"));
         assert!(output.contains("$45.67 total"));
         assert!(output.contains("$3.21 today"));
 
         // Detailed mode (--all)
-        let output_all = render_provider(&snapshot, None, Some(&cost), None, true, false);
+        let output_all = render_provider(&snapshot, None, Some(&cost), None, None, true, false);
         assert!(output_all.contains("Cost(30d)"));
         assert!(output_all.contains("$45.67"));
         assert!(output_all.contains("Today"));
+        assert!(!output_all.contains("Unpriced"));
+    }
+
+    #[test]
+    fn render_warns_about_unpriced_models() {
+        use crate::core::models::cost::TokenCostSnapshot;
+        let snapshot = make_snapshot();
+        let cost = CostSummary {
+            total_cost: 1.0,
+            today_cost: 0.0,
+            days: 30,
+            by_model: vec![TokenCostSnapshot {
+                model: "mystery-model".to_string(),
+                input_tokens: 1_500,
+                output_tokens: 200,
+                cache_read_tokens: 0,
+                cache_creation_tokens: 0,
+                input_cost: 0.0,
+                output_cost: 0.0,
+                cache_read_cost: 0.0,
+                cache_creation_cost: 0.0,
+                total_cost: 0.0,
+                priced: false,
+            }],
+            daily: vec![],
+            unpriced_models: vec!["mystery-model".to_string()],
+            converted: None,
+            by_workspace: Vec::new(),
+            by_api_key: Vec::new(),
+        };
+        let output = render_provider(&snapshot, None, Some(&cost), None, None, false, false);
+        assert!(output.contains("Unpriced"));
+        assert!(output.contains("mystery-model"));
+
+        let output_all = render_provider(&snapshot, None, Some(&cost), None, None, true, false);
+        assert!(output_all.contains("n/a"));
+    }
+
+    #[test]
+    fn render_org_cost_groups_in_detailed_mode() {
+        use crate::core::models::cost::GroupCost;
+        let snapshot = make_snapshot();
+        let group = |id: &str, total_cost: f64| GroupCost {
+            id: id.to_string(),
+            input_tokens: 2_000_000,
+            output_tokens: 150_000,
+            total_cost,
+        };
+        let cost = CostSummary {
+            total_cost: 12.5,
+            today_cost: 1.0,
+            days: 30,
+            by_model: vec![],
+            daily: vec![],
+            unpriced_models: vec![],
+            converted: None,
+            by_workspace: vec![group("wrkspc_prod", 12.5)],
+            by_api_key: vec![group("apikey_ci", 8.0), group("apikey_dev", 4.5)],
+        };
+        let output = render_provider(&snapshot, None, Some(&cost), None, None, false, false);
+        assert!(!output.contains("By Workspace"));
+
+        let output_all = render_provider(&snapshot, None, Some(&cost), None, None, true, false);
+        assert!(output_all.contains("By Workspace"));
+        assert!(output_all.contains("wrkspc_prod"));
+        assert!(output_all.contains("By API Key"));
+        assert!(output_all.contains("apikey_dev"));
+        assert!(output_all.contains("$8.00"));
     }
 }
@@ -0,0 +1,9 @@
+use anyhow::Result;
+
+use crate::core::models::report;
+
+/// Print the JSON Schema of `ait usage --json` output.
+pub fn run() -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&report::usage_report_schema())?);
+    Ok(())
+}
@@ -46,7 +46,7 @@ pub fn interactive_select(items: &[SelectableProvider]) -> anyhow::Result<Option
     let mut checked: Vec<bool> = items.iter().map(|i| i.detected).collect();
     let mut cursor_pos: usize = 0;
 
-    draw(&items, &checked, cursor_pos)?;
+    draw(items, &checked, cursor_pos)?;
 
     loop {
         if let Event::Key(KeyEvent {
@@ -63,14 +63,12 @@ pub fn interactive_select(items: &[SelectableProvider]) -> anyhow::Result<Option
                     anyhow::bail!("cancelled");
                 }
                 (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
-                    if cursor_pos > 0 {
-                        cursor_pos -= 1;
-                    }
+                    cursor_pos = cursor_pos.saturating_sub(1);
                 }
-                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
-                    if cursor_pos + 1 < items.len() {
-                        cursor_pos += 1;
-                    }
+                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::NONE)
+                    if cursor_pos + 1 < items.len() =>
+                {
+                    cursor_pos += 1;
                 }
                 (KeyCode::Char(' '), _) => {
                     checked[cursor_pos] = !checked[cursor_pos];
@@ -93,7 +91,7 @@ pub fn interactive_select(items: &[SelectableProvider]) -> anyhow::Result<Option
                 }
                 _ => {}
             }
-            draw(&items, &checked, cursor_pos)?;
+            draw(items, &checked, cursor_pos)?;
         }
     }
 }
@@ -220,6 +218,38 @@ pub fn detect_credentials(provider: &Provider) -> bool {
         }
         Provider::Antigravity => false, // Requires running language server, no static check
         Provider::Synthetic => std::env::var("SYNTHETIC_API_KEY").is_ok(),
+        Provider::Cursor => crate::core::providers::cursor::state_db_path().exists(),
+        Provider::Ollama => std::env::var("OLLAMA_API_KEY").is_ok() || which_exists("ollama"),
+        Provider::VertexAi => crate::core::providers::vertex_ai::credentials_path().exists(),
+        Provider::Bedrock => {
+            std::env::var("AWS_ACCESS_KEY_ID").is_ok()
+                || crate::core::aws::credentials_path().exists()
+        }
+        Provider::Augment => {
+            let config = crate::core::config::AppConfig::load().unwrap_or_default();
+            std::env::var("AUGMENT_SESSION_AUTH").is_ok()
+                || crate::core::providers::augment::session_path(&config).exists()
+        }
+        Provider::Factory => {
+            let config = crate::core::config::AppConfig::load().unwrap_or_default();
+            std::env::var("FACTORY_API_KEY").is_ok()
+                || crate::core::providers::factory::auth_path(&config).exists()
+        }
+        Provider::AnthropicAdmin => std::env::var("ANTHROPIC_ADMIN_KEY").is_ok(),
+        Provider::OpenAiAdmin => std::env::var("OPENAI_ADMIN_KEY").is_ok(),
+        Provider::DeepSeek => std::env::var("DEEPSEEK_API_KEY").is_ok(),
+        Provider::Mistral => std::env::var("MISTRAL_API_KEY").is_ok(),
+        Provider::Groq => std::env::var("GROQ_API_KEY").is_ok(),
+        Provider::Xai => std::env::var("XAI_API_KEY").is_ok(),
+        Provider::Together => std::env::var("TOGETHER_API_KEY").is_ok(),
+        Provider::GenericHttp => {
+            let config = crate::core::config::AppConfig::load().unwrap_or_default();
+            !crate::core::providers::generic_http::configured(&config).is_empty()
+        }
+        Provider::LiteLlm => {
+            crate::core::providers::litellm::proxy_url().is_some()
+                && std::env::var(crate::core::providers::litellm::API_KEY_ENV).is_ok()
+        }
         _ => false, // Stubs
     }
 }
@@ -289,7 +319,7 @@ mod tests {
     #[test]
     fn build_selectable_list_excludes_stubs() {
         let items = build_selectable_list();
-        assert_eq!(items.len(), 14);
+        assert_eq!(items.len(), 29);
     }
 
     #[test]
@@ -299,8 +329,22 @@ mod tests {
         assert!(ids.contains(&"claude"));
         assert!(ids.contains(&"codex"));
         assert!(ids.contains(&"synthetic"));
-        assert!(!ids.contains(&"cursor"));
-        assert!(!ids.contains(&"ollama"));
+        assert!(ids.contains(&"cursor"));
+        assert!(ids.contains(&"ollama"));
+        assert!(ids.contains(&"vertex_ai"));
+        assert!(ids.contains(&"bedrock"));
+        assert!(ids.contains(&"augment"));
+        assert!(ids.contains(&"factory"));
+        assert!(ids.contains(&"anthropic_admin"));
+        assert!(ids.contains(&"openai_admin"));
+        assert!(ids.contains(&"deepseek"));
+        assert!(ids.contains(&"mistral"));
+        assert!(ids.contains(&"groq"));
+        assert!(ids.contains(&"xai"));
+        assert!(ids.contains(&"together"));
+        assert!(ids.contains(&"generic_http"));
+        assert!(ids.contains(&"litellm"));
+        assert!(!ids.contains(&"amp"));
     }
 
     #[test]
@@ -318,6 +362,6 @@ mod tests {
     fn auto_detect_providers_returns_vec() {
         // Just verify it runs without panic — actual detection depends on environment
         let detected = auto_detect_providers();
-        assert!(detected.len() <= 14);
+        assert!(detected.len() <= 29);
     }
 }
@@ -1,64 +1,93 @@
 use anyhow::Result;
-use serde::Serialize;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 use crate::cli::output::{OutputFormat, OutputOptions};
 use crate::cli::renderer;
+use crate::core::budget::{self, BudgetStatus};
 use crate::core::config::AppConfig;
+use crate::core::currency::CurrencyConverter;
+use crate::core::daemon;
+use crate::core::http::HttpClient;
+use crate::core::models::cost::CostSummary;
 use crate::core::models::credits::CreditsSnapshot;
-use crate::core::models::status::StatusInfo;
-use crate::core::models::usage::UsageSnapshot;
+use crate::core::models::report::{ProviderCost, ProviderFailure, ProviderReport, UsageReport};
+use crate::core::providers::error::{ErrorKind, ErrorReport, ProviderError};
 use crate::core::providers::Provider;
+use crate::core::usage_cache::{self, CacheMode};
 
-#[derive(Serialize)]
-struct ProviderPayload {
-    #[serde(flatten)]
-    usage: UsageSnapshot,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    credits: Option<CreditsSnapshot>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    cost: Option<crate::core::models::cost::CostSummary>,
-}
-
-fn dispatch_fetch(
+pub(crate) fn dispatch_fetch(
     provider: Provider,
+    http: &HttpClient,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = anyhow::Result<crate::core::providers::fetch::FetchResult>> + Send>>
 {
     use crate::core::providers::*;
+    let http = http.for_provider(provider);
     Box::pin(async move {
+        let http = &http;
         match provider {
-            Provider::Claude => claude::fetch().await,
-            Provider::Codex => codex::fetch().await,
-            Provider::Copilot => copilot::fetch().await,
-            Provider::Warp => warp::fetch().await,
-            Provider::Kimi => kimi::fetch().await,
-            Provider::KimiK2 => kimi_k2::fetch().await,
-            Provider::OpenRouter => openrouter::fetch().await,
-            Provider::MiniMax => minimax::fetch().await,
-            Provider::Zai => zai::fetch().await,
-            Provider::Ollama => ollama::fetch().await,
-            Provider::Gemini => gemini::fetch().await,
-            Provider::Kiro => kiro::fetch().await,
-            Provider::Augment => augment::fetch().await,
-            Provider::JetBrains => jetbrains::fetch().await,
-            Provider::Cursor => cursor::fetch().await,
-            Provider::OpenCode => opencode::fetch().await,
-            Provider::Factory => factory::fetch().await,
-            Provider::Amp => amp::fetch().await,
-            Provider::Antigravity => antigravity::fetch().await,
-            Provider::Synthetic => synthetic::fetch().await,
-            Provider::VertexAi => vertex_ai::fetch().await,
+            Provider::Claude => claude::fetch(http).await,
+            Provider::Codex => codex::fetch(http).await,
+            Provider::Copilot => copilot::fetch(http).await,
+            Provider::Warp => warp::fetch(http).await,
+            Provider::Kimi => kimi::fetch(http).await,
+            Provider::KimiK2 => kimi_k2::fetch(http).await,
+            Provider::OpenRouter => openrouter::fetch(http).await,
+            Provider::MiniMax => minimax::fetch(http).await,
+            Provider::Zai => zai::fetch(http).await,
+            Provider::Ollama => ollama::fetch(http).await,
+            Provider::Gemini => gemini::fetch(http).await,
+            Provider::Kiro => kiro::fetch(http).await,
+            Provider::Augment => augment::fetch(http).await,
+            Provider::JetBrains => jetbrains::fetch(http).await,
+            Provider::Cursor => cursor::fetch(http).await,
+            Provider::OpenCode => opencode::fetch(http).await,
+            Provider::Factory => factory::fetch(http).await,
+            Provider::Amp => amp::fetch(http).await,
+            Provider::Antigravity => antigravity::fetch(http).await,
+            Provider::Synthetic => synthetic::fetch(http).await,
+            Provider::VertexAi => vertex_ai::fetch(http).await,
+            Provider::Bedrock => bedrock::fetch(http).await,
+            Provider::AnthropicAdmin => anthropic_admin::fetch(http).await,
+            Provider::OpenAiAdmin => openai_admin::fetch(http).await,
+            Provider::DeepSeek => deepseek::fetch(http).await,
+            Provider::Mistral => mistral::fetch(http).await,
+            Provider::Groq => groq::fetch(http).await,
+            Provider::Xai => xai::fetch(http).await,
+            Provider::Together => together::fetch(http).await,
+            Provider::GenericHttp => generic_http::fetch(http).await,
+            Provider::LiteLlm => litellm::fetch(http).await,
         }
     })
 }
 
+/// `dispatch_fetch` bounded by the `--timeout` deadline, if one is set.
+pub(crate) async fn fetch_with_timeout(
+    provider: Provider,
+    http: &HttpClient,
+    timeout: Option<std::time::Duration>,
+) -> anyhow::Result<crate::core::providers::fetch::FetchResult> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, dispatch_fetch(provider, http))
+            .await
+            .unwrap_or_else(|_| {
+                Err(ProviderError::Timeout(format!("timed out after {}s", timeout.as_secs())).into())
+            }),
+        None => dispatch_fetch(provider, http).await,
+    }
+}
+
 pub async fn run(
     provider_filter: Option<String>,
     _source: Option<String>,
     fetch_status: bool,
     show_all: bool,
+    cache_mode: CacheMode,
     opts: &OutputOptions,
 ) -> Result<()> {
     let config = AppConfig::load().unwrap_or_default();
+    let http = HttpClient::from_config(&config)?;
 
     // Determine which providers to fetch
     let providers: Vec<Provider> = if let Some(filter) = &provider_filter {
@@ -95,33 +124,48 @@ pub async fn run(
         return Ok(());
     }
 
+    // A running daemon already holds recent data; fetch directly without one.
+    // `--refresh`, `--no-cache` and `--status` always go to the providers.
+    if cache_mode == CacheMode::Normal && !fetch_status {
+        if let Some(report) = daemon::query_usage(&providers).await {
+            if opts.verbose {
+                eprintln!("Using ait daemon at {}", daemon::socket_path().display());
+            }
+            return print_report(&report, show_all, opts);
+        }
+    }
+
     // Spawn cost scan concurrently if any cost-scannable provider is requested
-    let has_cost_provider = providers.iter().any(|p| {
-        matches!(p, Provider::Claude | Provider::Codex | Provider::VertexAi)
-    });
-    let cost_handle = if has_cost_provider {
-        Some(tokio::task::spawn_blocking(|| {
-            crate::core::cost::scanner::scan(30).ok()
-        }))
-    } else {
-        None
-    };
+    // or a budget is tracked against session logs
+    let has_cost_provider = providers.iter().any(|p| p.has_cost_logs());
+    let cost_query = budget::CostQuery::new(&config, chrono::Local::now().date_naive());
+    let has_cost_budget = cost_query.is_some();
+    let mut cost_handle =
+        (has_cost_provider || has_cost_budget).then(|| spawn_cost_scan(cost_query));
+    let mut scanned: Option<ScannedCost> = None;
+    // Spend reported by the providers' own APIs rather than session logs
+    let mut reported_cost: HashMap<Provider, CostSummary> = HashMap::new();
+    let fx = CurrencyConverter::from_config(&config);
+
+    // Providers still being fetched, shared with the spinner. Holding the lock
+    // while drawing keeps spinner frames and streamed sections from interleaving.
+    let pending = Arc::new(Mutex::new(providers.clone()));
 
     // Show spinner on stderr (text mode only)
     let show_spinner = matches!(opts.format, OutputFormat::Text);
     let spinner = if show_spinner {
         let is_cold_cache =
             has_cost_provider && !crate::core::cost::cache::CostCache::has_warm_cache();
-        let msg: &'static str = if is_cold_cache {
-            "First scan, indexing session files..."
-        } else {
-            "Fetching usage data..."
-        };
+        let pending = Arc::clone(&pending);
         Some(tokio::spawn(async move {
             let frames = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
             let mut i = 0usize;
             loop {
-                eprint!("\r {} {}", frames[i % frames.len()], msg);
+                {
+                    let pending = pending.lock().unwrap();
+                    let msg = spinner_message(&pending, is_cold_cache);
+                    eprint!("\r\x1b[2K {} {}", frames[i % frames.len()], msg);
+                }
                 i = i.wrapping_add(1);
                 tokio::time::sleep(std::time::Duration::from_millis(80)).await;
             }
@@ -131,43 +175,133 @@ pub async fn run(
     };
 
     // Fetch all providers concurrently
-    let handles: Vec<_> = providers
-        .into_iter()
-        .map(|provider| {
-            let should_fetch_status = fetch_status;
-            tokio::spawn(async move {
-                let result = dispatch_fetch(provider).await;
-                let status = if should_fetch_status {
-                    crate::core::status::fetch_status(&provider).await.ok()
-                } else {
-                    None
-                };
-                (provider, result, status)
-            })
-        })
-        .collect();
+    // Recent results are reused from the response cache to spare upstream APIs
+    let cache_ttl = config.settings.cache_ttl_secs;
+    let cache_dir = usage_cache::cache_dir();
+    let mut tasks = tokio::task::JoinSet::new();
+    for &provider in &providers {
+        let should_fetch_status = fetch_status;
+        let http = http.clone();
+        let cache_dir = cache_dir.clone();
+        tasks.spawn(async move {
+            let cached = if cache_mode.reads() {
+                usage_cache::load(&cache_dir, provider, cache_ttl, Utc::now())
+            } else {
+                None
+            };
+            let fetched = match cached {
+                Some(entry) => (entry.fetched_at, true, Ok(entry.into_result())),
+                None => {
+                    let fetched_at = Utc::now();
+                    let result = dispatch_fetch(provider, &http).await;
+                    if let (true, Ok(result)) = (cache_mode.writes() && cache_ttl > 0, &result) {
+                        // Best effort: a read-only cache dir only costs a refetch
+                        let _ = usage_cache::store(&cache_dir, provider, result, fetched_at);
+                    }
+                    (fetched_at, false, result)
+                }
+            };
+            let status = if should_fetch_status {
+                crate::core::status::fetch_status(&http, &provider).await.ok()
+            } else {
+                None
+            };
+            (provider, fetched, status)
+        });
+    }
+    let deadline = opts.timeout.map(|t| tokio::time::Instant::now() + t);
+
+    // In text mode each provider is printed as soon as it completes
+    let stream = matches!(opts.format, OutputFormat::Text);
+    let mut printed_any = false;
 
-    let mut results: Vec<(Provider, UsageSnapshot, Option<CreditsSnapshot>, Option<StatusInfo>)> =
-        Vec::new();
-    let mut errors: Vec<(Provider, String)> = Vec::new();
+    let mut results: Vec<ProviderReport> = Vec::new();
+    let mut errors: Vec<(Provider, ErrorReport)> = Vec::new();
+    let mut timed_out: Vec<Provider> = Vec::new();
+    let mut cost_timed_out = false;
+
+    loop {
+        let next = match deadline {
+            Some(deadline) => match tokio::time::timeout_at(deadline, tasks.join_next()).await {
+                Ok(next) => next,
+                Err(_) => {
+                    tasks.abort_all();
+                    timed_out = pending.lock().unwrap().clone();
+                    break;
+                }
+            },
+            None => tasks.join_next().await,
+        };
+        let Some(joined) = next else { break };
+        let (provider, (updated_at, cached, result), status) = joined?;
+        pending.lock().unwrap().retain(|p| *p != provider);
 
-    for handle in handles {
-        let (provider, result, status) = handle.await?;
         match result {
-            Ok(fetch_result) => {
-                results.push((provider, fetch_result.usage, fetch_result.credits, status));
+            Ok(mut fetch_result) => {
+                let mut credits = fetch_result.credits;
+                if let (Some(code), Some(credits)) = (config.credits_currency(provider), &mut credits) {
+                    credits.currency = Some(code.to_string());
+                }
+                // Attach display-currency amounts; originals are kept alongside in JSON
+                if let (Some(fx), Some(credits)) = (&fx, &mut credits) {
+                    fx.convert_credits(credits);
+                }
+                if let Some(fx) = &fx {
+                    fx.convert_endpoints(&mut fetch_result.usage);
+                }
+                let mut cost = fetch_result.cost;
+                if let (Some(fx), Some(cost)) = (&fx, &mut cost) {
+                    fx.convert_cost(cost);
+                }
+                if stream {
+                    if provider.has_cost_logs() {
+                        if let Some(handle) = cost_handle.take() {
+                            match finish_cost_scan(handle, fx.as_ref(), deadline).await {
+                                Ok(result) => scanned = result,
+                                Err(_) => cost_timed_out = true,
+                            }
+                        }
+                    }
+                    let text = renderer::render_provider(
+                        &fetch_result.usage,
+                        credits.as_ref(),
+                        cost.as_ref()
+                            .or_else(|| scanned.as_ref().and_then(|s| s.costs.get(&provider))),
+                        status.as_ref(),
+                        cached.then_some(&updated_at),
+                        show_all,
+                        opts.use_color,
+                    );
+                    print_section(&pending, &mut printed_any, &text);
+                }
+                if let Some(cost) = cost {
+                    reported_cost.insert(provider, cost);
+                }
+                results.push(ProviderReport::new(
+                    fetch_result.usage,
+                    credits,
+                    status,
+                    updated_at,
+                    cached,
+                ));
             }
             Err(e) => {
-                errors.push((provider, format!("{:#}", e)));
+                let report = ErrorReport::from_error(&e);
+                if stream {
+                    let text = render_problem(provider, "error", &report.message, opts.use_color);
+                    print_section(&pending, &mut printed_any, &text);
+                }
+                errors.push((provider, report));
             }
         }
     }
 
-    let cost_map: Option<std::collections::HashMap<Provider, crate::core::models::cost::CostSummary>> =
-        match cost_handle {
-            Some(handle) => handle.await.unwrap_or(None),
-            None => None,
-        };
+    if let Some(handle) = cost_handle.take() {
+        match finish_cost_scan(handle, fx.as_ref(), deadline).await {
+            Ok(result) => scanned = result,
+            Err(_) => cost_timed_out = true,
+        }
+    }
 
     // Stop spinner and clear the line
     if let Some(s) = spinner {
@@ -175,65 +309,360 @@ pub async fn run(
         eprint!("\r\x1b[2K");
     }
 
+    let timeout_msg = opts
+        .timeout
+        .map(|t| format!("No response within {}s", t.as_secs()))
+        .unwrap_or_default();
+    let cost_timeout_msg = opts
+        .timeout
+        .map(|t| format!("Session logs not scanned within {}s", t.as_secs()))
+        .unwrap_or_default();
+
+    // Cost budgets share the scan above, so they are bound by the same deadline
+    let budget_spend = match &scanned {
+        _ if !has_cost_budget => Ok(Vec::new()),
+        _ if cost_timed_out => Err(cost_timeout_msg.clone()),
+        Some(scanned) => Ok(scanned.budget_spend.clone()),
+        None => Err("Failed to scan session logs".to_string()),
+    };
+    let mut cost_map = scanned.map(|s| s.costs);
+    if !reported_cost.is_empty() {
+        cost_map.get_or_insert_with(HashMap::new).extend(reported_cost);
+    }
+
     match opts.format {
         OutputFormat::Text => {
-            let mut sections: Vec<String> = Vec::new();
-
-            for (provider, usage, credits, status) in &results {
-                let provider_cost = cost_map
-                    .as_ref()
-                    .and_then(|m| m.get(provider));
-                let text = renderer::render_provider(
-                    usage,
-                    credits.as_ref(),
-                    provider_cost,
-                    status.as_ref(),
-                    show_all,
-                    opts.use_color,
-                );
-                sections.push(text);
+            for provider in &timed_out {
+                let text = render_problem(*provider, "timed out", &timeout_msg, opts.use_color);
+                print_section(&pending, &mut printed_any, &text);
+            }
+            if cost_timed_out && has_cost_provider {
+                let text = render_section("Cost", "timed out", &cost_timeout_msg, opts.use_color);
+                print_section(&pending, &mut printed_any, &text);
             }
 
-            for (provider, err) in &errors {
-                let header = format!(" {} (error)", provider.display_name());
-                let msg = format!("  {}", err);
-                if opts.use_color {
-                    use colored::Colorize;
-                    colored::control::set_override(true);
-                    sections.push(format!("{}\n{}", header.bold(), msg.red()));
-                } else {
-                    sections.push(format!("{}\n{}", header, msg));
+            let budgets = evaluate_budgets(&config, &results, &budget_spend);
+            if let Some(text) = render_budgets(&budgets, opts.use_color) {
+                print_section(&pending, &mut printed_any, &text);
+            }
+        }
+        OutputFormat::Json => {
+            if opts.verbose {
+                for (provider, err) in &errors {
+                    eprintln!("Error fetching {}: {}", provider.display_name(), err.message);
                 }
             }
 
-            println!("{}", sections.join("\n\n"));
+            // Costs of providers whose session logs were not scanned in time
+            if cost_timed_out {
+                errors.extend(providers.iter().filter(|p| p.has_cost_logs()).map(|&p| {
+                    let error = ErrorReport {
+                        kind: ErrorKind::Timeout,
+                        message: cost_timeout_msg.clone(),
+                        status: None,
+                    };
+                    (p, error)
+                }));
+            }
+            let budgets = evaluate_budgets(&config, &results, &budget_spend);
+            let mut report = build_report(
+                &providers,
+                results,
+                errors,
+                &timed_out,
+                &timeout_msg,
+                cost_map,
+            );
+            report.budgets = budgets;
+            let json = if opts.pretty {
+                serde_json::to_string_pretty(&report)?
+            } else {
+                serde_json::to_string(&report)?
+            };
+            println!("{}", json);
         }
-        OutputFormat::Json => {
-            let payloads: Vec<ProviderPayload> = results
-                .into_iter()
-                .map(|(provider, usage, credits, _)| {
-                    let cost = cost_map
-                        .as_ref()
-                        .and_then(|m| m.get(&provider))
-                        .cloned();
-                    ProviderPayload { usage, credits, cost }
-                })
-                .collect();
+    }
+
+    Ok(())
+}
 
+/// Configured budgets evaluated against the fetched credits and the cost
+/// budgets' spend from the session log scan.
+pub(crate) fn evaluate_budgets(
+    config: &AppConfig,
+    results: &[ProviderReport],
+    cost_spend: &Result<Vec<f64>, String>,
+) -> Vec<BudgetStatus> {
+    if config.budgets.is_empty() {
+        return Vec::new();
+    }
+    let credits: HashMap<Provider, CreditsSnapshot> = results
+        .iter()
+        .filter_map(|r| r.credits.clone().map(|c| (r.provider, c)))
+        .collect();
+    budget::evaluate(config, &credits, cost_spend)
+}
+
+/// Budget section, when budgets are configured.
+fn render_budgets(budgets: &[BudgetStatus], use_color: bool) -> Option<String> {
+    (!budgets.is_empty()).then(|| renderer::render_budgets(budgets, use_color))
+}
+
+/// Print a complete report, as received from the daemon, which also
+/// evaluates the budgets.
+fn print_report(report: &UsageReport, show_all: bool, opts: &OutputOptions) -> Result<()> {
+    match opts.format {
+        OutputFormat::Json => {
             let json = if opts.pretty {
-                serde_json::to_string_pretty(&payloads)?
+                serde_json::to_string_pretty(report)?
             } else {
-                serde_json::to_string(&payloads)?
+                serde_json::to_string(report)?
             };
             println!("{}", json);
+        }
+        OutputFormat::Text => {
+            let mut sections: Vec<String> = report
+                .providers
+                .iter()
+                .map(|r| {
+                    let cost = report.cost.iter().find(|c| c.provider == r.provider);
+                    renderer::render_provider(
+                        &r.usage(),
+                        r.credits.as_ref(),
+                        cost.map(|c| &c.summary),
+                        r.status.as_ref(),
+                        r.cached.then_some(&r.updated_at),
+                        show_all,
+                        opts.use_color,
+                    )
+                })
+                .collect();
+            sections.extend(report.errors.iter().map(|f| {
+                let label = match f.error.kind {
+                    ErrorKind::Timeout => "timed out",
+                    _ => "error",
+                };
+                render_problem(f.provider, label, &f.error.message, opts.use_color)
+            }));
+            sections.extend(render_budgets(&report.budgets, opts.use_color));
+            println!("{}", sections.join("\n\n"));
+        }
+    }
+    Ok(())
+}
 
-            if !errors.is_empty() && opts.verbose {
-                for (provider, err) in &errors {
-                    eprintln!("Error fetching {}: {}", provider.display_name(), err);
-                }
-            }
+/// Assemble the versioned JSON envelope. Providers, errors and costs keep the
+/// requested provider order regardless of completion order.
+pub(crate) fn build_report(
+    providers: &[Provider],
+    results: Vec<ProviderReport>,
+    errors: Vec<(Provider, ErrorReport)>,
+    timed_out: &[Provider],
+    timeout_msg: &str,
+    cost_map: Option<HashMap<Provider, CostSummary>>,
+) -> UsageReport {
+    let position = |p: &Provider| providers.iter().position(|q| q == p);
+    let mut report = UsageReport::new(Utc::now());
+
+    report.providers = results;
+    report.providers.sort_by_key(|r| position(&r.provider));
+
+    let timeout_errors = timed_out.iter().map(|&provider| {
+        let error = ErrorReport {
+            kind: ErrorKind::Timeout,
+            message: timeout_msg.to_string(),
+            status: None,
+        };
+        (provider, error)
+    });
+    report.errors = errors
+        .into_iter()
+        .chain(timeout_errors)
+        .map(|(provider, error)| ProviderFailure { provider, error })
+        .collect();
+    report.errors.sort_by_key(|f| position(&f.provider));
+
+    report.cost = cost_map
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(provider, _)| providers.contains(provider))
+        .map(|(provider, summary)| ProviderCost { provider, summary })
+        .collect();
+    report.cost.sort_by_key(|c| position(&c.provider));
+    report
+}
+
+/// Result of the background session log scan.
+#[derive(Debug)]
+pub(crate) struct ScannedCost {
+    /// Cost per provider over the last 30 days
+    pub costs: HashMap<Provider, CostSummary>,
+    /// [`budget::CostQuery::spend`] of the cost-based budgets; empty
+    /// without a query
+    pub budget_spend: Vec<f64>,
+}
+
+type CostScan = tokio::sync::oneshot::Receiver<Option<ScannedCost>>;
+
+/// Start the session log cost scan in the background, reading the spend of
+/// `query`'s budgets in the same pass. It runs on its own thread rather than
+/// `spawn_blocking` so that a scan abandoned at the `--timeout` deadline does
+/// not keep the process alive on exit.
+pub(crate) fn spawn_cost_scan(query: Option<budget::CostQuery>) -> CostScan {
+    use crate::core::cost::scanner;
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    std::thread::spawn(move || {
+        let scanned = match &query {
+            Some(query) => scanner::scan_with_filters(30, &query.filters, query.since()).map(
+                |(costs, summaries)| ScannedCost {
+                    costs,
+                    budget_spend: query.spend(&summaries),
+                },
+            ),
+            None => scanner::scan(30).map(|costs| ScannedCost {
+                costs,
+                budget_spend: Vec::new(),
+            }),
+        };
+        let _ = tx.send(scanned.ok());
+    });
+    rx
+}
+
+/// Await the background cost scan, up to `deadline`, and attach
+/// display-currency totals. Errs when the deadline passes first.
+pub(crate) async fn finish_cost_scan(
+    scan: CostScan,
+    fx: Option<&CurrencyConverter>,
+    deadline: Option<tokio::time::Instant>,
+) -> Result<Option<ScannedCost>, tokio::time::error::Elapsed> {
+    let result = match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, scan).await?,
+        None => scan.await,
+    };
+    let Some(mut scanned) = result.unwrap_or(None) else {
+        return Ok(None);
+    };
+    if let Some(fx) = fx {
+        for cost in scanned.costs.values_mut() {
+            fx.convert_cost(cost);
         }
     }
+    Ok(Some(scanned))
+}
 
-    Ok(())
+fn spinner_message(pending: &[Provider], is_cold_cache: bool) -> String {
+    let names: Vec<&str> = pending.iter().map(|p| p.display_name()).collect();
+    match (is_cold_cache, names.is_empty()) {
+        (true, true) => "First scan, indexing session files...".to_string(),
+        (true, false) => format!(
+            "First scan, indexing session files... waiting for {}",
+            names.join(", ")
+        ),
+        (false, true) => "Scanning session logs...".to_string(),
+        (false, false) => format!("Waiting for {}...", names.join(", ")),
+    }
+}
+
+/// Print one output section, clearing the spinner line first and separating
+/// it from the previous section with a blank line.
+fn print_section(pending: &Mutex<Vec<Provider>>, printed_any: &mut bool, text: &str) {
+    let _guard = pending.lock().unwrap();
+    eprint!("\r\x1b[2K");
+    if *printed_any {
+        println!();
+    }
+    println!("{}", text);
+    *printed_any = true;
+}
+
+/// Section for a provider without usage data, e.g. " Kiro (timed out)".
+fn render_problem(provider: Provider, label: &str, message: &str, use_color: bool) -> String {
+    render_section(provider.display_name(), label, message, use_color)
+}
+
+/// Section with a title, a label and a message line in red.
+fn render_section(title: &str, label: &str, message: &str, use_color: bool) -> String {
+    let header = format!(" {} ({})", title, label);
+    let msg = format!("  {}", message);
+    if use_color {
+        use colored::Colorize;
+        colored::control::set_override(true);
+        format!("{}\n{}", header.bold(), msg.red())
+    } else {
+        format!("{}\n{}", header, msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spinner_message_lists_pending_providers() {
+        let pending = [Provider::Kiro, Provider::Copilot];
+        assert_eq!(spinner_message(&pending, false), "Waiting for Kiro, Copilot...");
+        assert_eq!(
+            spinner_message(&pending[..1], true),
+            "First scan, indexing session files... waiting for Kiro"
+        );
+        assert_eq!(spinner_message(&[], false), "Scanning session logs...");
+    }
+
+    #[test]
+    fn build_report_orders_and_types_errors() {
+        let providers = [Provider::Claude, Provider::Warp, Provider::Kiro];
+        let failed = ErrorReport {
+            kind: ErrorKind::AuthMissing,
+            message: "WARP_TOKEN env var not set".to_string(),
+            status: None,
+        };
+        let report = build_report(
+            &providers,
+            vec![],
+            vec![(Provider::Warp, failed)],
+            &[Provider::Kiro],
+            "No response within 5s",
+            None,
+        );
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["providers"], serde_json::json!([]));
+        assert_eq!(json["errors"][0]["provider"], "warp");
+        assert_eq!(json["errors"][1]["provider"], "kiro");
+        assert_eq!(json["errors"][1]["error"]["kind"], "timeout");
+        assert_eq!(json["errors"][1]["error"]["message"], "No response within 5s");
+        assert!(json["errors"][1]["error"]["status"].is_null());
+    }
+
+    #[tokio::test]
+    async fn stub_provider_is_unsupported() {
+        let http = HttpClient::new(&Default::default()).unwrap();
+        let Err(err) = dispatch_fetch(Provider::Amp, &http).await else {
+            panic!("stub provider should not succeed");
+        };
+        assert_eq!(ErrorReport::from_error(&err).kind, ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn cost_scan_stops_at_deadline() {
+        // A scan that never reports back, like a cold scan of a large log dir
+        let (_tx, rx) = tokio::sync::oneshot::channel();
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_millis(20);
+        assert!(finish_cost_scan(rx, None, Some(deadline)).await.is_err());
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let scanned = ScannedCost {
+            costs: HashMap::new(),
+            budget_spend: vec![1.5],
+        };
+        tx.send(Some(scanned)).unwrap();
+        let scanned = finish_cost_scan(rx, None, Some(deadline)).await.unwrap();
+        assert_eq!(scanned.unwrap().budget_spend, vec![1.5]);
+    }
+
+    #[test]
+    fn render_problem_plain() {
+        let text = render_problem(Provider::Kiro, "timed out", "No response within 5s", false);
+        assert_eq!(text, " Kiro (timed out)\n  No response within 5s");
+    }
 }
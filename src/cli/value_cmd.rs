@@ -0,0 +1,145 @@
+use anyhow::Result;
+use std::collections::HashMap;
+
+use crate::cli::output::{OutputFormat, OutputOptions};
+use crate::cli::renderer;
+use crate::cli::usage_cmd::fetch_with_timeout;
+use crate::core::config::AppConfig;
+use crate::core::http::HttpClient;
+use crate::core::cost::scanner::{self, CostFilter};
+use crate::core::currency::{CurrencyConverter, BASE_CURRENCY};
+use crate::core::models::usage::ProviderIdentity;
+use crate::core::providers::Provider;
+use crate::core::value::{self, ValueReport};
+
+/// Providers sold as subscriptions whose usage shows up in session logs.
+const SUBSCRIPTION_PROVIDERS: &[Provider] = &[Provider::Claude, Provider::Codex];
+
+/// Compare API-equivalent token cost with subscription price per billing period.
+pub async fn run(
+    provider_filter: Option<String>,
+    months: u32,
+    opts: &OutputOptions,
+) -> Result<()> {
+    let config = AppConfig::load().unwrap_or_default();
+    let http = HttpClient::from_config(&config)?;
+
+    let providers: Vec<Provider> = match &provider_filter {
+        Some(id) => match Provider::from_id(id) {
+            Some(p) if SUBSCRIPTION_PROVIDERS.contains(&p) => vec![p],
+            _ => {
+                eprintln!(
+                    "Value reports are available for: {}",
+                    SUBSCRIPTION_PROVIDERS
+                        .iter()
+                        .map(|p| p.id())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                std::process::exit(1);
+            }
+        },
+        None => SUBSCRIPTION_PROVIDERS
+            .iter()
+            .copied()
+            .filter(|p| config.providers.iter().any(|c| c.enabled && c.id == p.id()))
+            .collect(),
+    };
+    if providers.is_empty() {
+        eprintln!("No subscription providers enabled (claude, codex).");
+        return Ok(());
+    }
+
+    // Identity (plan + account) comes from each provider's usage endpoint
+    let handles: Vec<_> = providers
+        .iter()
+        .map(|&provider| {
+            let http = http.clone();
+            let timeout = opts.timeout;
+            tokio::spawn(async move {
+                (provider, fetch_with_timeout(provider, &http, timeout).await)
+            })
+        })
+        .collect();
+    let mut identities: HashMap<Provider, ProviderIdentity> = HashMap::new();
+    for handle in handles {
+        let (provider, result) = handle.await?;
+        match result {
+            Ok(fetched) => {
+                if let Some(identity) = fetched.usage.identity {
+                    identities.insert(provider, identity);
+                }
+            }
+            Err(e) => {
+                if opts.verbose {
+                    eprintln!("Error fetching {}: {:#}", provider.display_name(), e);
+                }
+            }
+        }
+    }
+
+    let plans: Vec<Option<value::PlanPrice>> = providers
+        .iter()
+        .map(|p| {
+            let plan = identities.get(p).and_then(|i| i.plan.as_deref());
+            value::lookup_plan(&config.plans, *p, plan)
+        })
+        .collect();
+
+    // Scan far enough back to cover the oldest requested period of every provider
+    let now = chrono::Local::now().naive_local();
+    let since = plans
+        .iter()
+        .map(|plan| {
+            let billing_day = plan.as_ref().and_then(|p| p.billing_day).unwrap_or(1);
+            value::billing_period(now.date(), billing_day, months.saturating_sub(1)).0
+        })
+        .min()
+        .unwrap_or(now.date());
+    let filters: Vec<CostFilter> = providers
+        .iter()
+        .map(|p| CostFilter {
+            provider: Some(*p),
+            ..Default::default()
+        })
+        .collect();
+    let summaries =
+        tokio::task::spawn_blocking(move || scanner::scan_filtered(&filters, since)).await??;
+
+    let fx = config
+        .settings
+        .display_currency
+        .as_deref()
+        .map(|code| CurrencyConverter::new(code, &config.exchange_rates))
+        .filter(|fx| fx.rate(BASE_CURRENCY).is_some())
+        .unwrap_or_else(|| CurrencyConverter::new(BASE_CURRENCY, &config.exchange_rates));
+
+    let reports: Vec<ValueReport> = providers
+        .iter()
+        .zip(plans.iter())
+        .zip(summaries.iter())
+        .map(|((provider, plan), summary)| {
+            let user = identities.get(provider).and_then(|i| i.email.clone());
+            value::build_report(*provider, user, plan.as_ref(), &fx, months, summary, now)
+        })
+        .collect();
+
+    match opts.format {
+        OutputFormat::Json => {
+            let json = if opts.pretty {
+                serde_json::to_string_pretty(&reports)?
+            } else {
+                serde_json::to_string(&reports)?
+            };
+            println!("{}", json);
+        }
+        OutputFormat::Text => {
+            let sections: Vec<String> = reports
+                .iter()
+                .map(|r| renderer::render_value(r, opts.use_color))
+                .collect();
+            println!("{}", sections.join("\n\n"));
+        }
+    }
+    Ok(())
+}
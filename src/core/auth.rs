@@ -2,6 +2,12 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::path::PathBuf;
 
+use crate::core::providers::error::ProviderError;
+
+fn missing(message: &str) -> ProviderError {
+    ProviderError::AuthMissing(message.to_string())
+}
+
 // --- Claude credentials ---
 
 #[derive(Deserialize)]
@@ -30,17 +36,17 @@ pub fn read_claude_credentials() -> Result<ClaudeCredentials> {
         .with_context(|| "Failed to parse Claude credentials JSON")?;
     let oauth = file
         .claude_ai_oauth
-        .context("Missing 'claudeAiOauth' in credentials file")?;
+        .ok_or_else(|| missing("Missing 'claudeAiOauth' in credentials file"))?;
     let token = oauth
         .access_token
-        .context("Missing 'accessToken' in credentials")?;
+        .ok_or_else(|| missing("Missing 'accessToken' in credentials"))?;
     if token.is_empty() {
-        anyhow::bail!("Empty access token in Claude credentials");
+        return Err(missing("Empty access token in Claude credentials").into());
     }
     Ok(ClaudeCredentials { access_token: token })
 }
 
-fn claude_credentials_path() -> PathBuf {
+pub fn claude_credentials_path() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("~"))
         .join(".claude")
@@ -84,9 +90,9 @@ pub fn read_codex_credentials() -> Result<CodexCredentials> {
     if let Some(tokens) = file.tokens {
         let token = tokens
             .access_token
-            .context("Missing 'access_token' in Codex tokens")?;
+            .ok_or_else(|| missing("Missing 'access_token' in Codex tokens"))?;
         if token.is_empty() {
-            anyhow::bail!("Empty access token in Codex credentials");
+            return Err(missing("Empty access token in Codex credentials").into());
         }
         return Ok(CodexCredentials {
             access_token: token,
@@ -103,10 +109,10 @@ pub fn read_codex_credentials() -> Result<CodexCredentials> {
         }
     }
 
-    anyhow::bail!("No valid credentials found in Codex auth file")
+    Err(missing("No valid credentials found in Codex auth file").into())
 }
 
-fn codex_auth_path() -> PathBuf {
+pub fn codex_auth_path() -> PathBuf {
     std::env::var("CODEX_HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| {
@@ -122,7 +128,11 @@ fn codex_auth_path() -> PathBuf {
 pub fn decode_jwt_claims(token: &str) -> Result<serde_json::Value> {
     let parts: Vec<&str> = token.split('.').collect();
     if parts.len() != 3 {
-        anyhow::bail!("Invalid JWT: expected 3 parts, got {}", parts.len());
+        return Err(ProviderError::Parse(format!(
+            "Invalid JWT: expected 3 parts, got {}",
+            parts.len()
+        ))
+        .into());
     }
     let payload = parts[1];
     use base64::Engine;
@@ -179,8 +189,8 @@ mod tests {
         let result = read_claude_credentials();
         // In CI the file won't exist, so we expect an error about reading the file.
         // If it happens to exist on the dev machine, that's fine too.
-        if result.is_err() {
-            let msg = result.unwrap_err().to_string();
+        if let Err(e) = result {
+            let msg = e.to_string();
             assert!(!msg.is_empty(), "Error message should not be empty");
         }
     }
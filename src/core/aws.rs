@@ -0,0 +1,349 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Region used when neither the config, the environment nor the AWS config
+/// file names one.
+pub const DEFAULT_REGION: &str = "us-east-1";
+
+/// Static AWS credentials from the environment or the shared files.
+#[derive(Clone)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+/// Profile from `AWS_PROFILE`, else `default`.
+pub fn profile() -> String {
+    std::env::var("AWS_PROFILE")
+        .ok()
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| "default".to_string())
+}
+
+fn aws_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("~"))
+        .join(".aws")
+}
+
+/// `AWS_SHARED_CREDENTIALS_FILE`, else `~/.aws/credentials`.
+pub fn credentials_path() -> PathBuf {
+    std::env::var("AWS_SHARED_CREDENTIALS_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| aws_dir().join("credentials"))
+}
+
+/// `AWS_CONFIG_FILE`, else `~/.aws/config`.
+pub fn config_path() -> PathBuf {
+    std::env::var("AWS_CONFIG_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| aws_dir().join("config"))
+}
+
+/// Sections of an INI file, keyed by the section name as written.
+fn parse_ini(content: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.trim().to_string();
+            sections.entry(name.clone()).or_default();
+            current = Some(name);
+        } else if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+    sections
+}
+
+fn read_section(path: &Path, section: &str) -> Option<HashMap<String, String>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    parse_ini(&content).remove(section)
+}
+
+/// The `config` file names profiles `[profile name]`, except `[default]`.
+fn config_section(profile: &str) -> String {
+    if profile == "default" {
+        profile.to_string()
+    } else {
+        format!("profile {}", profile)
+    }
+}
+
+fn from_section(section: &HashMap<String, String>) -> Option<AwsCredentials> {
+    Some(AwsCredentials {
+        access_key_id: section.get("aws_access_key_id")?.clone(),
+        secret_access_key: section.get("aws_secret_access_key")?.clone(),
+        session_token: section.get("aws_session_token").cloned(),
+    })
+}
+
+/// Credentials from `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`, else the
+/// profile's keys in the credentials or config file. SSO and
+/// `credential_process` profiles are not resolved.
+pub fn load_credentials(profile: &str) -> Option<AwsCredentials> {
+    let env = |var: &str| std::env::var(var).ok().filter(|v| !v.is_empty());
+    if let (Some(access_key_id), Some(secret_access_key)) =
+        (env("AWS_ACCESS_KEY_ID"), env("AWS_SECRET_ACCESS_KEY"))
+    {
+        return Some(AwsCredentials {
+            access_key_id,
+            secret_access_key,
+            session_token: env("AWS_SESSION_TOKEN"),
+        });
+    }
+    read_section(&credentials_path(), profile)
+        .as_ref()
+        .and_then(from_section)
+        .or_else(|| {
+            read_section(&config_path(), &config_section(profile))
+                .as_ref()
+                .and_then(from_section)
+        })
+}
+
+/// Region from `AWS_REGION`, `AWS_DEFAULT_REGION` or the profile's `region`.
+pub fn region(profile: &str) -> Option<String> {
+    ["AWS_REGION", "AWS_DEFAULT_REGION"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .or_else(|| read_section(&config_path(), &config_section(profile))?.remove("region"))
+}
+
+// --- Signature Version 4 ---
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encode per SigV4: everything but unreserved characters.
+fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{}", secret).as_bytes(), date);
+    let k_region = hmac_sha256(&k_date, region);
+    let k_service = hmac_sha256(&k_region, service);
+    hmac_sha256(&k_service, "aws4_request")
+}
+
+/// Signs requests to one service in one region.
+pub struct Signer<'a> {
+    pub creds: &'a AwsCredentials,
+    pub region: &'a str,
+    pub service: &'a str,
+}
+
+impl Signer<'_> {
+    /// Headers that sign a request with SigV4: `x-amz-date`, the session
+    /// token when there is one, and `authorization`. `headers` are the other
+    /// headers to sign besides `host`.
+    pub fn sign(
+        &self,
+        method: &str,
+        url: &reqwest::Url,
+        headers: &[(&str, &str)],
+        body: &[u8],
+        now: DateTime<Utc>,
+    ) -> Vec<(String, String)> {
+        let (creds, region, service) = (self.creds, self.region, self.service);
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        let mut signed: Vec<(String, String)> = headers
+            .iter()
+            .map(|(k, v)| (k.to_lowercase(), v.trim().to_string()))
+            .collect();
+        signed.push((
+            "host".into(),
+            url.host_str().unwrap_or_default().to_string(),
+        ));
+        signed.push(("x-amz-date".into(), amz_date.clone()));
+        if let Some(token) = &creds.session_token {
+            signed.push(("x-amz-security-token".into(), token.clone()));
+        }
+        signed.sort();
+
+        let mut query: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(k, v)| (uri_encode(&k), uri_encode(&v)))
+            .collect();
+        query.sort();
+        let canonical_query = query
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+        let canonical_headers: String = signed
+            .iter()
+            .map(|(k, v)| format!("{}:{}\n", k, v))
+            .collect();
+        let signed_headers = signed
+            .iter()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method,
+            url.path(),
+            canonical_query,
+            canonical_headers,
+            signed_headers,
+            hex(&Sha256::digest(body))
+        );
+
+        let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = signing_key(&creds.secret_access_key, &date, region, service);
+        let signature = hex(&hmac_sha256(&key, &string_to_sign));
+
+        let mut out = vec![("x-amz-date".to_string(), amz_date)];
+        if let Some(token) = &creds.session_token {
+            out.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+        out.push((
+            "authorization".to_string(),
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                creds.access_key_id, scope, signed_headers, signature
+            ),
+        ));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_creds() -> AwsCredentials {
+        AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".into(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".into(),
+            session_token: None,
+        }
+    }
+
+    #[test]
+    fn signing_key_matches_aws_example() {
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20150830",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex(&key),
+            "c4afb1cc5771d871763a393e44b703571b55cc28424d1a5e86da6ed3c154a4b9"
+        );
+    }
+
+    #[test]
+    fn signs_aws_documentation_example() {
+        let url =
+            reqwest::Url::parse("https://iam.amazonaws.com/?Action=ListUsers&Version=2010-05-08")
+                .unwrap();
+        let now: DateTime<Utc> = "2015-08-30T12:36:00Z".parse().unwrap();
+        let creds = example_creds();
+        let signer = Signer {
+            creds: &creds,
+            region: "us-east-1",
+            service: "iam",
+        };
+        let headers = signer.sign(
+            "GET",
+            &url,
+            &[(
+                "Content-Type",
+                "application/x-www-form-urlencoded; charset=utf-8",
+            )],
+            b"",
+            now,
+        );
+        assert_eq!(headers[0], ("x-amz-date".into(), "20150830T123600Z".into()));
+        assert_eq!(
+            headers[1].1,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/iam/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date, \
+             Signature=5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7"
+        );
+    }
+
+    #[test]
+    fn session_token_is_signed() {
+        let mut creds = example_creds();
+        creds.session_token = Some("token".into());
+        let url = reqwest::Url::parse("https://servicequotas.us-east-1.amazonaws.com/").unwrap();
+        let signer = Signer {
+            creds: &creds,
+            region: "us-east-1",
+            service: "servicequotas",
+        };
+        let headers = signer.sign("POST", &url, &[], b"{}", Utc::now());
+        assert_eq!(headers[1], ("x-amz-security-token".into(), "token".into()));
+        assert!(headers[2]
+            .1
+            .contains("SignedHeaders=host;x-amz-date;x-amz-security-token,"));
+    }
+
+    #[test]
+    fn parses_shared_files() {
+        let content = "[default]\n\
+                       aws_access_key_id = AKIADEFAULT\n\
+                       aws_secret_access_key = secret\n\
+                       \n\
+                       # comment\n\
+                       [profile bedrock]\n\
+                       region = eu-west-1\n\
+                       AWS_ACCESS_KEY_ID=AKIABEDROCK\n\
+                       aws_secret_access_key=s2\n\
+                       aws_session_token=tok\n";
+        let sections = parse_ini(content);
+        let default = from_section(&sections["default"]).unwrap();
+        assert_eq!(default.access_key_id, "AKIADEFAULT");
+        assert!(default.session_token.is_none());
+
+        let profile = &sections[&config_section("bedrock")];
+        assert_eq!(profile["region"], "eu-west-1");
+        let creds = from_section(profile).unwrap();
+        assert_eq!(creds.access_key_id, "AKIABEDROCK");
+        assert_eq!(creds.session_token.as_deref(), Some("tok"));
+    }
+
+    #[test]
+    fn uri_encoding() {
+        assert_eq!(uri_encode("a b/c~d"), "a%20b%2Fc~d");
+    }
+}
@@ -0,0 +1,559 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::core::config::AppConfig;
+use crate::core::cost::scanner::CostFilter;
+use crate::core::currency::{self, CurrencyConverter, BASE_CURRENCY};
+use crate::core::models::cost::CostSummary;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::providers::Provider;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    Daily,
+    Weekly,
+    #[default]
+    Monthly,
+}
+
+impl BudgetPeriod {
+    /// Start (inclusive) and end (exclusive) of the period containing `today`.
+    /// Weeks start on Monday.
+    pub fn bounds(self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            Self::Daily => (today, today + Duration::days(1)),
+            Self::Weekly => {
+                let start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+                (start, start + Duration::days(7))
+            }
+            Self::Monthly => {
+                let start = today.with_day(1).unwrap_or(today);
+                let end = if start.month() == 12 {
+                    NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+                };
+                (start, end.unwrap_or(start + Duration::days(31)))
+            }
+        }
+    }
+
+    /// The period a `CreditsSnapshot.period` describes ("Monthly", or a
+    /// LiteLLM budget duration like "30d"), if it is one budgets use.
+    pub fn from_credits_period(period: &str) -> Option<Self> {
+        match period.trim().to_ascii_lowercase().as_str() {
+            "daily" | "day" | "1d" | "24h" => Some(Self::Daily),
+            "weekly" | "week" | "7d" | "1w" => Some(Self::Weekly),
+            "monthly" | "month" | "30d" | "1mo" => Some(Self::Monthly),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Daily => "Daily",
+            Self::Weekly => "Weekly",
+            Self::Monthly => "Monthly",
+        }
+    }
+}
+
+/// A `[[budgets]]` config entry. Scope fields left out match everything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Budget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub amount: f64,
+    #[serde(default)]
+    pub period: BudgetPeriod,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Model name or glob (e.g. "claude-opus-*")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Project directory name or path glob
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Currency of `amount`; defaults to `settings.display_currency`, then USD.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+impl Budget {
+    /// `name`, or a description of the scope.
+    pub fn label(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        let parts: Vec<&str> = [&self.provider, &self.model, &self.project]
+            .into_iter()
+            .filter_map(|p| p.as_deref())
+            .collect();
+        if parts.is_empty() {
+            "All usage".to_string()
+        } else {
+            parts.join(" / ")
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.amount.is_finite() || self.amount <= 0.0 {
+            return Err(format!(
+                "'{}': amount must be a positive number",
+                self.label()
+            ));
+        }
+        if let Some(id) = &self.provider {
+            if Provider::from_id(id).is_none() {
+                return Err(format!("'{}': unknown provider '{}'", self.label(), id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Budgets scoped only to a provider without session logs are tracked
+    /// against that provider's `CreditsSnapshot.used`, when the credits cover
+    /// the budget's period.
+    pub fn credits_provider(&self) -> Option<Provider> {
+        if self.model.is_some() || self.project.is_some() {
+            return None;
+        }
+        let provider = Provider::from_id(self.provider.as_deref()?)?;
+        (!provider.has_cost_logs()).then_some(provider)
+    }
+
+    fn filter(&self) -> CostFilter {
+        CostFilter {
+            provider: self.provider.as_deref().and_then(Provider::from_id),
+            model: self.model.clone(),
+            project: self.project.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SpendSource {
+    /// Token costs from session logs
+    Cost,
+    /// `used` amount reported by the provider
+    Credits,
+}
+
+/// A budget evaluated for the current period.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BudgetStatus {
+    pub name: String,
+    pub amount: f64,
+    pub currency: String,
+    pub period: BudgetPeriod,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub source: SpendSource,
+    pub period_start: NaiveDate,
+    /// First day of the next period
+    pub resets_on: NaiveDate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spent: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used_percent: Option<f64>,
+    /// Spend at the end of the period if the current pace continues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected: Option<f64>,
+    pub exceeded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Extrapolate `spent` over the whole period from the time elapsed so far.
+/// At least one hour is assumed elapsed so early-period spend doesn't explode.
+pub fn project_spend(spent: f64, start: NaiveDate, end: NaiveDate, now: NaiveDateTime) -> f64 {
+    let period_start = start.and_hms_opt(0, 0, 0).unwrap_or_default();
+    let total = (end - start).num_seconds() as f64;
+    let elapsed = ((now - period_start).num_seconds() as f64).clamp(3600.0, total);
+    spent * total / elapsed
+}
+
+fn build_status(
+    budget: &Budget,
+    currency: &str,
+    source: SpendSource,
+    spent: Result<f64, String>,
+    now: NaiveDateTime,
+) -> BudgetStatus {
+    let (start, end) = budget.period.bounds(now.date());
+    let (spent, error) = match spent {
+        Ok(v) => (Some(v), None),
+        Err(e) => (None, Some(e)),
+    };
+    BudgetStatus {
+        name: budget.label(),
+        amount: budget.amount,
+        currency: currency.to_string(),
+        period: budget.period,
+        provider: budget.provider.clone(),
+        model: budget.model.clone(),
+        project: budget.project.clone(),
+        source,
+        period_start: start,
+        resets_on: end,
+        spent,
+        used_percent: spent.map(|s| s / budget.amount * 100.0),
+        projected: spent.map(|s| project_spend(s, start, end, now)),
+        exceeded: spent.is_some_and(|s| s >= budget.amount),
+        error,
+    }
+}
+
+/// Spend from a provider's credits, if they are money and cover the budget's
+/// period. Lifetime totals (e.g. OpenRouter's `usage`) can't be compared to a
+/// period budget.
+fn credits_spend(
+    budget: &Budget,
+    provider: Provider,
+    used: f64,
+    credits: &CreditsSnapshot,
+) -> Result<(f64, Option<String>), String> {
+    if let Some(unit) = &credits.unit {
+        return Err(format!("{} reports {}, not money", provider.display_name(), unit));
+    }
+    let period = credits
+        .period
+        .as_deref()
+        .and_then(BudgetPeriod::from_credits_period);
+    if period != Some(budget.period) {
+        return Err(format!(
+            "{} does not report {} spend",
+            provider.display_name(),
+            budget.period.label().to_lowercase()
+        ));
+    }
+    Ok((used, credits.currency.clone()))
+}
+
+/// The session log spend that cost-based budgets need, read by the same
+/// scan that builds the per-provider cost summaries.
+#[derive(Debug, Clone)]
+pub struct CostQuery {
+    /// One per cost-based budget, in config order
+    pub filters: Vec<CostFilter>,
+    starts: Vec<NaiveDate>,
+}
+
+impl CostQuery {
+    /// `None` when no budget is tracked against session logs.
+    pub fn new(config: &AppConfig, today: NaiveDate) -> Option<Self> {
+        let cost_budgets: Vec<&Budget> = config
+            .budgets
+            .iter()
+            .filter(|b| b.credits_provider().is_none())
+            .collect();
+        if cost_budgets.is_empty() {
+            return None;
+        }
+        Some(Self {
+            filters: cost_budgets.iter().map(|b| b.filter()).collect(),
+            starts: cost_budgets
+                .iter()
+                .map(|b| b.period.bounds(today).0)
+                .collect(),
+        })
+    }
+
+    /// Earliest period start: how far back the scan must read.
+    pub fn since(&self) -> NaiveDate {
+        self.starts.iter().copied().min().unwrap_or_default()
+    }
+
+    /// USD spend of each budget in its current period, from one summary per
+    /// filter covering [`Self::since`].
+    pub fn spend(&self, summaries: &[CostSummary]) -> Vec<f64> {
+        summaries
+            .iter()
+            .zip(&self.starts)
+            .map(|(summary, start)| {
+                summary
+                    .daily
+                    .iter()
+                    .filter(|d| d.date >= *start)
+                    .map(|d| d.total_cost)
+                    .fold(0.0, |acc, c| acc + c)
+            })
+            .collect()
+    }
+}
+
+/// Evaluate every configured budget. `credits` holds the fetched credits of
+/// providers that budgets may be tracked against (see [`Budget::credits_provider`]);
+/// `cost_spend` is [`CostQuery::spend`] for the cost-based budgets, or why the
+/// session logs could not be read in time.
+pub fn evaluate(
+    config: &AppConfig,
+    credits: &HashMap<Provider, CreditsSnapshot>,
+    cost_spend: &Result<Vec<f64>, String>,
+) -> Vec<BudgetStatus> {
+    let now = chrono::Local::now().naive_local();
+    let mut usd_spend = cost_spend.as_deref().unwrap_or_default().iter();
+
+    config
+        .budgets
+        .iter()
+        .map(|budget| {
+            let target = budget
+                .currency
+                .as_deref()
+                .or(config.settings.display_currency.as_deref())
+                .map(currency::normalize_code)
+                .unwrap_or_else(|| BASE_CURRENCY.to_string());
+            let fx = CurrencyConverter::new(&target, &config.exchange_rates);
+            let convert = |amount: f64, from: &str| {
+                fx.rate(from)
+                    .map(|rate| amount * rate)
+                    .ok_or_else(|| format!("no exchange rate for {}", currency::normalize_code(from)))
+            };
+            match budget.credits_provider() {
+                Some(provider) => {
+                    let spent = credits
+                        .get(&provider)
+                        .and_then(|c| c.used.map(|used| (used, c)))
+                        .ok_or_else(|| format!("no spend reported by {}", provider.display_name()))
+                        .and_then(|(used, c)| credits_spend(budget, provider, used, c))
+                        .and_then(|(used, cur)| {
+                            convert(used, cur.as_deref().unwrap_or(BASE_CURRENCY))
+                        });
+                    build_status(budget, &target, SpendSource::Credits, spent, now)
+                }
+                None => {
+                    let spent = match cost_spend {
+                        Ok(_) => usd_spend
+                            .next()
+                            .ok_or_else(|| "cost scan failed".to_string()),
+                        Err(e) => Err(e.clone()),
+                    }
+                    .and_then(|&usd| convert(usd, BASE_CURRENCY));
+                    build_status(budget, &target, SpendSource::Cost, spent, now)
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::cost::DailyReport;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn budget(amount: f64, period: BudgetPeriod) -> Budget {
+        Budget {
+            name: None,
+            amount,
+            period,
+            provider: None,
+            model: None,
+            project: None,
+            currency: None,
+        }
+    }
+
+    #[test]
+    fn period_bounds() {
+        let wed = date(2026, 10, 14);
+        assert_eq!(BudgetPeriod::Daily.bounds(wed), (wed, date(2026, 10, 15)));
+        assert_eq!(
+            BudgetPeriod::Weekly.bounds(wed),
+            (date(2026, 10, 12), date(2026, 10, 19))
+        );
+        assert_eq!(
+            BudgetPeriod::Monthly.bounds(wed),
+            (date(2026, 10, 1), date(2026, 11, 1))
+        );
+        assert_eq!(
+            BudgetPeriod::Monthly.bounds(date(2026, 12, 31)),
+            (date(2026, 12, 1), date(2027, 1, 1))
+        );
+    }
+
+    #[test]
+    fn projection_extrapolates_pace() {
+        // 10 of 30 days elapsed in November: $100 spent → $300 projected
+        let now = date(2026, 11, 11).and_hms_opt(0, 0, 0).unwrap();
+        let projected = project_spend(100.0, date(2026, 11, 1), date(2026, 12, 1), now);
+        assert!((projected - 300.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn projection_assumes_at_least_an_hour() {
+        let now = date(2026, 11, 1).and_hms_opt(0, 0, 1).unwrap();
+        let projected = project_spend(1.0, date(2026, 11, 1), date(2026, 11, 2), now);
+        assert!((projected - 24.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn status_flags_exceeded_budget() {
+        let now = date(2026, 11, 16).and_hms_opt(0, 0, 0).unwrap();
+        let b = budget(100.0, BudgetPeriod::Monthly);
+        let s = build_status(&b, "USD", SpendSource::Cost, Ok(120.0), now);
+        assert!(s.exceeded);
+        assert!((s.used_percent.unwrap() - 120.0).abs() < 1e-9);
+        assert_eq!(s.period_start, date(2026, 11, 1));
+        assert_eq!(s.resets_on, date(2026, 12, 1));
+
+        let s = build_status(&b, "USD", SpendSource::Cost, Err("x".into()), now);
+        assert!(!s.exceeded);
+        assert!(s.spent.is_none());
+        assert_eq!(s.error.as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn cost_query_sums_each_budget_period() {
+        let mut config = AppConfig::default();
+        assert!(CostQuery::new(&config, date(2026, 10, 14)).is_none());
+
+        config.budgets = vec![
+            budget(10.0, BudgetPeriod::Daily),
+            budget(100.0, BudgetPeriod::Monthly),
+        ];
+        let query = CostQuery::new(&config, date(2026, 10, 14)).unwrap();
+        assert_eq!(query.filters.len(), 2);
+        assert_eq!(query.since(), date(2026, 10, 1));
+
+        let day = |d: u32, total_cost: f64| DailyReport {
+            date: date(2026, 10, d),
+            costs: vec![],
+            total_cost,
+        };
+        let summary = CostSummary {
+            total_cost: 7.0,
+            today_cost: 4.0,
+            days: 30,
+            by_model: vec![],
+            daily: vec![day(2, 3.0), day(14, 4.0)],
+            unpriced_models: vec![],
+            converted: None,
+            by_workspace: vec![],
+            by_api_key: vec![],
+        };
+        assert_eq!(query.spend(&[summary.clone(), summary]), vec![4.0, 7.0]);
+    }
+
+    #[test]
+    fn unscanned_cost_budget_reports_error() {
+        let config = AppConfig {
+            budgets: vec![budget(10.0, BudgetPeriod::Daily)],
+            ..AppConfig::default()
+        };
+        let credits = HashMap::new();
+
+        let statuses = evaluate(&config, &credits, &Ok(vec![4.0]));
+        assert_eq!(statuses[0].spent, Some(4.0));
+        assert!(statuses[0].error.is_none());
+
+        let timed_out = Err("Session logs not scanned within 5s".to_string());
+        let statuses = evaluate(&config, &credits, &timed_out);
+        assert!(statuses[0].spent.is_none());
+        assert_eq!(
+            statuses[0].error.as_deref(),
+            Some("Session logs not scanned within 5s")
+        );
+    }
+
+    #[test]
+    fn credits_provider_only_for_provider_scope_without_logs() {
+        let mut b = budget(10.0, BudgetPeriod::Monthly);
+        assert_eq!(b.credits_provider(), None);
+        b.provider = Some("openrouter".to_string());
+        assert_eq!(b.credits_provider(), Some(Provider::OpenRouter));
+        b.model = Some("gpt-*".to_string());
+        assert_eq!(b.credits_provider(), None);
+        b.model = None;
+        b.provider = Some("claude".to_string());
+        assert_eq!(b.credits_provider(), None);
+    }
+
+    #[test]
+    fn credits_period_names() {
+        assert_eq!(BudgetPeriod::from_credits_period("Monthly"), Some(BudgetPeriod::Monthly));
+        assert_eq!(BudgetPeriod::from_credits_period("30d"), Some(BudgetPeriod::Monthly));
+        assert_eq!(BudgetPeriod::from_credits_period("7d"), Some(BudgetPeriod::Weekly));
+        assert_eq!(BudgetPeriod::from_credits_period("1d"), Some(BudgetPeriod::Daily));
+        assert_eq!(BudgetPeriod::from_credits_period("Billing cycle"), None);
+    }
+
+    #[test]
+    fn credits_spend_requires_matching_period() {
+        let mut b = budget(10.0, BudgetPeriod::Monthly);
+        b.provider = Some("openrouter".to_string());
+        let mut credits = CreditsSnapshot {
+            remaining: 5.0,
+            has_credits: true,
+            unlimited: false,
+            used: Some(25.0),
+            limit: None,
+            currency: None,
+            unit: None,
+            period: None,
+            converted: None,
+        };
+        // Lifetime usage is not this month's spend
+        let err = credits_spend(&b, Provider::OpenRouter, 25.0, &credits).unwrap_err();
+        assert_eq!(err, "OpenRouter does not report monthly spend");
+
+        credits.period = Some("Monthly".to_string());
+        assert_eq!(
+            credits_spend(&b, Provider::OpenRouter, 25.0, &credits),
+            Ok((25.0, None))
+        );
+        b.period = BudgetPeriod::Weekly;
+        assert!(credits_spend(&b, Provider::OpenRouter, 25.0, &credits).is_err());
+    }
+
+    #[test]
+    fn credits_spend_rejects_non_monetary_units() {
+        let mut b = budget(100.0, BudgetPeriod::Monthly);
+        b.provider = Some("augment".to_string());
+        let credits = CreditsSnapshot {
+            remaining: 3420.0,
+            has_credits: true,
+            unlimited: false,
+            used: Some(580.0),
+            limit: Some(4000.0),
+            currency: None,
+            unit: Some("credits".to_string()),
+            period: Some("Monthly".to_string()),
+            converted: None,
+        };
+        let err = credits_spend(&b, Provider::Augment, 580.0, &credits).unwrap_err();
+        assert_eq!(err, "Augment reports credits, not money");
+    }
+
+    #[test]
+    fn label_describes_scope() {
+        let mut b = budget(10.0, BudgetPeriod::Daily);
+        assert_eq!(b.label(), "All usage");
+        b.provider = Some("claude".to_string());
+        b.project = Some("aitracker".to_string());
+        assert_eq!(b.label(), "claude / aitracker");
+        b.name = Some("team".to_string());
+        assert_eq!(b.label(), "team");
+    }
+
+    #[test]
+    fn validate_rejects_bad_amount_and_provider() {
+        assert!(budget(0.0, BudgetPeriod::Daily).validate().is_err());
+        let mut b = budget(5.0, BudgetPeriod::Daily);
+        assert!(b.validate().is_ok());
+        b.provider = Some("nope".to_string());
+        assert!(b.validate().is_err());
+    }
+}
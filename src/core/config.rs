@@ -1,7 +1,14 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::core::budget::Budget;
+use crate::core::cost::pricing::PricingEntry;
+use crate::core::http::HttpSettings;
+use crate::core::providers::generic_http::GenericHttpConfig;
+use crate::core::value::PlanPrice;
+
 #[derive(Error, Debug)]
 pub enum ConfigError {
     #[error("Failed to read config: {0}")]
@@ -16,6 +23,14 @@ pub struct Settings {
     pub default_format: String,
     #[serde(default = "default_color")]
     pub color: String,
+    /// Currency code credits and costs are shown in (e.g. "EUR"), converted
+    /// with `[exchange_rates]`. Unset shows each amount in its own currency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_currency: Option<String>,
+    /// Seconds a provider's fetched usage is reused by `ait usage` before
+    /// the upstream API is queried again. 0 disables the cache.
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl_secs: u64,
 }
 
 fn default_format() -> String {
@@ -24,12 +39,17 @@ fn default_format() -> String {
 fn default_color() -> String {
     "auto".to_string()
 }
+fn default_cache_ttl() -> u64 {
+    crate::core::usage_cache::DEFAULT_TTL_SECS
+}
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             default_format: default_format(),
             color: default_color(),
+            display_currency: None,
+            cache_ttl_secs: default_cache_ttl(),
         }
     }
 }
@@ -42,6 +62,48 @@ pub struct ProviderConfig {
     #[serde(default = "default_source")]
     pub source: String,
     pub api_key: Option<String>,
+    /// Request timeout overriding `[http] timeout_secs` for this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Where cookies for web dashboards come from: a browser name
+    /// ("firefox", "chrome", ...), "file:<path>" to a Netscape cookies.txt,
+    /// or a raw `name=value; ...` header. Unset searches installed browsers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cookie: Option<String>,
+    /// Cloud project whose quotas are read (Vertex AI).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Cloud region the provider is used in (Vertex AI, Bedrock).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Credentials file read instead of the tool's default location
+    /// (Augment, Factory). A leading `~/` is expanded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+    /// Currency the provider's credit balance is in (e.g. "CNY"), replacing
+    /// the one it reports. Useful when an account is billed in another region.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+impl ProviderConfig {
+    /// The `credentials` override as a path.
+    pub fn credentials_path(&self) -> Option<PathBuf> {
+        self.credentials
+            .as_deref()
+            .filter(|p| !p.is_empty())
+            .map(expand_home)
+    }
+}
+
+/// Expand a leading `~/` to the home directory.
+pub fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("~"))
+            .join(rest),
+        None => PathBuf::from(path),
+    }
 }
 
 fn default_true() -> bool {
@@ -57,6 +119,24 @@ pub struct AppConfig {
     pub settings: Settings,
     #[serde(default)]
     pub providers: Vec<ProviderConfig>,
+    /// Shared HTTP client settings: timeouts, retries, proxy, CA bundle.
+    #[serde(default, skip_serializing_if = "HttpSettings::is_default")]
+    pub http: HttpSettings,
+    /// Model pricing overrides/additions, consulted before the pricing table.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pricing: Vec<PricingEntry>,
+    /// Static exchange rates as units of each currency per 1 USD.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub exchange_rates: BTreeMap<String, f64>,
+    /// Spending budgets, evaluated per period.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub budgets: Vec<Budget>,
+    /// Subscription plan prices for `ait value`, consulted before the built-in ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plans: Vec<PlanPrice>,
+    /// Usage endpoints read by the `generic_http` provider.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generic_http: Vec<GenericHttpConfig>,
 }
 
 impl Default for AppConfig {
@@ -69,31 +149,70 @@ impl Default for AppConfig {
                     enabled: true,
                     source: "auto".into(),
                     api_key: None,
+                    timeout_secs: None,
+                    cookie: None,
+                    project: None,
+                    region: None,
+                    credentials: None,
+                    currency: None,
                 },
                 ProviderConfig {
                     id: "codex".into(),
                     enabled: true,
                     source: "auto".into(),
                     api_key: None,
+                    timeout_secs: None,
+                    cookie: None,
+                    project: None,
+                    region: None,
+                    credentials: None,
+                    currency: None,
                 },
                 ProviderConfig {
                     id: "copilot".into(),
                     enabled: false,
                     source: "auto".into(),
                     api_key: None,
+                    timeout_secs: None,
+                    cookie: None,
+                    project: None,
+                    region: None,
+                    credentials: None,
+                    currency: None,
                 },
                 ProviderConfig {
                     id: "openrouter".into(),
                     enabled: false,
                     source: "auto".into(),
                     api_key: None,
+                    timeout_secs: None,
+                    cookie: None,
+                    project: None,
+                    region: None,
+                    credentials: None,
+                    currency: None,
                 },
             ],
+            http: HttpSettings::default(),
+            pricing: Vec::new(),
+            exchange_rates: BTreeMap::new(),
+            budgets: Vec::new(),
+            plans: Vec::new(),
+            generic_http: Vec::new(),
         }
     }
 }
 
 impl AppConfig {
+    /// The `currency` override configured for a provider, if any.
+    pub fn credits_currency(&self, provider: crate::core::providers::Provider) -> Option<&str> {
+        self.providers
+            .iter()
+            .find(|p| crate::core::providers::Provider::from_id(&p.id) == Some(provider))
+            .and_then(|p| p.currency.as_deref())
+            .filter(|c| !c.trim().is_empty())
+    }
+
     /// Get the config file path, respecting XDG_CONFIG_HOME
     pub fn config_path() -> PathBuf {
         let config_dir = std::env::var("XDG_CONFIG_HOME")
@@ -106,6 +225,18 @@ impl AppConfig {
         config_dir.join("ait").join("config.toml")
     }
 
+    /// Directory for caches, respecting XDG_CACHE_HOME
+    pub fn cache_dir() -> PathBuf {
+        let cache_dir = std::env::var("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                dirs::home_dir()
+                    .unwrap_or_else(|| PathBuf::from("~"))
+                    .join(".cache")
+            });
+        cache_dir.join("ait")
+    }
+
     /// Load config from the default path, falling back to defaults if not found
     pub fn load() -> Result<Self, ConfigError> {
         let path = Self::config_path();
@@ -139,11 +270,23 @@ impl AppConfig {
                 enabled: enabled_ids.iter().any(|id| id == p.id()),
                 source: "auto".to_string(),
                 api_key: None,
+                timeout_secs: None,
+                cookie: None,
+                project: None,
+                region: None,
+                credentials: None,
+                currency: None,
             })
             .collect();
         let config = Self {
             settings: Settings::default(),
             providers,
+            http: HttpSettings::default(),
+            pricing: Vec::new(),
+            exchange_rates: BTreeMap::new(),
+            budgets: Vec::new(),
+            plans: Vec::new(),
+            generic_http: Vec::new(),
         };
         config.save()
     }
@@ -165,6 +308,12 @@ impl AppConfig {
                     enabled: enabled_ids.iter().any(|id| id == p.id()),
                     source: "auto".to_string(),
                     api_key: None,
+                    timeout_secs: None,
+                    cookie: None,
+                    project: None,
+                    region: None,
+                    credentials: None,
+                    currency: None,
                 });
             }
         }
@@ -187,6 +336,57 @@ impl AppConfig {
                 self.settings.color
             ));
         }
+        if let Some(code) = &self.settings.display_currency {
+            let code = crate::core::currency::normalize_code(code);
+            if code != crate::core::currency::BASE_CURRENCY
+                && !self
+                    .exchange_rates
+                    .keys()
+                    .any(|k| crate::core::currency::normalize_code(k) == code)
+            {
+                issues.push(format!(
+                    "display_currency '{}' has no entry in [exchange_rates]",
+                    code
+                ));
+            }
+        }
+        for (code, rate) in &self.exchange_rates {
+            if !rate.is_finite() || *rate <= 0.0 {
+                issues.push(format!(
+                    "Invalid exchange rate for '{}': {} (must be a positive number)",
+                    code, rate
+                ));
+            }
+        }
+        if let Err(msg) = self.http.validate() {
+            issues.push(format!("Invalid [http] settings: {}", msg));
+        }
+        for budget in &self.budgets {
+            if let Err(msg) = budget.validate() {
+                issues.push(format!("Invalid budget: {}", msg));
+            }
+        }
+        for plan in &self.plans {
+            if let Err(msg) = plan.validate() {
+                issues.push(format!("Invalid plan price: {}", msg));
+            }
+        }
+        for (i, endpoint) in self.generic_http.iter().enumerate() {
+            if let Err(msg) = endpoint.validate() {
+                issues.push(format!("Invalid [[generic_http]] endpoint: {}", msg));
+            }
+            if self.generic_http[..i].iter().any(|e| e.name == endpoint.name) {
+                issues.push(format!(
+                    "Duplicate [[generic_http]] endpoint name '{}'",
+                    endpoint.name
+                ));
+            }
+        }
+        for entry in &self.pricing {
+            if let Err(msg) = crate::core::cost::pricing::validate_entry(entry) {
+                issues.push(format!("Invalid pricing entry: {}", msg));
+            }
+        }
         for p in &self.providers {
             if !["auto", "oauth", "cli", "api"].contains(&p.source.as_str()) {
                 issues.push(format!(
@@ -194,6 +394,11 @@ impl AppConfig {
                     p.id, p.source
                 ));
             }
+            if let Some(spec) = &p.cookie {
+                if let Err(msg) = crate::core::cookies::CookieSource::parse(spec) {
+                    issues.push(format!("Provider '{}': invalid cookie: {}", p.id, msg));
+                }
+            }
             if crate::core::providers::Provider::from_id(&p.id).is_none() {
                 issues.push(format!("Unknown provider ID: '{}'", p.id));
             }
@@ -267,6 +472,17 @@ mod tests {
         assert!(issues.iter().any(|i| i.contains("source")));
     }
 
+    #[test]
+    fn validate_catches_invalid_cookie() {
+        let mut config = AppConfig::default();
+        config.providers[0].cookie = Some("safari".to_string());
+        let issues = config.validate();
+        assert!(issues.iter().any(|i| i.contains("invalid cookie")));
+
+        config.providers[0].cookie = Some("file:~/cookies.txt".to_string());
+        assert!(config.validate().is_empty());
+    }
+
     #[test]
     fn validate_catches_unknown_provider_id() {
         let mut config = AppConfig::default();
@@ -275,6 +491,12 @@ mod tests {
             enabled: true,
             source: "auto".to_string(),
             api_key: None,
+            timeout_secs: None,
+            cookie: None,
+            project: None,
+            region: None,
+            credentials: None,
+            currency: None,
         });
         let issues = config.validate();
         assert!(issues.iter().any(|i| i.contains("Unknown provider")));
@@ -307,6 +529,158 @@ source = "oauth"
         assert_eq!(config.providers[0].source, "oauth");
     }
 
+    #[test]
+    fn parse_pricing_overrides_toml() {
+        let toml = r#"
+[[pricing]]
+model = "claude-sonnet-4-5"
+input = 2.5
+output = 12.0
+
+[[pricing]]
+regex = "^internal-.*"
+input = 0.1
+output = 0.2
+"#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.pricing.len(), 2);
+        assert_eq!(config.pricing[1].regex.as_deref(), Some("^internal-.*"));
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn validate_catches_invalid_pricing_entry() {
+        let toml = r#"
+[[pricing]]
+regex = "(unclosed"
+input = 1.0
+output = 1.0
+"#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        let issues = config.validate();
+        assert!(issues.iter().any(|i| i.contains("pricing")));
+    }
+
+    #[test]
+    fn parse_display_currency_and_rates() {
+        let toml = r#"
+[settings]
+display_currency = "EUR"
+
+[exchange_rates]
+EUR = 0.92
+CNY = 7.1
+"#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.settings.display_currency.as_deref(), Some("EUR"));
+        assert_eq!(config.exchange_rates.get("CNY"), Some(&7.1));
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn parse_provider_currency_override() {
+        use crate::core::providers::Provider;
+        let toml = r#"
+[[providers]]
+id = "kimi-k2"
+currency = "CNY"
+
+[[providers]]
+id = "openrouter"
+currency = ""
+"#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.credits_currency(Provider::KimiK2), Some("CNY"));
+        assert_eq!(config.credits_currency(Provider::OpenRouter), None);
+        assert_eq!(config.credits_currency(Provider::Kimi), None);
+    }
+
+    #[test]
+    fn validate_catches_display_currency_without_rate() {
+        let mut config = AppConfig::default();
+        config.settings.display_currency = Some("eur".to_string());
+        let issues = config.validate();
+        assert!(issues.iter().any(|i| i.contains("display_currency")));
+
+        config.settings.display_currency = Some("usd".to_string());
+        config.exchange_rates.insert("CNY".to_string(), 0.0);
+        let issues = config.validate();
+        assert!(issues.iter().any(|i| i.contains("exchange rate")));
+        assert!(!issues.iter().any(|i| i.contains("display_currency")));
+    }
+
+    #[test]
+    fn parse_budgets_toml() {
+        let toml = r#"
+[[budgets]]
+name = "team"
+amount = 500.0
+period = "weekly"
+provider = "claude"
+project = "aitracker"
+
+[[budgets]]
+amount = 20.0
+"#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.budgets.len(), 2);
+        assert_eq!(config.budgets[0].period, crate::core::budget::BudgetPeriod::Weekly);
+        assert_eq!(config.budgets[1].period, crate::core::budget::BudgetPeriod::Monthly);
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn validate_catches_invalid_budget() {
+        let toml = r#"
+[[budgets]]
+amount = -5.0
+"#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().iter().any(|i| i.contains("budget")));
+    }
+
+    #[test]
+    fn parse_plans_toml() {
+        let toml = r#"
+[[plans]]
+provider = "claude"
+plan = "max"
+price = 180.0
+currency = "EUR"
+billing_day = 12
+"#;
+        let config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.plans.len(), 1);
+        assert_eq!(config.plans[0].billing_day, Some(12));
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn parse_generic_http_endpoints_toml() {
+        let toml = r#"
+[[generic_http]]
+name = "gateway"
+url = "https://gateway.example.com/usage"
+[generic_http.primary]
+used_percent = "$.pct"
+
+[[generic_http]]
+name = "quota"
+url = "https://quota.example.com/v1"
+"#;
+        let mut config: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.generic_http.len(), 2);
+        assert!(config.generic_http[0].primary.is_some());
+        assert_eq!(config.generic_http[1].name, "quota");
+        assert!(config.validate().is_empty());
+
+        config.generic_http[1].name = "gateway".into();
+        assert!(config
+            .validate()
+            .iter()
+            .any(|i| i.contains("Duplicate [[generic_http]] endpoint name 'gateway'")));
+    }
+
     #[test]
     fn parse_empty_toml_gives_defaults() {
         let config: AppConfig = toml::from_str("").unwrap();
@@ -0,0 +1,363 @@
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, KeyIvInit};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::{candidate_hosts, newest_first, placeholders, Browser, Cookie, DbCopy};
+use crate::core::process::which;
+use crate::core::providers::error::ProviderError;
+
+type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
+
+/// Fixed parameters of Chromium's Linux cookie encryption (`os_crypt`).
+const SALT: &[u8] = b"saltysalt";
+const IV: [u8; 16] = [b' '; 16];
+/// Password used for `v10` values when no keyring is available.
+const V10_PASSWORD: &[u8] = b"peanuts";
+
+/// Seconds between 1601-01-01 (Chromium's epoch) and 1970-01-01.
+const WINDOWS_EPOCH_OFFSET: i64 = 11_644_473_600;
+
+/// Database schema version from which decrypted values start with a
+/// SHA-256 of the cookie's host.
+const HASH_PREFIX_VERSION: i64 = 24;
+
+/// User data directory under `~/.config`.
+pub fn user_data_dir(browser: Browser) -> PathBuf {
+    let config = dirs::config_dir().unwrap_or_else(|| {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("~"))
+            .join(".config")
+    });
+    match browser {
+        Browser::Chromium => config.join("chromium"),
+        Browser::Brave => config.join("BraveSoftware").join("Brave-Browser"),
+        Browser::Edge => config.join("microsoft-edge"),
+        Browser::Vivaldi => config.join("vivaldi"),
+        Browser::Chrome | Browser::Firefox => config.join("google-chrome"),
+    }
+}
+
+/// `application` attribute of the browser's libsecret entry.
+fn keyring_application(browser: Browser) -> &'static str {
+    match browser {
+        Browser::Chromium => "chromium",
+        Browser::Brave => "brave",
+        Browser::Edge => "microsoft-edge",
+        Browser::Vivaldi => "vivaldi",
+        Browser::Chrome | Browser::Firefox => "chrome",
+    }
+}
+
+/// Cookie databases of the default profile and numbered profiles.
+fn cookie_dbs(user_data: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(user_data) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name == "Default" || name.starts_with("Profile ")
+        })
+        .filter_map(|entry| {
+            // Chromium 96+ keeps cookies under Network/
+            let profile = entry.path();
+            [profile.join("Network").join("Cookies"), profile.join("Cookies")]
+                .into_iter()
+                .find(|db| db.is_file())
+        })
+        .collect()
+}
+
+/// Decryption keys, with the keyring queried only when a `v11` value needs it.
+struct Keys {
+    application: &'static str,
+    v10: [u8; 16],
+    v11: Option<Vec<[u8; 16]>>,
+}
+
+impl Keys {
+    fn new(application: &'static str) -> Self {
+        Self {
+            application,
+            v10: derive_key(V10_PASSWORD),
+            v11: None,
+        }
+    }
+
+    /// The keyring password's key, then the empty-password key Chromium
+    /// falls back to when the keyring is unavailable.
+    fn v11(&mut self) -> &[[u8; 16]] {
+        let application = self.application;
+        self.v11.get_or_insert_with(|| {
+            let mut keys = Vec::new();
+            if let Some(password) = keyring_password(application) {
+                keys.push(derive_key(password.as_bytes()));
+            }
+            keys.push(derive_key(b""));
+            keys
+        })
+    }
+}
+
+fn derive_key(password: &[u8]) -> [u8; 16] {
+    let mut key = [0u8; 16];
+    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(password, SALT, 1, &mut key);
+    key
+}
+
+/// The browser's "Safe Storage" password from the Secret Service, via
+/// `secret-tool` (libsecret). KWallet is not supported.
+fn keyring_password(application: &str) -> Option<String> {
+    which("secret-tool")?;
+    let output = std::process::Command::new("secret-tool")
+        .args(["lookup", "application", application])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let password = String::from_utf8(output.stdout).ok()?;
+    let password = password.trim_end_matches('\n');
+    (!password.is_empty()).then(|| password.to_string())
+}
+
+/// Decrypt one `encrypted_value` (without its `v10`/`v11` prefix).
+fn decrypt(ciphertext: &[u8], key: &[u8; 16], strip_hash: bool) -> Option<String> {
+    let mut buf = ciphertext.to_vec();
+    let plain = Aes128CbcDec::new(key.into(), &IV.into())
+        .decrypt_padded_mut::<Pkcs7>(&mut buf)
+        .ok()?;
+    let plain = if strip_hash {
+        plain.get(32..)?
+    } else {
+        plain
+    };
+    String::from_utf8(plain.to_vec()).ok()
+}
+
+/// Cookies for `domain` from the most recently used profile that has any.
+pub fn load(browser: Browser, domain: &str) -> Result<Vec<Cookie>> {
+    let dbs = newest_first(cookie_dbs(&user_data_dir(browser)));
+    if dbs.is_empty() {
+        return Err(
+            ProviderError::AuthMissing(format!("No {} profile found", browser.id())).into(),
+        );
+    }
+    let mut keys = Keys::new(keyring_application(browser));
+    for db in dbs {
+        let cookies = read(&db, domain, &mut keys)?;
+        if !cookies.is_empty() {
+            return Ok(cookies);
+        }
+    }
+    Ok(Vec::new())
+}
+
+struct Row {
+    host: String,
+    name: String,
+    value: String,
+    encrypted: Vec<u8>,
+    path: String,
+    expires_utc: i64,
+    secure: bool,
+}
+
+fn read(db: &Path, domain: &str, keys: &mut Keys) -> Result<Vec<Cookie>> {
+    let copy = DbCopy::new(db)?;
+    let conn = copy.open()?;
+    let version: i64 = conn
+        .query_row("SELECT value FROM meta WHERE key = 'version'", [], |row| {
+            row.get::<_, String>(0)
+        })
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let strip_hash = version >= HASH_PREFIX_VERSION;
+
+    let hosts = candidate_hosts(domain);
+    let sql = format!(
+        "SELECT host_key, name, value, encrypted_value, path, expires_utc, is_secure \
+         FROM cookies WHERE host_key IN ({})",
+        placeholders(hosts.len())
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .with_context(|| format!("Unexpected cookie database format in {}", db.display()))?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(&hosts), |row| {
+            Ok(Row {
+                host: row.get(0)?,
+                name: row.get(1)?,
+                value: row.get(2)?,
+                encrypted: row.get(3)?,
+                path: row.get(4)?,
+                expires_utc: row.get(5)?,
+                secure: row.get::<_, i64>(6)? != 0,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read cookies from {}", db.display()))?;
+
+    // A cookie that cannot be decrypted is skipped so it does not hide the
+    // others; its error is reported only when no cookie could be read
+    let mut cookies = Vec::new();
+    let mut undecrypted = None;
+    for row in rows {
+        let value = if !row.value.is_empty() || row.encrypted.is_empty() {
+            row.value
+        } else if let Some(ciphertext) = row.encrypted.strip_prefix(b"v10") {
+            match decrypt(ciphertext, &keys.v10, strip_hash) {
+                Some(value) => value,
+                None => {
+                    undecrypted.get_or_insert_with(|| {
+                        ProviderError::Parse(format!("Failed to decrypt cookie {}", row.name))
+                    });
+                    continue;
+                }
+            }
+        } else if let Some(ciphertext) = row.encrypted.strip_prefix(b"v11") {
+            match keys
+                .v11()
+                .iter()
+                .find_map(|key| decrypt(ciphertext, key, strip_hash))
+            {
+                Some(value) => value,
+                None => {
+                    undecrypted.get_or_insert_with(|| {
+                        ProviderError::AuthMissing(format!(
+                            "Failed to decrypt cookie {}: browser keyring password unavailable \
+                             (is secret-tool installed and the keyring unlocked?)",
+                            row.name
+                        ))
+                    });
+                    continue;
+                }
+            }
+        } else {
+            // Unknown encryption scheme
+            continue;
+        };
+        cookies.push(Cookie {
+            domain: row.host.trim_start_matches('.').to_string(),
+            name: row.name,
+            value,
+            path: row.path,
+            expires: unix_expiry(row.expires_utc),
+            secure: row.secure,
+        });
+    }
+    match undecrypted {
+        Some(err) if cookies.is_empty() => Err(err.into()),
+        _ => Ok(cookies),
+    }
+}
+
+/// Microseconds since 1601 to Unix seconds; 0 marks a session cookie.
+fn unix_expiry(expires_utc: i64) -> Option<i64> {
+    (expires_utc > 0).then(|| expires_utc / 1_000_000 - WINDOWS_EPOCH_OFFSET)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes::cipher::BlockEncryptMut;
+    use rusqlite::Connection;
+
+    type Aes128CbcEnc = cbc::Encryptor<aes::Aes128>;
+
+    fn encrypt(plain: &[u8], key: &[u8; 16], prefix: &[u8]) -> Vec<u8> {
+        let mut buf = vec![0u8; plain.len() + 16];
+        buf[..plain.len()].copy_from_slice(plain);
+        let ciphertext = Aes128CbcEnc::new(key.into(), &IV.into())
+            .encrypt_padded_mut::<Pkcs7>(&mut buf, plain.len())
+            .unwrap();
+        [prefix, ciphertext].concat()
+    }
+
+    #[test]
+    fn peanuts_key_matches_chromium() {
+        // Known os_crypt v10 key on Linux
+        assert_eq!(
+            derive_key(V10_PASSWORD),
+            [
+                0xfd, 0x62, 0x1f, 0xe5, 0xa2, 0xb4, 0x02, 0x53, 0x9d, 0xfa, 0x14, 0x7c, 0xa9,
+                0x27, 0x27, 0x78
+            ]
+        );
+    }
+
+    #[test]
+    fn decrypts_with_and_without_host_hash() {
+        let key = derive_key(V10_PASSWORD);
+        let plain = b"session-token";
+        let encrypted = encrypt(plain, &key, b"");
+        assert_eq!(decrypt(&encrypted, &key, false).as_deref(), Some("session-token"));
+
+        let hashed = [[7u8; 32].as_slice(), plain].concat();
+        let encrypted = encrypt(&hashed, &key, b"");
+        assert_eq!(decrypt(&encrypted, &key, true).as_deref(), Some("session-token"));
+
+        assert!(decrypt(&encrypted, &derive_key(b"wrong"), true).is_none());
+    }
+
+    #[test]
+    fn converts_chromium_expiry() {
+        assert_eq!(unix_expiry(0), None);
+        assert_eq!(unix_expiry(13_400_000_000_000_000), Some(1_755_526_400));
+    }
+
+    #[test]
+    fn reads_profile_database() {
+        let root = std::env::temp_dir().join("ait_test_chromium_read");
+        let _ = std::fs::remove_dir_all(&root);
+        let network = root.join("Default").join("Network");
+        std::fs::create_dir_all(&network).unwrap();
+        std::fs::create_dir_all(root.join("Crashpad")).unwrap();
+        let db = network.join("Cookies");
+        let conn = Connection::open(&db).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE meta (key LONGVARCHAR NOT NULL UNIQUE PRIMARY KEY, value LONGVARCHAR);
+             INSERT INTO meta VALUES ('version', '24');
+             CREATE TABLE cookies (creation_utc INTEGER NOT NULL, host_key TEXT NOT NULL,
+                name TEXT NOT NULL, value TEXT NOT NULL, encrypted_value BLOB NOT NULL,
+                path TEXT NOT NULL, expires_utc INTEGER NOT NULL, is_secure INTEGER NOT NULL);",
+        )
+        .unwrap();
+        let key = derive_key(V10_PASSWORD);
+        let hashed = [[0u8; 32].as_slice(), b"tok123"].concat();
+        let corrupt = encrypt(&hashed, &derive_key(b"other"), b"v10");
+        let rows: [(&str, &str, &str, Vec<u8>); 5] = [
+            (".factory.ai", "session", "", encrypt(&hashed, &key, b"v10")),
+            ("app.factory.ai", "plain", "visible", Vec::new()),
+            (".factory.ai", "corrupt", "", corrupt.clone()),
+            (".other.ai", "foreign", "x", Vec::new()),
+            (".broken.ai", "only", "", corrupt),
+        ];
+        for (host, name, value, encrypted) in rows {
+            conn.execute(
+                "INSERT INTO cookies VALUES (0, ?1, ?2, ?3, ?4, '/', 0, 1)",
+                rusqlite::params![host, name, value, encrypted],
+            )
+            .unwrap();
+        }
+        drop(conn);
+
+        assert_eq!(cookie_dbs(&root), std::slice::from_ref(&db));
+        let mut keys = Keys::new("chrome");
+        let mut cookies = read(&db, "app.factory.ai", &mut keys).unwrap();
+        cookies.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].name, "plain");
+        assert_eq!(cookies[1].value, "tok123");
+        assert_eq!(cookies[1].domain, "factory.ai");
+        // The keyring was never needed
+        assert!(keys.v11.is_none());
+
+        // Fails only when nothing could be decrypted
+        let err = read(&db, "broken.ai", &mut keys).unwrap_err();
+        assert!(err.to_string().contains("Failed to decrypt cookie only"));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::{candidate_hosts, newest_first, placeholders, Cookie, DbCopy};
+use crate::core::providers::error::ProviderError;
+
+/// Directories holding Firefox profiles: the native install, Snap and Flatpak.
+pub fn profile_roots() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("~"));
+    vec![
+        home.join(".mozilla").join("firefox"),
+        home.join("snap")
+            .join("firefox")
+            .join("common")
+            .join(".mozilla")
+            .join("firefox"),
+        home.join(".var")
+            .join("app")
+            .join("org.mozilla.firefox")
+            .join(".mozilla")
+            .join("firefox"),
+    ]
+}
+
+/// `cookies.sqlite` of every profile under `roots`.
+fn cookie_dbs(roots: &[PathBuf]) -> Vec<PathBuf> {
+    roots
+        .iter()
+        .filter_map(|root| std::fs::read_dir(root).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path().join("cookies.sqlite"))
+        .filter(|db| db.is_file())
+        .collect()
+}
+
+/// Cookies for `domain` from the most recently used profile that has any.
+pub fn load(domain: &str) -> Result<Vec<Cookie>> {
+    let dbs = newest_first(cookie_dbs(&profile_roots()));
+    if dbs.is_empty() {
+        return Err(ProviderError::AuthMissing("No Firefox profile found".into()).into());
+    }
+    for db in dbs {
+        let cookies = read(&db, domain)?;
+        if !cookies.is_empty() {
+            return Ok(cookies);
+        }
+    }
+    Ok(Vec::new())
+}
+
+/// Cookies for `domain` and its parent domains in one `cookies.sqlite`.
+pub fn read(db: &Path, domain: &str) -> Result<Vec<Cookie>> {
+    let copy = DbCopy::new(db)?;
+    let conn = copy.open()?;
+    let hosts = candidate_hosts(domain);
+    let sql = format!(
+        "SELECT host, name, value, path, expiry, isSecure FROM moz_cookies WHERE host IN ({})",
+        placeholders(hosts.len())
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .with_context(|| format!("Unexpected cookie database format in {}", db.display()))?;
+    let rows = stmt.query_map(rusqlite::params_from_iter(&hosts), |row| {
+        let expiry: i64 = row.get(4)?;
+        Ok(Cookie {
+            domain: row.get::<_, String>(0)?.trim_start_matches('.').to_string(),
+            name: row.get(1)?,
+            value: row.get(2)?,
+            path: row.get(3)?,
+            expires: normalize_expiry(expiry),
+            secure: row.get::<_, i64>(5)? != 0,
+        })
+    })?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read cookies from {}", db.display()))
+}
+
+/// Unix seconds. Recent Firefox versions store milliseconds; 0 marks a
+/// session cookie.
+fn normalize_expiry(expiry: i64) -> Option<i64> {
+    match expiry {
+        e if e <= 0 => None,
+        e if e > 100_000_000_000 => Some(e / 1000),
+        e => Some(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn fixture_profile(root: &Path, name: &str, rows: &[(&str, &str, &str, i64)]) -> PathBuf {
+        let profile = root.join(name);
+        std::fs::create_dir_all(&profile).unwrap();
+        let db = profile.join("cookies.sqlite");
+        let conn = Connection::open(&db).unwrap();
+        conn.execute(
+            "CREATE TABLE moz_cookies (id INTEGER PRIMARY KEY, originAttributes TEXT NOT NULL DEFAULT '',
+             name TEXT, value TEXT, host TEXT, path TEXT, expiry INTEGER, lastAccessed INTEGER,
+             creationTime INTEGER, isSecure INTEGER, isHttpOnly INTEGER)",
+            [],
+        )
+        .unwrap();
+        for (host, name, value, expiry) in rows {
+            conn.execute(
+                "INSERT INTO moz_cookies (name, value, host, path, expiry, isSecure, isHttpOnly)
+                 VALUES (?1, ?2, ?3, '/', ?4, 1, 1)",
+                rusqlite::params![name, value, host, expiry],
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn reads_matching_cookies() {
+        let root = std::env::temp_dir().join("ait_test_firefox_read");
+        let _ = std::fs::remove_dir_all(&root);
+        let db = fixture_profile(
+            &root,
+            "abcd.default-release",
+            &[
+                (".ollama.com", "__Secure-session", "abc", 1_900_000_000),
+                ("ollama.com", "theme", "dark", 1_900_000_000_000),
+                ("registry.ollama.com", "other", "x", 0),
+                (".example.com", "foreign", "y", 0),
+            ],
+        );
+
+        let mut cookies = read(&db, "ollama.com").unwrap();
+        cookies.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].name, "__Secure-session");
+        assert_eq!(cookies[0].domain, "ollama.com");
+        assert_eq!(cookies[0].expires, Some(1_900_000_000));
+        assert!(cookies[0].secure);
+        // Millisecond expiry from newer Firefox versions
+        assert_eq!(cookies[1].expires, Some(1_900_000_000));
+
+        assert_eq!(cookie_dbs(std::slice::from_ref(&root)), [db]);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn expiry_normalization() {
+        assert_eq!(normalize_expiry(0), None);
+        assert_eq!(normalize_expiry(1_700_000_000), Some(1_700_000_000));
+        assert_eq!(normalize_expiry(1_700_000_000_123), Some(1_700_000_000));
+    }
+}
@@ -0,0 +1,395 @@
+//! Browser cookies for providers that only expose usage on a web dashboard.
+
+pub mod chromium;
+pub mod firefox;
+pub mod netscape;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+
+use crate::core::config::{expand_home, AppConfig};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::Provider;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    /// Domain the cookie was set for, without a leading dot
+    pub domain: String,
+    pub name: String,
+    pub value: String,
+    pub path: String,
+    /// Unix seconds; `None` for session cookies
+    pub expires: Option<i64>,
+    pub secure: bool,
+}
+
+impl Cookie {
+    /// Whether the cookie is sent to `domain`: its own domain or a subdomain.
+    pub fn matches(&self, domain: &str) -> bool {
+        let own = self.domain.trim_start_matches('.');
+        domain == own || domain.ends_with(&format!(".{}", own))
+    }
+
+    fn expired(&self, now: i64) -> bool {
+        self.expires.is_some_and(|at| at <= now)
+    }
+}
+
+/// Cookies matching `domain` that have not expired, most specific first.
+fn select(cookies: Vec<Cookie>, domain: &str) -> Vec<Cookie> {
+    let now = Utc::now().timestamp();
+    let mut selected: Vec<Cookie> = cookies
+        .into_iter()
+        .filter(|c| c.matches(domain) && !c.expired(now))
+        .collect();
+    selected.sort_by_key(|c| std::cmp::Reverse(c.domain.len()));
+    selected
+}
+
+/// Value of the first cookie named `name`.
+pub fn find<'a>(cookies: &'a [Cookie], name: &str) -> Option<&'a str> {
+    cookies
+        .iter()
+        .find(|c| c.name == name)
+        .map(|c| c.value.as_str())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Browser {
+    Firefox,
+    Chrome,
+    Chromium,
+    Brave,
+    Edge,
+    Vivaldi,
+}
+
+impl Browser {
+    /// Search order when no browser is configured.
+    pub fn all() -> &'static [Browser] {
+        &[
+            Browser::Firefox,
+            Browser::Chrome,
+            Browser::Chromium,
+            Browser::Brave,
+            Browser::Edge,
+            Browser::Vivaldi,
+        ]
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Firefox => "firefox",
+            Self::Chrome => "chrome",
+            Self::Chromium => "chromium",
+            Self::Brave => "brave",
+            Self::Edge => "edge",
+            Self::Vivaldi => "vivaldi",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|b| b.id() == id)
+    }
+
+    /// Cookies for `domain` from the most recently used profile that has any.
+    pub fn load(&self, domain: &str) -> Result<Vec<Cookie>> {
+        let cookies = match self {
+            Self::Firefox => firefox::load(domain)?,
+            _ => chromium::load(*self, domain)?,
+        };
+        Ok(select(cookies, domain))
+    }
+}
+
+/// Where a provider's cookies come from, from `cookie` in `[[providers]]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CookieSource {
+    /// Firefox, then Chromium-family browsers
+    Auto,
+    Browser(Browser),
+    /// Netscape cookies.txt, as exported by browser extensions or curl
+    File(PathBuf),
+    /// A raw `name=value; name2=value2` header
+    Header(String),
+}
+
+impl CookieSource {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if spec.is_empty() || spec == "auto" {
+            return Ok(Self::Auto);
+        }
+        if let Some(path) = spec.strip_prefix("file:") {
+            if path.is_empty() {
+                return Err("'file:' needs a path".into());
+            }
+            return Ok(Self::File(expand_home(path)));
+        }
+        if spec.contains('=') {
+            return Ok(Self::Header(spec.to_string()));
+        }
+        Browser::from_id(spec).map(Self::Browser).ok_or_else(|| {
+            let ids: Vec<&str> = Browser::all().iter().map(|b| b.id()).collect();
+            format!(
+                "unknown browser '{}' (expected auto, {}, file:<path> or name=value pairs)",
+                spec,
+                ids.join(", ")
+            )
+        })
+    }
+
+    /// The configured source for `provider`, if its entry sets `cookie`.
+    pub fn configured(provider: Provider) -> Option<Self> {
+        let config = AppConfig::load().unwrap_or_default();
+        let entry = config.providers.iter().find(|p| p.id == provider.id())?;
+        match Self::parse(entry.cookie.as_deref()?) {
+            Ok(source) => Some(source),
+            Err(msg) => {
+                eprintln!("{}: ignoring cookie setting: {}", provider.id(), msg);
+                None
+            }
+        }
+    }
+
+    /// Cookies for `domain`. Fails with `auth_missing` when none are found.
+    pub fn load(&self, domain: &str) -> Result<Vec<Cookie>> {
+        let cookies = match self {
+            Self::Auto => {
+                // A browser that is not installed or cannot be decrypted is skipped
+                let found = Browser::all()
+                    .iter()
+                    .find_map(|b| b.load(domain).ok().filter(|c| !c.is_empty()));
+                return found.ok_or_else(|| {
+                    ProviderError::AuthMissing(format!(
+                        "No cookies for {} found in Firefox or Chromium-based browsers",
+                        domain
+                    ))
+                    .into()
+                });
+            }
+            Self::Browser(browser) => browser.load(domain)?,
+            Self::File(path) => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read cookies from {}", path.display()))?;
+                select(netscape::parse(&content), domain)
+            }
+            Self::Header(header) => parse_header(header, domain),
+        };
+        if cookies.is_empty() {
+            return Err(ProviderError::AuthMissing(format!(
+                "No cookies for {} in {}",
+                domain,
+                self.describe()
+            ))
+            .into());
+        }
+        Ok(cookies)
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Auto => "installed browsers".into(),
+            Self::Browser(browser) => browser.id().into(),
+            Self::File(path) => path.display().to_string(),
+            Self::Header(_) => "the configured cookie header".into(),
+        }
+    }
+}
+
+fn parse_header(header: &str, domain: &str) -> Vec<Cookie> {
+    header
+        .split(';')
+        .filter_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            Some(Cookie {
+                domain: domain.to_string(),
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+                path: "/".into(),
+                expires: None,
+                secure: true,
+            })
+        })
+        .collect()
+}
+
+/// A private copy of a browser's cookie database. Browsers keep the live
+/// file locked while running, so it is copied (with its WAL) before opening.
+struct DbCopy {
+    dir: PathBuf,
+}
+
+impl DbCopy {
+    fn new(db: &Path) -> Result<Self> {
+        use std::os::unix::fs::DirBuilderExt;
+        static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        let n = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("ait-cookies-{}-{}", std::process::id(), n));
+        // Cookie values are secrets; keep the copy private to this user
+        std::fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let copy = Self { dir };
+        std::fs::copy(db, copy.path())
+            .with_context(|| format!("Failed to copy {}", db.display()))?;
+        let wal = PathBuf::from(format!("{}-wal", db.display()));
+        if wal.exists() {
+            let _ = std::fs::copy(&wal, copy.dir.join("cookies.db-wal"));
+        }
+        Ok(copy)
+    }
+
+    fn path(&self) -> PathBuf {
+        self.dir.join("cookies.db")
+    }
+
+    fn open(&self) -> Result<rusqlite::Connection> {
+        rusqlite::Connection::open(self.path()).context("Failed to open cookie database copy")
+    }
+}
+
+impl Drop for DbCopy {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Host values a cookie database may store for cookies sent to `domain`:
+/// the domain and each parent, with and without a leading dot.
+fn candidate_hosts(domain: &str) -> Vec<String> {
+    let labels: Vec<&str> = domain.split('.').collect();
+    (0..labels.len().saturating_sub(1))
+        .flat_map(|i| {
+            let host = labels[i..].join(".");
+            [format!(".{}", host), host]
+        })
+        .collect()
+}
+
+/// SQL placeholders `?1, ?2, ...` for `n` values.
+fn placeholders(n: usize) -> String {
+    (1..=n)
+        .map(|i| format!("?{}", i))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Cookie databases, most recently modified (used) first.
+fn newest_first(mut dbs: Vec<PathBuf>) -> Vec<PathBuf> {
+    dbs.sort_by_key(|p| {
+        std::cmp::Reverse(
+            std::fs::metadata(p)
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH),
+        )
+    });
+    dbs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cookie(domain: &str, name: &str, expires: Option<i64>) -> Cookie {
+        Cookie {
+            domain: domain.into(),
+            name: name.into(),
+            value: "v".into(),
+            path: "/".into(),
+            expires,
+            secure: true,
+        }
+    }
+
+    #[test]
+    fn domain_matching() {
+        assert!(cookie(".cursor.com", "a", None).matches("cursor.com"));
+        assert!(cookie("cursor.com", "a", None).matches("www.cursor.com"));
+        assert!(!cookie("www.cursor.com", "a", None).matches("cursor.com"));
+        assert!(!cookie("cursor.com", "a", None).matches("notcursor.com"));
+    }
+
+    #[test]
+    fn select_drops_expired_and_foreign() {
+        let now = Utc::now().timestamp();
+        let cookies = vec![
+            cookie("cursor.com", "session", Some(now + 3600)),
+            cookie("cursor.com", "old", Some(now - 10)),
+            cookie("example.com", "other", None),
+            cookie("app.cursor.com", "specific", None),
+        ];
+        let selected = select(cookies, "app.cursor.com");
+        let names: Vec<&str> = selected.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["specific", "session"]);
+        assert_eq!(find(&selected, "session"), Some("v"));
+    }
+
+    #[test]
+    fn candidate_hosts_stop_before_tld() {
+        assert_eq!(
+            candidate_hosts("app.cursor.com"),
+            [".app.cursor.com", "app.cursor.com", ".cursor.com", "cursor.com"]
+        );
+        assert_eq!(placeholders(3), "?1, ?2, ?3");
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(CookieSource::parse("auto").unwrap(), CookieSource::Auto);
+        assert_eq!(
+            CookieSource::parse("firefox").unwrap(),
+            CookieSource::Browser(Browser::Firefox)
+        );
+        assert_eq!(
+            CookieSource::parse("file:/tmp/cookies.txt").unwrap(),
+            CookieSource::File(PathBuf::from("/tmp/cookies.txt"))
+        );
+        assert!(matches!(
+            CookieSource::parse("file:~/cookies.txt").unwrap(),
+            CookieSource::File(p) if !p.starts_with("~")
+        ));
+        assert!(matches!(
+            CookieSource::parse("session=abc; csrf=def").unwrap(),
+            CookieSource::Header(_)
+        ));
+        assert!(CookieSource::parse("netscape").is_err());
+        assert!(CookieSource::parse("file:").is_err());
+    }
+
+    #[test]
+    fn header_source_yields_pairs() {
+        let source = CookieSource::parse("session=abc; csrf = def").unwrap();
+        let cookies = source.load("ollama.com").unwrap();
+        assert_eq!(find(&cookies, "session"), Some("abc"));
+        assert_eq!(find(&cookies, "csrf"), Some("def"));
+        assert_eq!(cookies.len(), 2);
+    }
+
+    #[test]
+    fn file_source_filters_domain() {
+        let dir = std::env::temp_dir().join("ait_test_cookies_file");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cookies.txt");
+        std::fs::write(
+            &path,
+            "# Netscape HTTP Cookie File\n\
+             .augmentcode.com\tTRUE\t/\tTRUE\t0\t_session\tabc\n\
+             example.com\tFALSE\t/\tFALSE\t0\tother\txyz\n",
+        )
+        .unwrap();
+        let source = CookieSource::File(path.clone());
+        let cookies = source.load("app.augmentcode.com").unwrap();
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(find(&cookies, "_session"), Some("abc"));
+
+        let err = source.load("cursor.com").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ProviderError>(),
+            Some(ProviderError::AuthMissing(_))
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,66 @@
+use super::Cookie;
+
+/// Parse a Netscape cookies.txt: tab-separated domain, include-subdomains
+/// flag, path, secure flag, expiry (0 for session cookies), name and value.
+/// Malformed lines are skipped.
+pub fn parse(content: &str) -> Vec<Cookie> {
+    content.lines().filter_map(parse_line).collect()
+}
+
+fn parse_line(line: &str) -> Option<Cookie> {
+    // curl marks HttpOnly cookies with a prefix on an otherwise normal line
+    let line = line.strip_prefix("#HttpOnly_").unwrap_or(line);
+    if line.trim().is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let fields: Vec<&str> = line.trim_end_matches(['\r', '\n']).split('\t').collect();
+    let [domain, _subdomains, path, secure, expires, name, value] = fields[..] else {
+        return None;
+    };
+    let expires: i64 = expires.parse().ok()?;
+    Some(Cookie {
+        domain: domain.trim_start_matches('.').to_string(),
+        name: name.to_string(),
+        value: value.to_string(),
+        path: path.to_string(),
+        expires: (expires > 0).then_some(expires),
+        secure: secure.eq_ignore_ascii_case("TRUE"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cookies_txt() {
+        let content = "# Netscape HTTP Cookie File\n\
+                       # https://curl.se/docs/http-cookies.html\n\
+                       \n\
+                       .ollama.com\tTRUE\t/\tTRUE\t1900000000\t__Secure-session\tabc123\n\
+                       #HttpOnly_app.factory.ai\tFALSE\t/api\tFALSE\t0\tsid\txyz\r\n\
+                       broken line without tabs\n\
+                       example.com\tFALSE\t/\tFALSE\tnever\tbad\texpiry\n";
+        let cookies = parse(content);
+        assert_eq!(cookies.len(), 2);
+
+        assert_eq!(cookies[0].domain, "ollama.com");
+        assert_eq!(cookies[0].name, "__Secure-session");
+        assert_eq!(cookies[0].value, "abc123");
+        assert_eq!(cookies[0].expires, Some(1900000000));
+        assert!(cookies[0].secure);
+
+        assert_eq!(cookies[1].domain, "app.factory.ai");
+        assert_eq!(cookies[1].path, "/api");
+        assert_eq!(cookies[1].value, "xyz");
+        assert_eq!(cookies[1].expires, None);
+        assert!(!cookies[1].secure);
+    }
+
+    #[test]
+    fn value_may_be_empty() {
+        let cookies = parse("example.com\tFALSE\t/\tFALSE\t0\tflag\t\n");
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].value, "");
+    }
+}
@@ -3,7 +3,14 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-const CACHE_VERSION: u64 = 2;
+pub const CACHE_VERSION: u64 = 7;
+
+fn is_zero(n: &u64) -> bool {
+    *n == 0
+}
+
+/// Number of leading bytes fingerprinted to detect a rewritten or rotated file.
+pub const HEAD_HASH_BYTES: u64 = 4096;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedRecord {
@@ -14,6 +21,30 @@ pub struct CachedRecord {
     pub output_tokens: u64,
     pub cache_read_tokens: u64,
     pub cache_creation_tokens: u64,
+    /// Portion of `cache_creation_tokens` written with a 1-hour TTL.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub cache_creation_1h_tokens: u64,
+    /// Working directory of the session that produced the record.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Streaming dedup key (message id + request id), kept so that chunks
+    /// appended after an incremental resume replace the earlier record.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_key: Option<String>,
+}
+
+/// Running state of a Codex session at the last parsed offset, so that an
+/// incremental parse can turn the next cumulative total into a delta.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CodexSessionState {
+    /// Model set by the most recent `turn_context` event.
+    pub model: Option<String>,
+    /// Working directory from `session_meta` / `turn_context`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    pub total_input_tokens: u64,
+    pub total_output_tokens: u64,
+    pub total_cached_tokens: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,8 +52,16 @@ pub struct FileEntry {
     pub mtime_ms: u64,
     pub size: u64,
     pub parsed_bytes: u64,
+    /// Length of the prefix covered by `head_hash`.
+    #[serde(default)]
+    pub head_len: u64,
+    /// Fingerprint of the first `head_len` bytes at the time of parsing.
+    #[serde(default)]
+    pub head_hash: u64,
     #[serde(default)]
     pub records: Vec<CachedRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex_state: Option<CodexSessionState>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,15 +80,8 @@ impl Default for CostCache {
     }
 }
 
-fn cache_path() -> PathBuf {
-    let base = std::env::var("XDG_CACHE_HOME")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| {
-            dirs::home_dir()
-                .unwrap_or_else(|| PathBuf::from("~"))
-                .join(".cache")
-        });
-    base.join("ait").join("cost-cache.json")
+pub fn cache_path() -> PathBuf {
+    crate::core::config::AppConfig::cache_dir().join("cost-cache.json")
 }
 
 impl CostCache {
@@ -89,7 +121,11 @@ impl CostCache {
                 .with_context(|| format!("Failed to create cache directory: {}", parent.display()))?;
         }
         let json = serde_json::to_string(self).context("Failed to serialize cost cache")?;
-        std::fs::write(&path, json)
+        // Written aside and renamed, so a scan cut off at exit leaves the old cache intact
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .with_context(|| format!("Failed to write cache to {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path)
             .with_context(|| format!("Failed to write cache to {}", path.display()))?;
         Ok(())
     }
@@ -104,16 +140,27 @@ impl CostCache {
     }
 
     /// Get the byte offset to resume parsing from for an incremental read.
-    /// Returns 0 if file is new or has been modified.
-    pub fn resume_offset(&self, path: &str, mtime_ms: u64) -> u64 {
-        if let Some(entry) = self.files.get(path) {
-            // If mtime changed, we must re-read from start
-            // But if only size grew (file appended), we can resume
-            if entry.mtime_ms == mtime_ms {
-                entry.parsed_bytes
-            } else {
-                0
-            }
+    ///
+    /// Resuming is only safe when the file was appended to: it must be at least
+    /// as large as what we already parsed, and its leading bytes must still hash
+    /// to the stored fingerprint. `head_hash` is called with the stored prefix
+    /// length and returns the current fingerprint (None if unreadable).
+    /// Returns 0 if the file is new, truncated, or was rewritten/rotated.
+    pub fn resume_offset(
+        &self,
+        path: &str,
+        size: u64,
+        head_hash: impl FnOnce(u64) -> Option<u64>,
+    ) -> u64 {
+        let entry = match self.files.get(path) {
+            Some(e) => e,
+            None => return 0,
+        };
+        if entry.parsed_bytes == 0 || size < entry.parsed_bytes {
+            return 0;
+        }
+        if head_hash(entry.head_len) == Some(entry.head_hash) {
+            entry.parsed_bytes
         } else {
             0
         }
@@ -128,12 +175,14 @@ impl CostCache {
     }
 
     /// Update the cache entry for a file, including parsed records.
+    /// `head` is the `(length, fingerprint)` of the file prefix, see `fingerprint`.
     pub fn update(
         &mut self,
         path: &str,
         mtime_ms: u64,
         size: u64,
         parsed_bytes: u64,
+        head: (u64, u64),
         records: Vec<CachedRecord>,
     ) {
         self.files.insert(
@@ -142,11 +191,41 @@ impl CostCache {
                 mtime_ms,
                 size,
                 parsed_bytes,
+                head_len: head.0,
+                head_hash: head.1,
                 records,
+                codex_state: None,
             },
         );
     }
 
+    /// Get the Codex session state stored for a file.
+    pub fn get_codex_state(&self, path: &str) -> Option<CodexSessionState> {
+        self.files.get(path).and_then(|e| e.codex_state.clone())
+    }
+
+    /// Attach Codex session state to an existing file entry.
+    pub fn set_codex_state(&mut self, path: &str, state: CodexSessionState) {
+        if let Some(entry) = self.files.get_mut(path) {
+            entry.codex_state = Some(state);
+        }
+    }
+
+    /// Drop entries for files that no longer exist (deleted or rotated away).
+    pub fn retain_paths(&mut self, seen: &std::collections::HashSet<String>) {
+        self.files.retain(|path, _| seen.contains(path));
+    }
+}
+
+/// FNV-1a hash of a byte slice. Stable across builds, unlike `DefaultHasher`,
+/// so fingerprints stored on disk stay comparable after a toolchain upgrade.
+pub fn fingerprint(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
 }
 
 #[cfg(test)]
@@ -162,7 +241,7 @@ mod tests {
     #[test]
     fn cache_unchanged_check() {
         let mut cache = CostCache::default();
-        cache.update("/test/file.jsonl", 1000, 5000, 5000, vec![]);
+        cache.update("/test/file.jsonl", 1000, 5000, 5000, (0, 0), vec![]);
         assert!(cache.is_unchanged("/test/file.jsonl", 1000, 5000));
         assert!(!cache.is_unchanged("/test/file.jsonl", 1001, 5000));
         assert!(!cache.is_unchanged("/test/file.jsonl", 1000, 6000));
@@ -172,19 +251,71 @@ mod tests {
     #[test]
     fn cache_resume_offset() {
         let mut cache = CostCache::default();
-        cache.update("/test/file.jsonl", 1000, 5000, 3000, vec![]);
-        // Same mtime -> resume from parsed_bytes
-        assert_eq!(cache.resume_offset("/test/file.jsonl", 1000), 3000);
-        // Different mtime -> start from 0
-        assert_eq!(cache.resume_offset("/test/file.jsonl", 1001), 0);
+        cache.update("/test/file.jsonl", 1000, 5000, 3000, (4096, 42), vec![]);
+        // Grown file with the same head -> resume from parsed_bytes
+        assert_eq!(cache.resume_offset("/test/file.jsonl", 6000, |_| Some(42)), 3000);
+        // Head changed (rewritten/rotated) -> start from 0
+        assert_eq!(cache.resume_offset("/test/file.jsonl", 6000, |_| Some(43)), 0);
+        // Head unreadable -> start from 0
+        assert_eq!(cache.resume_offset("/test/file.jsonl", 6000, |_| None), 0);
+        // Truncated below parsed_bytes -> start from 0
+        assert_eq!(cache.resume_offset("/test/file.jsonl", 2000, |_| Some(42)), 0);
         // Unknown file -> 0
-        assert_eq!(cache.resume_offset("/test/other.jsonl", 1000), 0);
+        assert_eq!(cache.resume_offset("/test/other.jsonl", 6000, |_| Some(42)), 0);
+    }
+
+    #[test]
+    fn cache_resume_offset_passes_stored_head_len() {
+        let mut cache = CostCache::default();
+        cache.update("/test/file.jsonl", 1000, 100, 100, (100, 7), vec![]);
+        let offset = cache.resume_offset("/test/file.jsonl", 200, |len| {
+            assert_eq!(len, 100);
+            Some(7)
+        });
+        assert_eq!(offset, 100);
+    }
+
+    #[test]
+    fn cache_retain_paths_drops_missing_files() {
+        let mut cache = CostCache::default();
+        cache.update("/test/a.jsonl", 1, 1, 1, (0, 0), vec![]);
+        cache.update("/test/b.jsonl", 1, 1, 1, (0, 0), vec![]);
+        let seen: std::collections::HashSet<String> =
+            ["/test/a.jsonl".to_string()].into_iter().collect();
+        cache.retain_paths(&seen);
+        assert!(cache.files.contains_key("/test/a.jsonl"));
+        assert!(!cache.files.contains_key("/test/b.jsonl"));
+    }
+
+    #[test]
+    fn cache_codex_state_roundtrip() {
+        let mut cache = CostCache::default();
+        cache.update("/test/codex.jsonl", 1, 10, 10, (10, 1), vec![]);
+        assert!(cache.get_codex_state("/test/codex.jsonl").is_none());
+        let state = CodexSessionState {
+            model: Some("gpt-5".to_string()),
+            cwd: Some("/work/project".to_string()),
+            total_input_tokens: 100,
+            total_output_tokens: 20,
+            total_cached_tokens: 50,
+        };
+        cache.set_codex_state("/test/codex.jsonl", state.clone());
+        let json = serde_json::to_string(&cache).unwrap();
+        let loaded: CostCache = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get_codex_state("/test/codex.jsonl"), Some(state));
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        assert_eq!(fingerprint(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fingerprint(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(fingerprint(b"abc"), fingerprint(b"abd"));
     }
 
     #[test]
     fn cache_clear() {
         let mut cache = CostCache::default();
-        cache.update("/test/file.jsonl", 1000, 5000, 5000, vec![]);
+        cache.update("/test/file.jsonl", 1000, 5000, 5000, (0, 0), vec![]);
         assert!(!cache.files.is_empty());
         cache.files.clear();
         assert!(cache.files.is_empty());
@@ -193,9 +324,10 @@ mod tests {
     #[test]
     fn cache_roundtrip_json() {
         let mut cache = CostCache::default();
-        cache.update("/test/file.jsonl", 1000, 5000, 3000, vec![]);
+        cache.update("/test/file.jsonl", 1000, 5000, 3000, (4096, 42), vec![]);
         let json = serde_json::to_string(&cache).unwrap();
         let loaded: CostCache = serde_json::from_str(&json).unwrap();
         assert!(loaded.is_unchanged("/test/file.jsonl", 1000, 5000));
+        assert_eq!(loaded.resume_offset("/test/file.jsonl", 5000, |_| Some(42)), 3000);
     }
 }
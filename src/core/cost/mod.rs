@@ -1,3 +1,4 @@
 pub mod cache;
+pub mod org;
 pub mod pricing;
 pub mod scanner;
@@ -0,0 +1,396 @@
+//! Organization spend reported by provider Admin APIs, shaped into the same
+//! `CostSummary` the session-log scanner produces.
+
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use crate::core::models::cost::{CostSummary, DailyReport, GroupCost, TokenCostSnapshot};
+
+/// Which token category a cost line bills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostPart {
+    Input,
+    Output,
+    CacheRead,
+    CacheCreation,
+    /// Tool calls, fine-tuning and other non-token charges.
+    Other,
+}
+
+/// One amount of a daily cost report, in USD.
+#[derive(Debug, Clone)]
+pub struct CostLine {
+    pub date: NaiveDate,
+    /// Model name, or the line item's description for non-model charges.
+    pub model: String,
+    pub part: CostPart,
+    pub amount: f64,
+    pub workspace: String,
+}
+
+/// Token counts of one row of a daily usage report.
+#[derive(Debug, Clone)]
+pub struct UsageLine {
+    pub date: NaiveDate,
+    pub model: String,
+    pub api_key: String,
+    pub workspace: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+}
+
+impl UsageLine {
+    fn all_input(&self) -> u64 {
+        self.input_tokens + self.cache_read_tokens + self.cache_creation_tokens
+    }
+
+    fn total(&self) -> u64 {
+        self.all_input() + self.output_tokens
+    }
+}
+
+fn empty_snapshot(model: &str) -> TokenCostSnapshot {
+    TokenCostSnapshot {
+        model: model.to_string(),
+        input_tokens: 0,
+        output_tokens: 0,
+        cache_read_tokens: 0,
+        cache_creation_tokens: 0,
+        input_cost: 0.0,
+        output_cost: 0.0,
+        cache_read_cost: 0.0,
+        cache_creation_cost: 0.0,
+        total_cost: 0.0,
+        priced: true,
+    }
+}
+
+fn add_snapshot(into: &mut TokenCostSnapshot, from: &TokenCostSnapshot) {
+    into.input_tokens += from.input_tokens;
+    into.output_tokens += from.output_tokens;
+    into.cache_read_tokens += from.cache_read_tokens;
+    into.cache_creation_tokens += from.cache_creation_tokens;
+    into.input_cost += from.input_cost;
+    into.output_cost += from.output_cost;
+    into.cache_read_cost += from.cache_read_cost;
+    into.cache_creation_cost += from.cache_creation_cost;
+    into.total_cost += from.total_cost;
+}
+
+fn sorted_groups(groups: HashMap<String, GroupCost>) -> Vec<GroupCost> {
+    let mut groups: Vec<GroupCost> = groups.into_values().collect();
+    groups.sort_by(|a, b| {
+        b.total_cost
+            .partial_cmp(&a.total_cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    groups
+}
+
+/// Summarize `days` of cost and usage reports. Costs are taken as billed;
+/// tokens come from the usage report. Cost reports are not broken down by
+/// API key, so each key is attributed its token share of the day's spend on
+/// each model.
+pub fn build_summary(
+    costs: &[CostLine],
+    usage: &[UsageLine],
+    days: u32,
+    today: NaiveDate,
+) -> CostSummary {
+    let mut date_model: HashMap<(NaiveDate, String), TokenCostSnapshot> = HashMap::new();
+    let mut workspaces: HashMap<String, GroupCost> = HashMap::new();
+    let group = |id: &str| GroupCost {
+        id: id.to_string(),
+        input_tokens: 0,
+        output_tokens: 0,
+        total_cost: 0.0,
+    };
+
+    for line in costs {
+        let entry = date_model
+            .entry((line.date, line.model.clone()))
+            .or_insert_with(|| empty_snapshot(&line.model));
+        match line.part {
+            CostPart::Input => entry.input_cost += line.amount,
+            CostPart::Output => entry.output_cost += line.amount,
+            CostPart::CacheRead => entry.cache_read_cost += line.amount,
+            CostPart::CacheCreation => entry.cache_creation_cost += line.amount,
+            CostPart::Other => {}
+        }
+        entry.total_cost += line.amount;
+        workspaces
+            .entry(line.workspace.clone())
+            .or_insert_with(|| group(&line.workspace))
+            .total_cost += line.amount;
+    }
+
+    let mut day_model_tokens: HashMap<(NaiveDate, &str), u64> = HashMap::new();
+    for line in usage {
+        let entry = date_model
+            .entry((line.date, line.model.clone()))
+            .or_insert_with(|| empty_snapshot(&line.model));
+        entry.input_tokens += line.input_tokens;
+        entry.output_tokens += line.output_tokens;
+        entry.cache_read_tokens += line.cache_read_tokens;
+        entry.cache_creation_tokens += line.cache_creation_tokens;
+        *day_model_tokens
+            .entry((line.date, line.model.as_str()))
+            .or_default() += line.total();
+
+        let workspace = workspaces
+            .entry(line.workspace.clone())
+            .or_insert_with(|| group(&line.workspace));
+        workspace.input_tokens += line.all_input();
+        workspace.output_tokens += line.output_tokens;
+    }
+
+    let mut api_keys: HashMap<String, GroupCost> = HashMap::new();
+    for line in usage {
+        let key = api_keys
+            .entry(line.api_key.clone())
+            .or_insert_with(|| group(&line.api_key));
+        key.input_tokens += line.all_input();
+        key.output_tokens += line.output_tokens;
+        let total = day_model_tokens[&(line.date, line.model.as_str())];
+        if total > 0 {
+            let spend = date_model[&(line.date, line.model.clone())].total_cost;
+            key.total_cost += spend * line.total() as f64 / total as f64;
+        }
+    }
+
+    let mut daily_map: HashMap<NaiveDate, Vec<TokenCostSnapshot>> = HashMap::new();
+    let mut model_totals: HashMap<String, TokenCostSnapshot> = HashMap::new();
+    for ((date, model), snapshot) in date_model {
+        add_snapshot(
+            model_totals
+                .entry(model.clone())
+                .or_insert_with(|| empty_snapshot(&model)),
+            &snapshot,
+        );
+        daily_map.entry(date).or_default().push(snapshot);
+    }
+
+    let mut daily: Vec<DailyReport> = daily_map
+        .into_iter()
+        .map(|(date, mut costs)| {
+            costs.sort_by(|a, b| a.model.cmp(&b.model));
+            let total_cost = costs.iter().map(|c| c.total_cost).sum();
+            DailyReport {
+                date,
+                costs,
+                total_cost,
+            }
+        })
+        .collect();
+    daily.sort_by_key(|d| std::cmp::Reverse(d.date));
+
+    let mut by_model: Vec<TokenCostSnapshot> = model_totals.into_values().collect();
+    by_model.sort_by(|a, b| {
+        b.total_cost
+            .partial_cmp(&a.total_cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.model.cmp(&b.model))
+    });
+
+    CostSummary {
+        total_cost: by_model.iter().map(|m| m.total_cost).sum(),
+        today_cost: daily
+            .iter()
+            .find(|d| d.date == today)
+            .map(|d| d.total_cost)
+            .unwrap_or(0.0),
+        days,
+        by_model,
+        daily,
+        unpriced_models: Vec::new(),
+        converted: None,
+        by_workspace: sorted_groups(workspaces),
+        by_api_key: sorted_groups(api_keys),
+    }
+}
+
+/// Spend without token counts, from gateways that only track the amount
+/// spent per model, team and API key. Amounts are in USD.
+#[derive(Debug, Clone, Default)]
+pub struct SpendReport {
+    pub total: f64,
+    pub days: u32,
+    pub models: Vec<(String, f64)>,
+    pub workspaces: Vec<(String, f64)>,
+    pub api_keys: Vec<(String, f64)>,
+    pub daily: Vec<(NaiveDate, f64)>,
+}
+
+/// Shape a spend-only report like `build_summary`, with every group sorted
+/// by spend.
+pub fn spend_summary(report: SpendReport, today: NaiveDate) -> CostSummary {
+    let groups = |entries: Vec<(String, f64)>| {
+        let mut map: HashMap<String, GroupCost> = HashMap::new();
+        for (id, spend) in entries {
+            map.entry(id.clone())
+                .or_insert_with(|| GroupCost {
+                    id,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    total_cost: 0.0,
+                })
+                .total_cost += spend;
+        }
+        sorted_groups(map)
+    };
+
+    let mut by_model: Vec<TokenCostSnapshot> = groups(report.models)
+        .into_iter()
+        .map(|group| TokenCostSnapshot {
+            total_cost: group.total_cost,
+            ..empty_snapshot(&group.id)
+        })
+        .collect();
+    by_model.retain(|m| m.total_cost > 0.0);
+
+    let mut daily: Vec<DailyReport> = report
+        .daily
+        .into_iter()
+        .map(|(date, total_cost)| DailyReport {
+            date,
+            costs: Vec::new(),
+            total_cost,
+        })
+        .collect();
+    daily.sort_by_key(|d| std::cmp::Reverse(d.date));
+
+    CostSummary {
+        total_cost: report.total,
+        today_cost: daily
+            .iter()
+            .find(|d| d.date == today)
+            .map(|d| d.total_cost)
+            .unwrap_or(0.0),
+        days: report.days,
+        by_model,
+        daily,
+        unpriced_models: Vec::new(),
+        converted: None,
+        by_workspace: groups(report.workspaces),
+        by_api_key: groups(report.api_keys),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn cost(day: u32, model: &str, part: CostPart, amount: f64, workspace: &str) -> CostLine {
+        CostLine {
+            date: date(day),
+            model: model.to_string(),
+            part,
+            amount,
+            workspace: workspace.to_string(),
+        }
+    }
+
+    fn usage(day: u32, model: &str, api_key: &str, input: u64, output: u64) -> UsageLine {
+        UsageLine {
+            date: date(day),
+            model: model.to_string(),
+            api_key: api_key.to_string(),
+            workspace: "default".to_string(),
+            input_tokens: input,
+            output_tokens: output,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+        }
+    }
+
+    #[test]
+    fn builds_daily_and_model_totals() {
+        let costs = vec![
+            cost(17, "claude-sonnet-4-5", CostPart::Input, 3.0, "default"),
+            cost(17, "claude-sonnet-4-5", CostPart::Output, 1.5, "default"),
+            cost(18, "claude-sonnet-4-5", CostPart::Input, 0.6, "wrk_prod"),
+            cost(18, "web_search", CostPart::Other, 0.1, "wrk_prod"),
+        ];
+        let usage = vec![
+            usage(17, "claude-sonnet-4-5", "key_a", 1_000_000, 100_000),
+            usage(18, "claude-sonnet-4-5", "key_a", 200_000, 0),
+        ];
+        let summary = build_summary(&costs, &usage, 30, date(18));
+
+        assert!((summary.total_cost - 5.2).abs() < 1e-9);
+        assert!((summary.today_cost - 0.7).abs() < 1e-9);
+        assert_eq!(summary.daily.len(), 2);
+        assert_eq!(summary.daily[0].date, date(18));
+
+        let sonnet = &summary.by_model[0];
+        assert_eq!(sonnet.model, "claude-sonnet-4-5");
+        assert_eq!(sonnet.input_tokens, 1_200_000);
+        assert!((sonnet.input_cost - 3.6).abs() < 1e-9);
+        assert!((sonnet.output_cost - 1.5).abs() < 1e-9);
+        assert_eq!(summary.by_model[1].model, "web_search");
+
+        assert_eq!(summary.by_workspace[0].id, "default");
+        assert!((summary.by_workspace[0].total_cost - 4.5).abs() < 1e-9);
+        assert_eq!(summary.by_workspace[0].input_tokens, 1_200_000);
+        assert!((summary.by_workspace[1].total_cost - 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn apportions_spend_to_api_keys_by_tokens() {
+        let costs = vec![cost(18, "gpt-5", CostPart::Input, 4.0, "default")];
+        let usage = vec![
+            usage(18, "gpt-5", "key_ci", 300_000, 0),
+            usage(18, "gpt-5", "key_dev", 100_000, 0),
+        ];
+        let summary = build_summary(&costs, &usage, 30, date(18));
+
+        assert_eq!(summary.by_api_key.len(), 2);
+        assert_eq!(summary.by_api_key[0].id, "key_ci");
+        assert!((summary.by_api_key[0].total_cost - 3.0).abs() < 1e-9);
+        assert!((summary.by_api_key[1].total_cost - 1.0).abs() < 1e-9);
+        assert_eq!(summary.by_api_key[1].input_tokens, 100_000);
+    }
+
+    #[test]
+    fn spend_summary_sorts_groups_and_finds_today() {
+        let summary = spend_summary(
+            SpendReport {
+                total: 12.0,
+                days: 30,
+                models: vec![
+                    ("gpt-4o".into(), 3.0),
+                    ("claude-sonnet-4".into(), 9.0),
+                    ("unused".into(), 0.0),
+                ],
+                workspaces: vec![("ml".into(), 2.0), ("platform".into(), 10.0)],
+                api_keys: Vec::new(),
+                daily: vec![(date(17), 4.0), (date(18), 1.5)],
+            },
+            date(18),
+        );
+        assert_eq!(summary.total_cost, 12.0);
+        assert_eq!(summary.today_cost, 1.5);
+        let models: Vec<&str> = summary.by_model.iter().map(|m| m.model.as_str()).collect();
+        assert_eq!(models, vec!["claude-sonnet-4", "gpt-4o"]);
+        assert_eq!(summary.by_workspace[0].id, "platform");
+        assert_eq!(summary.daily[0].date, date(18));
+        assert!(summary.by_api_key.is_empty());
+    }
+
+    #[test]
+    fn empty_reports_give_empty_summary() {
+        let summary = build_summary(&[], &[], 30, date(18));
+        assert_eq!(summary.total_cost, 0.0);
+        assert!(summary.daily.is_empty());
+        assert!(summary.by_workspace.is_empty());
+        let json = serde_json::to_value(&summary).unwrap();
+        assert!(json.get("by_api_key").is_none());
+    }
+}
@@ -1,109 +1,456 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Built-in pricing table, used when no newer table has been imported.
+const BUILTIN_PRICING: &str = include_str!("pricing.toml");
+
 /// Per-model token pricing in dollars per token.
 #[derive(Debug, Clone)]
 pub struct ModelPricing {
-    pub model: &'static str,
     pub input_per_token: f64,
     pub output_per_token: f64,
     pub cache_read_per_token: f64,
+    /// Cache writes with the default 5-minute TTL.
     pub cache_create_per_token: f64,
+    /// Cache writes with a 1-hour TTL.
+    pub cache_create_1h_per_token: f64,
+    /// Long-context tiers, ascending by threshold. A request whose context
+    /// exceeds a tier's threshold is billed entirely at that tier's rates.
+    pub tiers: Vec<PricingTier>,
+}
+
+/// Dollars-per-token rates for one pricing tier.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenRates {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_create: f64,
+    pub cache_create_1h: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PricingTier {
+    /// Context size (input + cache read + cache write tokens) above which this tier applies.
+    pub above_context_tokens: u64,
+    pub rates: TokenRates,
+}
+
+impl ModelPricing {
+    /// Rates for a single request with the given context size.
+    pub fn rates_for(&self, context_tokens: u64) -> TokenRates {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|t| context_tokens > t.above_context_tokens)
+            .map(|t| t.rates)
+            .unwrap_or(TokenRates {
+                input: self.input_per_token,
+                output: self.output_per_token,
+                cache_read: self.cache_read_per_token,
+                cache_create: self.cache_create_per_token,
+                cache_create_1h: self.cache_create_1h_per_token,
+            })
+    }
+}
+
+/// A pricing entry as written in a pricing file (`[[models]]`) or in
+/// config.toml (`[[pricing]]`). Prices are in dollars per million tokens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricingEntry {
+    /// Exact model name, or a glob such as `gemini-2.5-flash*`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Regular expression matched against the raw and normalized model name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    pub input: f64,
+    pub output: f64,
+    #[serde(default)]
+    pub cache_read: f64,
+    #[serde(default)]
+    pub cache_write: f64,
+    /// 1-hour TTL cache write price; defaults to `cache_write`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_1h: Option<f64>,
+    /// Long-context tiers (e.g. requests above 200K input tokens).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tiers: Vec<PricingTierEntry>,
+}
+
+/// A `[[models.tiers]]` entry. Prices left out fall back to the base price.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricingTierEntry {
+    /// Context size in tokens above which this tier applies.
+    pub above: u64,
+    pub input: f64,
+    pub output: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_1h: Option<f64>,
+}
+
+/// On-disk pricing table format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricingFile {
+    /// Monotonic table version; the higher of built-in and imported wins.
+    pub version: u64,
+    #[serde(default)]
+    pub models: Vec<PricingEntry>,
+    /// Bedrock endpoint surcharges (`[[bedrock]]`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bedrock: Vec<BedrockPricing>,
+}
+
+/// Price multiplier for a model served from a regional Bedrock endpoint: an
+/// in-region model id or a geographic cross-region inference profile (`us.`,
+/// `eu.`, ...). The `global.` profile is billed at the base price.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BedrockPricing {
+    /// Normalized model name or glob, as in `[[models]]`.
+    pub model: String,
+    pub regional: f64,
+}
+
+/// Inference endpoint named by a Bedrock model id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BedrockEndpoint {
+    /// `global.` cross-region inference profile.
+    Global,
+    /// Geographic cross-region inference profile such as `us.` or `eu.`.
+    Geographic,
+    /// Plain model id, served from the caller's region.
+    InRegion,
+}
+
+/// Prefixes of Bedrock's geographic cross-region inference profiles.
+const BEDROCK_GEO_PREFIXES: &[&str] = &["us", "us-gov", "eu", "apac", "jp", "au", "ca"];
+
+/// Endpoint of a Bedrock Anthropic model id such as
+/// `us.anthropic.claude-sonnet-4-5-20250929-v1:0` or an inference profile
+/// ARN; `None` for ids that are not Bedrock's.
+pub fn bedrock_endpoint(model: &str) -> Option<BedrockEndpoint> {
+    let id = strip_arn(model);
+    if id.starts_with("anthropic.") {
+        return Some(BedrockEndpoint::InRegion);
+    }
+    let (prefix, rest) = id.split_once('.')?;
+    if !rest.starts_with("anthropic.") {
+        return None;
+    }
+    match prefix {
+        "global" => Some(BedrockEndpoint::Global),
+        p if BEDROCK_GEO_PREFIXES.contains(&p) => Some(BedrockEndpoint::Geographic),
+        _ => None,
+    }
+}
+
+/// Model id of an `arn:aws:bedrock:...:inference-profile/<id>` ARN.
+fn strip_arn(model: &str) -> &str {
+    if model.starts_with("arn:") {
+        model.rsplit('/').next().unwrap_or(model)
+    } else {
+        model
+    }
+}
+
+impl PricingFile {
+    /// Parse a pricing table from TOML, or JSON when `path` ends in `.json`.
+    pub fn parse(content: &str, path: &Path) -> Result<Self> {
+        if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            serde_json::from_str(content).context("Failed to parse pricing JSON")
+        } else {
+            toml::from_str(content).context("Failed to parse pricing TOML")
+        }
+    }
+}
+
+enum Matcher {
+    Exact(String),
+    Pattern(regex::Regex),
+}
+
+struct CompiledEntry {
+    matcher: Matcher,
+    pricing: ModelPricing,
+}
+
+/// A compiled list of pricing entries.
+pub struct PricingTable {
+    pub version: u64,
+    entries: Vec<CompiledEntry>,
+    bedrock: Vec<(Matcher, f64)>,
+}
+
+/// Translate a glob (`*`, `?`) into an anchored regular expression.
+pub(crate) fn glob_to_regex(glob: &str) -> String {
+    let mut re = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            _ => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    re
+}
+
+/// Check a single entry, returning a human-readable problem if it is invalid.
+pub fn validate_entry(entry: &PricingEntry) -> Result<(), String> {
+    compile_entry(entry).map(|_| ())
+}
+
+fn compile_entry(entry: &PricingEntry) -> Result<CompiledEntry, String> {
+    let (matcher, label) = match (&entry.model, &entry.regex) {
+        (Some(_), Some(_)) => return Err("set either `model` or `regex`, not both".to_string()),
+        (None, None) => return Err("missing `model` or `regex`".to_string()),
+        (Some(model), None) if model.contains(['*', '?']) => {
+            let re = regex::Regex::new(&glob_to_regex(model))
+                .map_err(|e| format!("invalid glob '{}': {}", model, e))?;
+            (Matcher::Pattern(re), model.clone())
+        }
+        (Some(model), None) => (Matcher::Exact(normalize_model(model)), model.clone()),
+        (None, Some(pattern)) => {
+            let re = regex::Regex::new(pattern)
+                .map_err(|e| format!("invalid regex '{}': {}", pattern, e))?;
+            (Matcher::Pattern(re), pattern.clone())
+        }
+    };
+    let cache_write_1h = entry.cache_write_1h.unwrap_or(entry.cache_write);
+    let mut prices = vec![
+        entry.input,
+        entry.output,
+        entry.cache_read,
+        entry.cache_write,
+        cache_write_1h,
+    ];
+
+    let mut tiers: Vec<PricingTier> = Vec::new();
+    for tier in &entry.tiers {
+        let rates = TokenRates {
+            input: tier.input,
+            output: tier.output,
+            cache_read: tier.cache_read.unwrap_or(entry.cache_read),
+            cache_create: tier.cache_write.unwrap_or(entry.cache_write),
+            cache_create_1h: tier.cache_write_1h.unwrap_or(cache_write_1h),
+        };
+        prices.extend([
+            rates.input,
+            rates.output,
+            rates.cache_read,
+            rates.cache_create,
+            rates.cache_create_1h,
+        ]);
+        tiers.push(PricingTier {
+            above_context_tokens: tier.above,
+            rates: TokenRates {
+                input: rates.input / 1e6,
+                output: rates.output / 1e6,
+                cache_read: rates.cache_read / 1e6,
+                cache_create: rates.cache_create / 1e6,
+                cache_create_1h: rates.cache_create_1h / 1e6,
+            },
+        });
+    }
+    if prices.iter().any(|p| !p.is_finite() || *p < 0.0) {
+        return Err(format!("'{}': prices must be non-negative numbers", label));
+    }
+    tiers.sort_by_key(|t| t.above_context_tokens);
+    if tiers
+        .windows(2)
+        .any(|w| w[0].above_context_tokens == w[1].above_context_tokens)
+    {
+        return Err(format!("'{}': duplicate tier threshold", label));
+    }
+
+    Ok(CompiledEntry {
+        matcher,
+        pricing: ModelPricing {
+            input_per_token: entry.input / 1e6,
+            output_per_token: entry.output / 1e6,
+            cache_read_per_token: entry.cache_read / 1e6,
+            cache_create_per_token: entry.cache_write / 1e6,
+            cache_create_1h_per_token: cache_write_1h / 1e6,
+            tiers,
+        },
+    })
+}
+
+impl PricingTable {
+    /// Compile a table; invalid entries are an error.
+    pub fn from_file(file: &PricingFile) -> Result<Self> {
+        let entries = file
+            .models
+            .iter()
+            .map(|e| compile_entry(e).map_err(|msg| anyhow::anyhow!(msg)))
+            .collect::<Result<Vec<_>>>()?;
+        let bedrock = file
+            .bedrock
+            .iter()
+            .map(|b| {
+                if !b.regional.is_finite() || b.regional <= 0.0 {
+                    anyhow::bail!("'{}': regional must be a positive number", b.model);
+                }
+                let matcher = if b.model.contains(['*', '?']) {
+                    Matcher::Pattern(regex::Regex::new(&glob_to_regex(&b.model))?)
+                } else {
+                    Matcher::Exact(normalize_model(&b.model))
+                };
+                Ok((matcher, b.regional))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            version: file.version,
+            entries,
+            bedrock,
+        })
+    }
+
+    /// The table embedded in the binary.
+    pub fn builtin() -> Self {
+        let file: PricingFile =
+            toml::from_str(BUILTIN_PRICING).expect("built-in pricing.toml is valid");
+        Self::from_file(&file).expect("built-in pricing.toml entries are valid")
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Find pricing for a model: exact names first, then globs/regexes in order.
+    pub fn lookup(&self, model: &str) -> Option<&ModelPricing> {
+        let normalized = normalize_model(model);
+        self.entries
+            .iter()
+            .find(|e| matches!(&e.matcher, Matcher::Exact(name) if *name == normalized))
+            .or_else(|| {
+                self.entries.iter().find(|e| match &e.matcher {
+                    Matcher::Pattern(re) => re.is_match(model) || re.is_match(&normalized),
+                    Matcher::Exact(_) => false,
+                })
+            })
+            .map(|e| &e.pricing)
+    }
+
+    /// Regional multiplier of a Bedrock model, if this table lists one.
+    fn bedrock_regional(&self, model: &str) -> Option<f64> {
+        let normalized = normalize_model(model);
+        self.bedrock
+            .iter()
+            .find(|(matcher, _)| match matcher {
+                Matcher::Exact(name) => *name == normalized,
+                Matcher::Pattern(re) => re.is_match(&normalized),
+            })
+            .map(|(_, regional)| *regional)
+    }
+}
+
+/// Where the imported pricing table is stored, respecting XDG_DATA_HOME.
+pub fn pricing_path() -> PathBuf {
+    let base = std::env::var("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("~"))
+                .join(".local")
+                .join("share")
+        });
+    base.join("ait").join("pricing.toml")
+}
+
+/// Effective pricing: user overrides from config.toml, then the imported and
+/// built-in tables with the newer one consulted first, so a model missing from
+/// one table still falls back to the other.
+pub struct PricingDb {
+    overrides: PricingTable,
+    tables: Vec<PricingTable>,
+}
+
+impl PricingDb {
+    /// Build from already-loaded parts. Invalid overrides are skipped with a warning.
+    pub fn new(mut tables: Vec<PricingTable>, overrides: &[PricingEntry]) -> Self {
+        let entries = overrides
+            .iter()
+            .filter_map(|e| match compile_entry(e) {
+                Ok(c) => Some(c),
+                Err(msg) => {
+                    eprintln!("pricing: ignoring config override: {}", msg);
+                    None
+                }
+            })
+            .collect();
+        tables.sort_by_key(|t| std::cmp::Reverse(t.version));
+        Self {
+            overrides: PricingTable {
+                version: 0,
+                entries,
+                bedrock: Vec::new(),
+            },
+            tables,
+        }
+    }
+
+    /// Load the built-in table, the imported table (if any) and config overrides.
+    pub fn load() -> Self {
+        let mut tables = vec![PricingTable::builtin()];
+        let path = pricing_path();
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            match PricingFile::parse(&content, &path).and_then(|f| PricingTable::from_file(&f)) {
+                Ok(imported) => tables.push(imported),
+                Err(e) => eprintln!("pricing: ignoring {}: {:#}", path.display(), e),
+            }
+        }
+        let config = crate::core::config::AppConfig::load().unwrap_or_default();
+        Self::new(tables, &config.pricing)
+    }
+
+    pub fn lookup(&self, model: &str) -> Option<&ModelPricing> {
+        self.overrides
+            .lookup(model)
+            .or_else(|| self.tables.iter().find_map(|t| t.lookup(model)))
+    }
+
+    /// Price multiplier for a Bedrock model id: the table's `regional` value
+    /// for regional endpoints, 1.0 for the global profile and unlisted models.
+    pub fn bedrock_multiplier(&self, model: &str) -> f64 {
+        match bedrock_endpoint(model) {
+            Some(BedrockEndpoint::Geographic | BedrockEndpoint::InRegion) => self
+                .tables
+                .iter()
+                .find_map(|t| t.bedrock_regional(model))
+                .unwrap_or(1.0),
+            Some(BedrockEndpoint::Global) | None => 1.0,
+        }
+    }
 }
 
-/// All known model pricing entries.
-static PRICING_TABLE: &[ModelPricing] = &[
-    ModelPricing {
-        model: "claude-haiku-4-5",
-        input_per_token: 1e-6,
-        output_per_token: 5e-6,
-        cache_read_per_token: 1e-7,
-        cache_create_per_token: 1.25e-6,
-    },
-    ModelPricing {
-        model: "claude-sonnet-4-5",
-        input_per_token: 3e-6,
-        output_per_token: 1.5e-5,
-        cache_read_per_token: 3e-7,
-        cache_create_per_token: 3.75e-6,
-    },
-    ModelPricing {
-        model: "claude-sonnet-4",
-        input_per_token: 3e-6,
-        output_per_token: 1.5e-5,
-        cache_read_per_token: 3e-7,
-        cache_create_per_token: 3.75e-6,
-    },
-    ModelPricing {
-        model: "claude-opus-4-5",
-        input_per_token: 5e-6,
-        output_per_token: 2.5e-5,
-        cache_read_per_token: 5e-7,
-        cache_create_per_token: 6.25e-6,
-    },
-    ModelPricing {
-        model: "claude-opus-4-6",
-        input_per_token: 5e-6,
-        output_per_token: 2.5e-5,
-        cache_read_per_token: 5e-7,
-        cache_create_per_token: 6.25e-6,
-    },
-    ModelPricing {
-        model: "claude-opus-4",
-        input_per_token: 1.5e-5,
-        output_per_token: 7.5e-5,
-        cache_read_per_token: 1.5e-6,
-        cache_create_per_token: 1.875e-5,
-    },
-    // GPT / Codex models
-    ModelPricing {
-        model: "gpt-5",
-        input_per_token: 1.25e-6,
-        output_per_token: 1e-5,
-        cache_read_per_token: 1.25e-7,
-        cache_create_per_token: 0.0,
-    },
-    ModelPricing {
-        model: "gpt-5-codex",
-        input_per_token: 1.25e-6,
-        output_per_token: 1e-5,
-        cache_read_per_token: 1.25e-7,
-        cache_create_per_token: 0.0,
-    },
-    ModelPricing {
-        model: "gpt-5.1",
-        input_per_token: 1.25e-6,
-        output_per_token: 1e-5,
-        cache_read_per_token: 1.25e-7,
-        cache_create_per_token: 0.0,
-    },
-    ModelPricing {
-        model: "gpt-5.2",
-        input_per_token: 1.75e-6,
-        output_per_token: 1.4e-5,
-        cache_read_per_token: 1.75e-7,
-        cache_create_per_token: 0.0,
-    },
-    ModelPricing {
-        model: "gpt-5.2-codex",
-        input_per_token: 1.75e-6,
-        output_per_token: 1.4e-5,
-        cache_read_per_token: 1.75e-7,
-        cache_create_per_token: 0.0,
-    },
-    ModelPricing {
-        model: "gpt-5.3-codex",
-        input_per_token: 1.75e-6,
-        output_per_token: 1.4e-5,
-        cache_read_per_token: 1.75e-7,
-        cache_create_per_token: 0.0,
-    },
-];
+static PRICING_DB: OnceLock<PricingDb> = OnceLock::new();
+
+/// The process-wide pricing database, loaded on first use.
+pub fn db() -> &'static PricingDb {
+    PRICING_DB.get_or_init(PricingDb::load)
+}
 
 /// Normalize a model name by stripping common prefixes and suffixes.
 /// Examples:
 ///   "anthropic.claude-sonnet-4-5-v2:0" -> "claude-sonnet-4-5"
+///   "us.anthropic.claude-sonnet-4-5-20250929-v1:0" -> "claude-sonnet-4-5"
 ///   "claude-sonnet-4-5-20250514" -> "claude-sonnet-4-5"
-fn normalize_model(model: &str) -> String {
+pub fn normalize_model(model: &str) -> String {
     let mut name = model.to_string();
 
+    // Strip Bedrock inference profile ARNs and region prefixes ("us.", "global.")
+    if bedrock_endpoint(model).is_some() {
+        let id = strip_arn(model);
+        name = id[id.find("anthropic.").unwrap_or(0)..].to_string();
+    }
+
     // Strip "anthropic." prefix
     if let Some(stripped) = name.strip_prefix("anthropic.") {
         name = stripped.to_string();
@@ -139,24 +486,38 @@ fn normalize_model(model: &str) -> String {
 
 /// Look up pricing for a model name. Returns None if unknown.
 pub fn lookup(model: &str) -> Option<&'static ModelPricing> {
-    let normalized = normalize_model(model);
-    PRICING_TABLE
-        .iter()
-        .find(|p| p.model == normalized)
+    db().lookup(model)
 }
 
-/// Calculate cost for given token counts.
+/// Price multiplier for a Bedrock model id; see [`PricingDb::bedrock_multiplier`].
+pub fn bedrock_multiplier(model: &str) -> f64 {
+    db().bedrock_multiplier(model)
+}
+
+/// Calculate cost for the token counts of a single request.
+///
+/// The request's context size (input + cache read + cache write tokens) picks
+/// the pricing tier. `cache_creation_1h_tokens` is the part of
+/// `cache_creation_tokens` written with a 1-hour TTL; the rest is billed at the
+/// 5-minute rate.
 pub fn calculate_cost(
     pricing: &ModelPricing,
     input_tokens: u64,
     output_tokens: u64,
     cache_read_tokens: u64,
     cache_creation_tokens: u64,
+    cache_creation_1h_tokens: u64,
 ) -> (f64, f64, f64, f64) {
-    let input_cost = input_tokens as f64 * pricing.input_per_token;
-    let output_cost = output_tokens as f64 * pricing.output_per_token;
-    let cache_read_cost = cache_read_tokens as f64 * pricing.cache_read_per_token;
-    let cache_creation_cost = cache_creation_tokens as f64 * pricing.cache_create_per_token;
+    let context = input_tokens + cache_read_tokens + cache_creation_tokens;
+    let rates = pricing.rates_for(context);
+    let cache_1h = cache_creation_1h_tokens.min(cache_creation_tokens);
+    let cache_5m = cache_creation_tokens - cache_1h;
+
+    let input_cost = input_tokens as f64 * rates.input;
+    let output_cost = output_tokens as f64 * rates.output;
+    let cache_read_cost = cache_read_tokens as f64 * rates.cache_read;
+    let cache_creation_cost =
+        cache_5m as f64 * rates.cache_create + cache_1h as f64 * rates.cache_create_1h;
     (input_cost, output_cost, cache_read_cost, cache_creation_cost)
 }
 
@@ -164,6 +525,23 @@ pub fn calculate_cost(
 mod tests {
     use super::*;
 
+    fn builtin_lookup(model: &str) -> Option<ModelPricing> {
+        PricingTable::builtin().lookup(model).cloned()
+    }
+
+    fn entry(model: Option<&str>, regex: Option<&str>, input: f64) -> PricingEntry {
+        PricingEntry {
+            model: model.map(String::from),
+            regex: regex.map(String::from),
+            input,
+            output: input * 5.0,
+            cache_read: 0.0,
+            cache_write: 0.0,
+            cache_write_1h: None,
+            tiers: Vec::new(),
+        }
+    }
+
     #[test]
     fn normalize_strips_anthropic_prefix() {
         assert_eq!(normalize_model("anthropic.claude-sonnet-4-5"), "claude-sonnet-4-5");
@@ -192,6 +570,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn normalize_strips_bedrock_profiles() {
+        assert_eq!(
+            normalize_model("us.anthropic.claude-sonnet-4-5-20250929-v1:0"),
+            "claude-sonnet-4-5"
+        );
+        assert_eq!(
+            normalize_model("global.anthropic.claude-opus-4-6-v1"),
+            "claude-opus-4-6"
+        );
+        assert_eq!(
+            normalize_model(
+                "arn:aws:bedrock:eu-west-1:123456789012:inference-profile/eu.anthropic.claude-haiku-4-5-20251001-v1:0"
+            ),
+            "claude-haiku-4-5"
+        );
+    }
+
+    #[test]
+    fn bedrock_endpoints() {
+        use BedrockEndpoint::*;
+        assert_eq!(bedrock_endpoint("anthropic.claude-3-5-sonnet-20241022-v2:0"), Some(InRegion));
+        assert_eq!(bedrock_endpoint("us.anthropic.claude-sonnet-4-5-20250929-v1:0"), Some(Geographic));
+        assert_eq!(bedrock_endpoint("apac.anthropic.claude-sonnet-4-20250514-v1:0"), Some(Geographic));
+        assert_eq!(bedrock_endpoint("global.anthropic.claude-sonnet-4-5-20250929-v1:0"), Some(Global));
+        assert_eq!(
+            bedrock_endpoint("arn:aws:bedrock:us-east-1:123:inference-profile/us.anthropic.claude-opus-4-1-20250805-v1:0"),
+            Some(Geographic)
+        );
+        assert_eq!(bedrock_endpoint("claude-sonnet-4-5"), None);
+        assert_eq!(bedrock_endpoint("claude-opus-4-5@20251101"), None);
+        assert_eq!(bedrock_endpoint("gpt-5.1"), None);
+    }
+
+    #[test]
+    fn bedrock_regional_multiplier() {
+        let db = PricingDb::new(vec![PricingTable::builtin()], &[]);
+        // Claude 4.5 models cost 10% more outside the global profile
+        let regional = db.bedrock_multiplier("us.anthropic.claude-sonnet-4-5-20250929-v1:0");
+        assert!((regional - 1.1).abs() < 1e-9);
+        let in_region = db.bedrock_multiplier("anthropic.claude-haiku-4-5-20251001-v1:0");
+        assert!((in_region - 1.1).abs() < 1e-9);
+        assert_eq!(db.bedrock_multiplier("global.anthropic.claude-sonnet-4-5-20250929-v1:0"), 1.0);
+        // Older models have a single price
+        assert_eq!(db.bedrock_multiplier("us.anthropic.claude-sonnet-4-20250514-v1:0"), 1.0);
+        // Not a Bedrock id
+        assert_eq!(db.bedrock_multiplier("claude-sonnet-4-5"), 1.0);
+    }
+
+    #[test]
+    fn bedrock_entries_are_validated() {
+        let file = PricingFile {
+            version: 1,
+            models: Vec::new(),
+            bedrock: vec![BedrockPricing {
+                model: "claude-*".into(),
+                regional: -1.0,
+            }],
+        };
+        assert!(PricingTable::from_file(&file).is_err());
+    }
+
     #[test]
     fn normalize_passthrough() {
         assert_eq!(normalize_model("claude-opus-4-6"), "claude-opus-4-6");
@@ -199,38 +639,165 @@ mod tests {
 
     #[test]
     fn lookup_known_model() {
-        let p = lookup("claude-sonnet-4-5").unwrap();
+        let p = builtin_lookup("claude-sonnet-4-5").unwrap();
         assert!((p.input_per_token - 3e-6).abs() < 1e-12);
         assert!((p.output_per_token - 1.5e-5).abs() < 1e-12);
     }
 
     #[test]
     fn lookup_with_prefix_and_suffix() {
-        let p = lookup("anthropic.claude-opus-4-6-20250514").unwrap();
+        let p = builtin_lookup("anthropic.claude-opus-4-6-20250514").unwrap();
         assert!((p.input_per_token - 5e-6).abs() < 1e-12);
     }
 
     #[test]
     fn lookup_unknown_returns_none() {
-        assert!(lookup("gpt-4o").is_none());
+        assert!(builtin_lookup("some-unreleased-model").is_none());
+    }
+
+    #[test]
+    fn builtin_covers_other_vendors() {
+        assert!(builtin_lookup("gpt-4o").is_some());
+        assert!(builtin_lookup("o3").is_some());
+        assert!(builtin_lookup("gemini-2.5-pro").is_some());
+        let p = builtin_lookup("claude-3-5-haiku-20241022").unwrap();
+        assert!((p.input_per_token - 8e-7).abs() < 1e-12);
+    }
+
+    #[test]
+    fn builtin_table_parses() {
+        let table = PricingTable::builtin();
+        assert!(table.version > 0);
+        assert!(table.len() > 20);
+    }
+
+    #[test]
+    fn glob_entry_matches() {
+        let file = PricingFile {
+            version: 1,
+            models: vec![entry(Some("gemini-2.5-flash*"), None, 0.3)],
+            bedrock: Vec::new(),
+        };
+        let table = PricingTable::from_file(&file).unwrap();
+        assert!(table.lookup("gemini-2.5-flash-preview-09-2025").is_some());
+        assert!(table.lookup("gemini-2.5-pro").is_none());
+    }
+
+    #[test]
+    fn regex_entry_matches_raw_and_normalized() {
+        let file = PricingFile {
+            version: 1,
+            models: vec![entry(None, Some("^claude-.*-4-5$"), 2.0)],
+            bedrock: Vec::new(),
+        };
+        let table = PricingTable::from_file(&file).unwrap();
+        // Normalized name matches even though the raw one carries a date suffix
+        assert!(table.lookup("claude-sonnet-4-5-20250929").is_some());
+        assert!(table.lookup("claude-sonnet-4").is_none());
+    }
+
+    #[test]
+    fn exact_entry_wins_over_pattern() {
+        let file = PricingFile {
+            version: 1,
+            models: vec![
+                entry(Some("gpt-*"), None, 9.0),
+                entry(Some("gpt-5"), None, 1.0),
+            ],
+            bedrock: Vec::new(),
+        };
+        let table = PricingTable::from_file(&file).unwrap();
+        let p = table.lookup("gpt-5").unwrap();
+        assert!((p.input_per_token - 1e-6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn overrides_take_precedence() {
+        let db = PricingDb::new(
+            vec![PricingTable::builtin()],
+            &[entry(Some("claude-sonnet-4-5"), None, 1.5), entry(Some("my-gateway-*"), None, 0.5)],
+        );
+        let p = db.lookup("claude-sonnet-4-5-20250929").unwrap();
+        assert!((p.input_per_token - 1.5e-6).abs() < 1e-12);
+        assert!(db.lookup("my-gateway-large").is_some());
+        // Non-overridden models still come from the table
+        assert!(db.lookup("gpt-5").is_some());
+    }
+
+    #[test]
+    fn invalid_overrides_are_skipped() {
+        let db = PricingDb::new(vec![PricingTable::builtin()], &[entry(None, Some("("), 1.0)]);
+        assert!(db.lookup("gpt-5").is_some());
+    }
+
+    #[test]
+    fn newer_table_wins_and_older_fills_gaps() {
+        let newer = PricingTable::from_file(&PricingFile {
+            version: u64::MAX,
+            models: vec![entry(Some("gpt-5"), None, 2.0)],
+            bedrock: Vec::new(),
+        })
+        .unwrap();
+        let older = PricingTable::from_file(&PricingFile {
+            version: 1,
+            models: vec![entry(Some("claude-sonnet-4-5"), None, 9.0)],
+            bedrock: Vec::new(),
+        })
+        .unwrap();
+        let db = PricingDb::new(vec![older, PricingTable::builtin(), newer], &[]);
+        let p = db.lookup("gpt-5").unwrap();
+        assert!((p.input_per_token - 2e-6).abs() < 1e-12);
+        // Built-in is newer than the version-1 table
+        let p = db.lookup("claude-sonnet-4-5").unwrap();
+        assert!((p.input_per_token - 3e-6).abs() < 1e-12);
+        assert!(db.lookup("gemini-2.5-pro").is_some());
+    }
+
+    #[test]
+    fn validate_entry_reports_problems() {
+        assert!(validate_entry(&entry(Some("gpt-5"), None, 1.0)).is_ok());
+        assert!(validate_entry(&entry(None, None, 1.0)).is_err());
+        assert!(validate_entry(&entry(Some("a"), Some("b"), 1.0)).is_err());
+        assert!(validate_entry(&entry(None, Some("(unclosed"), 1.0)).is_err());
+        assert!(validate_entry(&entry(Some("gpt-5"), None, -1.0)).is_err());
+    }
+
+    #[test]
+    fn parse_pricing_file_toml_and_json() {
+        let toml_src = r#"
+version = 7
+
+[[models]]
+model = "x-model"
+input = 1.0
+output = 2.0
+"#;
+        let file = PricingFile::parse(toml_src, Path::new("table.toml")).unwrap();
+        assert_eq!(file.version, 7);
+        assert_eq!(file.models[0].cache_read, 0.0);
+
+        let json_src = r#"{"version": 8, "models": [{"regex": "^y-", "input": 1, "output": 2}]}"#;
+        let file = PricingFile::parse(json_src, Path::new("table.json")).unwrap();
+        assert_eq!(file.version, 8);
+        assert!(PricingTable::from_file(&file).unwrap().lookup("y-1").is_some());
     }
 
     #[test]
     fn lookup_gpt5() {
-        let p = lookup("gpt-5").unwrap();
+        let p = builtin_lookup("gpt-5").unwrap();
         assert!((p.input_per_token - 1.25e-6).abs() < 1e-12);
         assert!((p.output_per_token - 1e-5).abs() < 1e-12);
     }
 
     #[test]
     fn lookup_gpt5_2() {
-        let p = lookup("gpt-5.2").unwrap();
+        let p = builtin_lookup("gpt-5.2").unwrap();
         assert!((p.input_per_token - 1.75e-6).abs() < 1e-12);
     }
 
     #[test]
     fn lookup_gpt5_3_codex() {
-        let p = lookup("gpt-5.3-codex").unwrap();
+        let p = builtin_lookup("gpt-5.3-codex").unwrap();
         assert!((p.input_per_token - 1.75e-6).abs() < 1e-12);
         assert!((p.output_per_token - 1.4e-5).abs() < 1e-12);
     }
@@ -243,11 +810,77 @@ mod tests {
 
     #[test]
     fn calculate_cost_basic() {
-        let p = lookup("claude-sonnet-4-5").unwrap();
-        let (ic, oc, crc, ccc) = calculate_cost(p, 1_000_000, 100_000, 500_000, 50_000);
-        assert!((ic - 3.0).abs() < 1e-6);      // 1M * 3e-6
-        assert!((oc - 1.5).abs() < 1e-6);      // 100K * 1.5e-5
-        assert!((crc - 0.15).abs() < 1e-6);    // 500K * 3e-7
-        assert!((ccc - 0.1875).abs() < 1e-6);  // 50K * 3.75e-6
+        let p = builtin_lookup("claude-sonnet-4-5").unwrap();
+        let (ic, oc, crc, ccc) = calculate_cost(&p, 100_000, 10_000, 50_000, 5_000, 0);
+        assert!((ic - 0.3).abs() < 1e-6);        // 100K * 3e-6
+        assert!((oc - 0.15).abs() < 1e-6);       // 10K * 1.5e-5
+        assert!((crc - 0.015).abs() < 1e-6);     // 50K * 3e-7
+        assert!((ccc - 0.01875).abs() < 1e-6);   // 5K * 3.75e-6
+    }
+
+    #[test]
+    fn calculate_cost_long_context_tier() {
+        let p = builtin_lookup("claude-sonnet-4-5").unwrap();
+        // 150K input + 60K cache read = 210K context, above the 200K threshold
+        let (ic, oc, crc, _) = calculate_cost(&p, 150_000, 10_000, 60_000, 0, 0);
+        assert!((ic - 0.9).abs() < 1e-6);        // 150K * 6e-6
+        assert!((oc - 0.225).abs() < 1e-6);      // 10K * 2.25e-5
+        assert!((crc - 0.036).abs() < 1e-6);     // 60K * 6e-7
+    }
+
+    #[test]
+    fn calculate_cost_splits_cache_ttl() {
+        let p = builtin_lookup("claude-sonnet-4-5").unwrap();
+        let (_, _, _, ccc) = calculate_cost(&p, 0, 0, 0, 10_000, 4_000);
+        // 6K * 3.75e-6 (5m) + 4K * 6e-6 (1h)
+        assert!((ccc - (0.0225 + 0.024)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rates_for_picks_highest_matching_tier() {
+        let mut e = entry(Some("tiered"), None, 1.0);
+        e.tiers = vec![
+            PricingTierEntry {
+                above: 500_000,
+                input: 4.0,
+                output: 20.0,
+                cache_read: None,
+                cache_write: None,
+                cache_write_1h: None,
+            },
+            PricingTierEntry {
+                above: 100_000,
+                input: 2.0,
+                output: 10.0,
+                cache_read: Some(0.2),
+                cache_write: None,
+                cache_write_1h: None,
+            },
+        ];
+        let table = PricingTable::from_file(&PricingFile {
+            version: 1,
+            models: vec![e],
+            bedrock: Vec::new(),
+        })
+        .unwrap();
+        let p = table.lookup("tiered").unwrap();
+        assert!((p.rates_for(100_000).input - 1e-6).abs() < 1e-12);
+        assert!((p.rates_for(100_001).input - 2e-6).abs() < 1e-12);
+        assert!((p.rates_for(100_001).cache_read - 2e-7).abs() < 1e-12);
+        assert!((p.rates_for(600_000).input - 4e-6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn cache_write_1h_defaults_to_cache_write() {
+        let mut e = entry(Some("m"), None, 1.0);
+        e.cache_write = 1.25;
+        let table = PricingTable::from_file(&PricingFile {
+            version: 1,
+            models: vec![e],
+            bedrock: Vec::new(),
+        })
+        .unwrap();
+        let p = table.lookup("m").unwrap();
+        assert!((p.cache_create_1h_per_token - 1.25e-6).abs() < 1e-12);
     }
 }
@@ -1,13 +1,14 @@
 use anyhow::{Context, Result};
 use chrono::NaiveDate;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::io::{BufRead, Seek, SeekFrom};
+use std::collections::HashSet;
+use std::io::{BufRead, Read, Seek, SeekFrom};
 use std::path::PathBuf;
 
-use crate::core::cost::cache::{CachedRecord, CostCache};
+use crate::core::cost::cache::{self, CachedRecord, CodexSessionState, CostCache};
 use crate::core::cost::pricing;
-use crate::core::models::cost::{CostSummary, DailyReport, TokenCostSnapshot};
+use crate::core::models::cost::{CostSummary, DailyReport, TokenCostSnapshot, UnpricedModel};
 use crate::core::providers::Provider;
 
 /// Convert ParsedRecords to CachedRecords for cache storage.
@@ -22,6 +23,9 @@ fn to_cached(records: &[ParsedRecord]) -> Vec<CachedRecord> {
             output_tokens: r.output_tokens,
             cache_read_tokens: r.cache_read_tokens,
             cache_creation_tokens: r.cache_creation_tokens,
+            cache_creation_1h_tokens: r.cache_creation_1h_tokens,
+            project: r.project.clone(),
+            dedup_key: r.dedup_key.clone(),
         })
         .collect()
 }
@@ -40,7 +44,10 @@ fn from_cached(cached: Vec<CachedRecord>) -> Vec<ParsedRecord> {
                 output_tokens: c.output_tokens,
                 cache_read_tokens: c.cache_read_tokens,
                 cache_creation_tokens: c.cache_creation_tokens,
+                cache_creation_1h_tokens: c.cache_creation_1h_tokens,
                 date,
+                project: c.project,
+                dedup_key: c.dedup_key,
             })
         })
         .collect()
@@ -61,6 +68,13 @@ struct JsonlUsage {
     output_tokens: Option<u64>,
     cache_read_input_tokens: Option<u64>,
     cache_creation_input_tokens: Option<u64>,
+    cache_creation: Option<JsonlCacheCreation>,
+}
+
+/// Breakdown of `cache_creation_input_tokens` by cache TTL.
+#[derive(Deserialize)]
+struct JsonlCacheCreation {
+    ephemeral_1h_input_tokens: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -71,6 +85,7 @@ struct JsonlLine {
     #[serde(rename = "requestId")]
     request_id: Option<String>,
     timestamp: Option<String>,
+    cwd: Option<String>,
 }
 
 // ── Codex JSONL structs ───────────────────────────────────────────────
@@ -88,6 +103,7 @@ struct CodexPayload {
     #[serde(rename = "type")]
     payload_type: Option<String>,
     model: Option<String>,
+    cwd: Option<String>,
     info: Option<CodexTokenInfo>,
 }
 
@@ -115,12 +131,19 @@ struct ParsedRecord {
     output_tokens: u64,
     cache_read_tokens: u64,
     cache_creation_tokens: u64,
+    /// Portion of `cache_creation_tokens` written with a 1-hour TTL.
+    cache_creation_1h_tokens: u64,
     date: NaiveDate,
+    /// Working directory of the session, used for project-scoped budgets.
+    project: Option<String>,
+    /// Claude streaming dedup key ("{message_id}:{request_id}"), if any.
+    dedup_key: Option<String>,
 }
 
 // ── Claude file discovery ─────────────────────────────────────────────
 
-fn discover_claude_files() -> Vec<PathBuf> {
+/// Claude `projects/` directories, in the order they are searched.
+fn claude_log_roots() -> Vec<PathBuf> {
     let mut roots: Vec<PathBuf> = Vec::new();
 
     if let Some(home) = dirs::home_dir() {
@@ -135,47 +158,53 @@ fn discover_claude_files() -> Vec<PathBuf> {
         roots.push(config_home.join("claude"));
     }
 
+    roots.into_iter().map(|root| root.join("projects")).collect()
+}
+
+fn discover_claude_files() -> Vec<PathBuf> {
     let mut files: Vec<PathBuf> = Vec::new();
-    for root in roots {
-        let projects_dir = root.join("projects");
-        if !projects_dir.is_dir() {
-            continue;
-        }
-        if let Ok(projects) = std::fs::read_dir(&projects_dir) {
-            for project_entry in projects.flatten() {
-                let project_path = project_entry.path();
-                if !project_path.is_dir() {
-                    continue;
-                }
+    for projects_dir in claude_log_roots() {
+        collect_claude_projects(&projects_dir, &mut files);
+    }
+    files
+}
 
-                // Level 1: {project-dir}/*.jsonl
-                if let Ok(entries) = std::fs::read_dir(&project_path) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if path.is_file()
-                            && path.extension().and_then(|e| e.to_str()) == Some("jsonl")
-                        {
-                            files.push(path);
-                        }
+fn collect_claude_projects(projects_dir: &PathBuf, files: &mut Vec<PathBuf>) {
+    if !projects_dir.is_dir() {
+        return;
+    }
+    if let Ok(projects) = std::fs::read_dir(projects_dir) {
+        for project_entry in projects.flatten() {
+            let project_path = project_entry.path();
+            if !project_path.is_dir() {
+                continue;
+            }
+
+            // Level 1: {project-dir}/*.jsonl
+            if let Ok(entries) = std::fs::read_dir(&project_path) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if path.is_file() && path.extension().and_then(|e| e.to_str()) == Some("jsonl")
+                    {
+                        files.push(path);
                     }
                 }
+            }
 
-                // Level 2: {project-dir}/{uuid-dir}/subagents/*.jsonl
-                if let Ok(subdirs) = std::fs::read_dir(&project_path) {
-                    for subdir in subdirs.flatten() {
-                        let subagents_dir = subdir.path().join("subagents");
-                        if !subagents_dir.is_dir() {
-                            continue;
-                        }
-                        if let Ok(sa_entries) = std::fs::read_dir(&subagents_dir) {
-                            for sa_entry in sa_entries.flatten() {
-                                let path = sa_entry.path();
-                                if path.is_file()
-                                    && path.extension().and_then(|e| e.to_str())
-                                        == Some("jsonl")
-                                {
-                                    files.push(path);
-                                }
+            // Level 2: {project-dir}/{uuid-dir}/subagents/*.jsonl
+            if let Ok(subdirs) = std::fs::read_dir(&project_path) {
+                for subdir in subdirs.flatten() {
+                    let subagents_dir = subdir.path().join("subagents");
+                    if !subagents_dir.is_dir() {
+                        continue;
+                    }
+                    if let Ok(sa_entries) = std::fs::read_dir(&subagents_dir) {
+                        for sa_entry in sa_entries.flatten() {
+                            let path = sa_entry.path();
+                            if path.is_file()
+                                && path.extension().and_then(|e| e.to_str()) == Some("jsonl")
+                            {
+                                files.push(path);
                             }
                         }
                     }
@@ -183,13 +212,11 @@ fn discover_claude_files() -> Vec<PathBuf> {
             }
         }
     }
-
-    files
 }
 
 // ── Codex file discovery ──────────────────────────────────────────────
 
-fn discover_codex_files() -> Vec<PathBuf> {
+fn codex_log_roots() -> Vec<PathBuf> {
     let mut roots: Vec<PathBuf> = Vec::new();
 
     // $CODEX_HOME/sessions/
@@ -203,8 +230,12 @@ fn discover_codex_files() -> Vec<PathBuf> {
         roots.push(home.join(".codex").join("archived_sessions"));
     }
 
+    roots
+}
+
+fn discover_codex_files() -> Vec<PathBuf> {
     let mut files: Vec<PathBuf> = Vec::new();
-    for root in roots {
+    for root in codex_log_roots() {
         if !root.is_dir() {
             continue;
         }
@@ -213,6 +244,44 @@ fn discover_codex_files() -> Vec<PathBuf> {
     files
 }
 
+/// A session log directory searched by the scanner.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogDir {
+    pub provider: Provider,
+    pub path: PathBuf,
+    pub exists: bool,
+    /// Number of `.jsonl` session files found under `path`.
+    pub files: usize,
+}
+
+/// Every log directory the scanner searches, with the session files in each.
+pub fn log_dirs() -> Vec<LogDir> {
+    let mut dirs = Vec::new();
+    for path in claude_log_roots() {
+        let mut files = Vec::new();
+        collect_claude_projects(&path, &mut files);
+        dirs.push(LogDir {
+            provider: Provider::Claude,
+            exists: path.is_dir(),
+            files: files.len(),
+            path,
+        });
+    }
+    for path in codex_log_roots() {
+        let mut files = Vec::new();
+        if path.is_dir() {
+            collect_jsonl_recursive(&path, &mut files, 4);
+        }
+        dirs.push(LogDir {
+            provider: Provider::Codex,
+            exists: path.is_dir(),
+            files: files.len(),
+            path,
+        });
+    }
+    dirs
+}
+
 /// Recursively collect *.jsonl files up to `max_depth` levels deep.
 fn collect_jsonl_recursive(dir: &PathBuf, files: &mut Vec<PathBuf>, max_depth: u32) {
     if max_depth == 0 {
@@ -246,22 +315,42 @@ fn detect_vertex_ai(msg_id: &str, request_id: &str, model: &str) -> bool {
         || model.contains('@')
 }
 
-/// Parse a single Claude/Vertex AI JSONL file, optionally resuming from a byte offset.
+/// Detect if a Claude log entry is actually AWS Bedrock traffic: Bedrock
+/// message ids carry `_bdrk_` and model ids look like
+/// `us.anthropic.claude-sonnet-4-5-20250929-v1:0`.
+fn detect_bedrock(msg_id: &str, request_id: &str, model: &str) -> bool {
+    msg_id.contains("_bdrk_")
+        || request_id.contains("_bdrk_")
+        || pricing::bedrock_endpoint(model).is_some()
+}
+
+/// Parse a single Claude/Vertex AI/Bedrock JSONL file, optionally resuming from a byte offset.
+///
+/// `prior` holds the records already counted for the bytes before `offset`; a
+/// streaming chunk appended later for one of those messages replaces the earlier
+/// record instead of being counted twice. Returns all records for the file and
+/// the offset just past the last complete line, so a line still being written is
+/// picked up again on the next run.
 fn parse_claude_file(
     path: &PathBuf,
     offset: u64,
+    prior: Vec<ParsedRecord>,
 ) -> Result<(Vec<ParsedRecord>, u64)> {
     let file = std::fs::File::open(path)
         .with_context(|| format!("Failed to open {}", path.display()))?;
-    let file_size = file.metadata().map(|m| m.len()).unwrap_or(0);
 
     let mut reader = std::io::BufReader::new(file);
     if offset > 0 {
         reader.seek(SeekFrom::Start(offset))?;
     }
 
-    let mut records: Vec<ParsedRecord> = Vec::new();
-    let mut dedup: HashMap<(String, String), usize> = HashMap::new();
+    let mut records: Vec<ParsedRecord> = prior;
+    let mut dedup: HashMap<String, usize> = records
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, r)| r.dedup_key.clone().map(|k| (k, idx)))
+        .collect();
+    let mut consumed = offset;
     let mut line_buf = String::new();
 
     loop {
@@ -272,6 +361,15 @@ fn parse_claude_file(
         }
 
         let line = line_buf.trim();
+        // An unterminated last line may still be in the middle of being written;
+        // leave it for the next run unless it is already a complete JSON value.
+        if !line_buf.ends_with('\n')
+            && serde_json::from_str::<serde::de::IgnoredAny>(line).is_err()
+        {
+            break;
+        }
+        consumed += bytes_read as u64;
+
         if line.is_empty() || !is_candidate_line(line) {
             continue;
         }
@@ -311,15 +409,23 @@ fn parse_claude_file(
             })
             .unwrap_or_else(|| chrono::Utc::now().date_naive());
 
-        let msg_id = message.id.as_deref().unwrap_or("");
-        let req_id = parsed.request_id.as_deref().unwrap_or("");
+        let msg_id = message.id.unwrap_or_default();
+        let req_id = parsed.request_id.unwrap_or_default();
 
-        let provider = if detect_vertex_ai(msg_id, req_id, &model) {
+        let provider = if detect_bedrock(&msg_id, &req_id, &model) {
+            Provider::Bedrock
+        } else if detect_vertex_ai(&msg_id, &req_id, &model) {
             Provider::VertexAi
         } else {
             Provider::Claude
         };
 
+        let dedup_key = if !msg_id.is_empty() || !req_id.is_empty() {
+            Some(format!("{}:{}", msg_id, req_id))
+        } else {
+            None
+        };
+
         let record = ParsedRecord {
             provider,
             model,
@@ -327,53 +433,92 @@ fn parse_claude_file(
             output_tokens: usage.output_tokens.unwrap_or(0),
             cache_read_tokens: usage.cache_read_input_tokens.unwrap_or(0),
             cache_creation_tokens: usage.cache_creation_input_tokens.unwrap_or(0),
+            cache_creation_1h_tokens: usage
+                .cache_creation
+                .as_ref()
+                .and_then(|c| c.ephemeral_1h_input_tokens)
+                .unwrap_or(0),
             date,
+            project: parsed.cwd,
+            dedup_key: dedup_key.clone(),
         };
 
-        let msg_id_owned = message.id.unwrap_or_default();
-        let req_id_owned = parsed.request_id.unwrap_or_default();
-        if !msg_id_owned.is_empty() || !req_id_owned.is_empty() {
-            let key = (msg_id_owned, req_id_owned);
-            if let Some(idx) = dedup.get(&key) {
-                records[*idx] = record;
-            } else {
-                let idx = records.len();
-                dedup.insert(key, idx);
-                records.push(record);
+        match dedup_key {
+            Some(key) => {
+                if let Some(idx) = dedup.get(&key) {
+                    records[*idx] = record;
+                } else {
+                    dedup.insert(key, records.len());
+                    records.push(record);
+                }
             }
-        } else {
-            records.push(record);
+            None => records.push(record),
         }
     }
 
-    Ok((records, file_size))
+    Ok((records, consumed))
 }
 
 // ── Codex parser ──────────────────────────────────────────────────────
 
 /// Fast ASCII check for Codex JSONL lines.
 fn is_codex_candidate(line: &str) -> bool {
-    line.contains("\"token_count\"") || line.contains("\"turn_context\"")
+    line.contains("\"token_count\"")
+        || line.contains("\"turn_context\"")
+        || line.contains("\"session_meta\"")
+}
+
+/// Turn a cumulative `total_token_usage` into the delta since the previous
+/// event. A total lower than the running one means the counter restarted
+/// (e.g. a new process appended to the session), so the total itself is the delta.
+fn codex_delta(state: &mut CodexSessionState, total: &CodexTokenUsage) -> (u64, u64, u64) {
+    let input = total.input_tokens.unwrap_or(0);
+    let output = total.output_tokens.unwrap_or(0);
+    let cached = total.cached_input_tokens.unwrap_or(0);
+
+    let reset = input < state.total_input_tokens
+        || output < state.total_output_tokens
+        || cached < state.total_cached_tokens;
+    let delta = if reset {
+        (input, output, cached)
+    } else {
+        (
+            input - state.total_input_tokens,
+            output - state.total_output_tokens,
+            cached - state.total_cached_tokens,
+        )
+    };
+
+    state.total_input_tokens = input;
+    state.total_output_tokens = output;
+    state.total_cached_tokens = cached;
+    delta
 }
 
-/// Parse a single Codex JSONL session file.
+/// Parse a single Codex JSONL session file, optionally resuming from a byte offset.
+///
+/// `total_token_usage` is cumulative per session, so each `token_count` event is
+/// converted into a delta against the previous total and attributed to the
+/// event's own day and the model active at that point. `prior` and `state` carry
+/// the records and running totals for the bytes before `offset`. Returns the
+/// per-request records, the offset past the last complete line, and the
+/// updated session state.
 fn parse_codex_file(
     path: &PathBuf,
     offset: u64,
-) -> Result<(Vec<ParsedRecord>, u64)> {
+    prior: Vec<ParsedRecord>,
+    mut state: CodexSessionState,
+) -> Result<(Vec<ParsedRecord>, u64, CodexSessionState)> {
     let file = std::fs::File::open(path)
         .with_context(|| format!("Failed to open {}", path.display()))?;
-    let file_size = file.metadata().map(|m| m.len()).unwrap_or(0);
 
     let mut reader = std::io::BufReader::new(file);
     if offset > 0 {
         reader.seek(SeekFrom::Start(offset))?;
     }
 
-    // total_token_usage is cumulative per session — only the LAST event per model
-    // has the correct total. Using a HashMap ensures we keep only the final value.
-    let mut last_per_model: HashMap<String, ParsedRecord> = HashMap::new();
-    let mut current_model: Option<String> = None;
+    let mut records = prior;
+    let mut consumed = offset;
     let mut line_buf = String::new();
 
     loop {
@@ -384,6 +529,13 @@ fn parse_codex_file(
         }
 
         let line = line_buf.trim();
+        if !line_buf.ends_with('\n')
+            && serde_json::from_str::<serde::de::IgnoredAny>(line).is_err()
+        {
+            break;
+        }
+        consumed += bytes_read as u64;
+
         if line.is_empty() || !is_codex_candidate(line) {
             continue;
         }
@@ -398,10 +550,19 @@ fn parse_codex_file(
             None => continue,
         };
 
-        // Track model from turn_context events
+        // Track model and working directory from session_meta / turn_context events
+        if parsed.line_type.as_deref() == Some("session_meta") {
+            if let Some(cwd) = payload.cwd {
+                state.cwd = Some(cwd);
+            }
+            continue;
+        }
         if parsed.line_type.as_deref() == Some("turn_context") {
             if let Some(model) = payload.model {
-                current_model = Some(model);
+                state.model = Some(model);
+            }
+            if let Some(cwd) = payload.cwd {
+                state.cwd = Some(cwd);
             }
             continue;
         }
@@ -419,16 +580,33 @@ fn parse_codex_file(
             None => continue,
         };
 
-        // Prefer total_token_usage, fall back to last_token_usage
-        let usage = match info.total_token_usage.or(info.last_token_usage) {
-            Some(u) => u,
-            None => continue,
+        // Prefer the cumulative total; fall back to the per-turn usage, which is
+        // already a delta and just advances the running totals.
+        let (input, output, cached) = if let Some(total) = &info.total_token_usage {
+            codex_delta(&mut state, total)
+        } else if let Some(last) = &info.last_token_usage {
+            let delta = (
+                last.input_tokens.unwrap_or(0),
+                last.output_tokens.unwrap_or(0),
+                last.cached_input_tokens.unwrap_or(0),
+            );
+            state.total_input_tokens += delta.0;
+            state.total_output_tokens += delta.1;
+            state.total_cached_tokens += delta.2;
+            delta
+        } else {
+            continue;
         };
 
-        // Determine model: info.model_name > current turn_context model
-        let model = info
-            .model_name
-            .or_else(|| current_model.clone())
+        if input == 0 && output == 0 && cached == 0 {
+            continue;
+        }
+
+        // Determine model: current turn_context model > info.model_name
+        let model = state
+            .model
+            .clone()
+            .or(info.model_name)
             .unwrap_or_else(|| "unknown-codex".to_string());
 
         let date = parsed
@@ -448,22 +626,21 @@ fn parse_codex_file(
             })
             .unwrap_or_else(|| chrono::Utc::now().date_naive());
 
-        last_per_model.insert(
-            model.clone(),
-            ParsedRecord {
-                provider: Provider::Codex,
-                model,
-                input_tokens: usage.input_tokens.unwrap_or(0),
-                output_tokens: usage.output_tokens.unwrap_or(0),
-                cache_read_tokens: usage.cached_input_tokens.unwrap_or(0),
-                cache_creation_tokens: 0,
-                date,
-            },
-        );
+        records.push(ParsedRecord {
+            provider: Provider::Codex,
+            model,
+            input_tokens: input,
+            output_tokens: output,
+            cache_read_tokens: cached,
+            cache_creation_tokens: 0,
+            cache_creation_1h_tokens: 0,
+            date,
+            project: state.cwd.clone(),
+            dedup_key: None,
+        });
     }
 
-    let records: Vec<ParsedRecord> = last_per_model.into_values().collect();
-    Ok((records, file_size))
+    Ok((records, consumed, state))
 }
 
 // ── Shared helpers ────────────────────────────────────────────────────
@@ -480,85 +657,138 @@ fn file_mtime_ms(path: &PathBuf) -> u64 {
         .unwrap_or(0)
 }
 
-/// Build a `CostSummary` from a set of records for a given date range.
-fn build_summary(records: Vec<ParsedRecord>, days: u32, today: NaiveDate) -> CostSummary {
-    // Group by date + model
-    let mut date_model_map: HashMap<(NaiveDate, String), ParsedRecord> = HashMap::new();
-    for record in records {
-        let key = (record.date, record.model.clone());
-        let entry = date_model_map.entry(key).or_insert(ParsedRecord {
-            provider: record.provider,
-            model: record.model.clone(),
-            input_tokens: 0,
-            output_tokens: 0,
-            cache_read_tokens: 0,
-            cache_creation_tokens: 0,
-            date: record.date,
-        });
-        entry.input_tokens += record.input_tokens;
-        entry.output_tokens += record.output_tokens;
-        entry.cache_read_tokens += record.cache_read_tokens;
-        entry.cache_creation_tokens += record.cache_creation_tokens;
+/// Fingerprint the first `len` bytes of a file (see `cache::fingerprint`).
+/// Returns None if the file cannot be read or is shorter than `len`.
+fn head_hash(path: &PathBuf, len: u64) -> Option<u64> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut buf = vec![0u8; len as usize];
+    file.read_exact(&mut buf).ok()?;
+    Some(cache::fingerprint(&buf))
+}
+
+/// Return the records for one Claude/Vertex AI/Bedrock file, re-using the cache where
+/// possible: unchanged files come straight from the cache, appended files resume
+/// from the last parsed offset, and truncated or rewritten files are re-read.
+fn scan_claude_file(cache: &mut CostCache, file_path: &PathBuf) -> Option<Vec<ParsedRecord>> {
+    let path_str = file_path.to_string_lossy().to_string();
+    let mtime_ms = file_mtime_ms(file_path);
+    let file_size = std::fs::metadata(file_path).map(|m| m.len()).ok()?;
+
+    if cache.is_unchanged(&path_str, mtime_ms, file_size) {
+        return Some(from_cached(cache.get_records(&path_str)));
     }
 
-    let mut daily_map: HashMap<NaiveDate, Vec<TokenCostSnapshot>> = HashMap::new();
-    let mut model_totals: HashMap<String, TokenCostSnapshot> = HashMap::new();
+    let offset = cache.resume_offset(&path_str, file_size, |len| head_hash(file_path, len));
+    let prior = if offset > 0 {
+        from_cached(cache.get_records(&path_str))
+    } else {
+        Vec::new()
+    };
+
+    let (records, parsed_bytes) = parse_claude_file(file_path, offset, prior).ok()?;
+    let head_len = parsed_bytes.min(cache::HEAD_HASH_BYTES);
+    let head = (head_len, head_hash(file_path, head_len).unwrap_or(0));
+    cache.update(&path_str, mtime_ms, file_size, parsed_bytes, head, to_cached(&records));
+    Some(records)
+}
+
+/// Return the records for one Codex session file. Appended files resume from
+/// the last parsed offset with the stored running totals; anything else is
+/// re-read from the start.
+fn scan_codex_file(cache: &mut CostCache, file_path: &PathBuf) -> Option<Vec<ParsedRecord>> {
+    let path_str = file_path.to_string_lossy().to_string();
+    let mtime_ms = file_mtime_ms(file_path);
+    let file_size = std::fs::metadata(file_path).map(|m| m.len()).ok()?;
+
+    if cache.is_unchanged(&path_str, mtime_ms, file_size) {
+        return Some(from_cached(cache.get_records(&path_str)));
+    }
+
+    let mut offset = cache.resume_offset(&path_str, file_size, |len| head_hash(file_path, len));
+    let state = if offset > 0 {
+        cache.get_codex_state(&path_str)
+    } else {
+        None
+    };
+    // Without running totals a resumed parse would count cumulative usage twice.
+    if state.is_none() {
+        offset = 0;
+    }
+    let prior = if offset > 0 {
+        from_cached(cache.get_records(&path_str))
+    } else {
+        Vec::new()
+    };
+
+    let (records, parsed_bytes, state) =
+        parse_codex_file(file_path, offset, prior, state.unwrap_or_default()).ok()?;
+    let head_len = parsed_bytes.min(cache::HEAD_HASH_BYTES);
+    let head = (head_len, head_hash(file_path, head_len).unwrap_or(0));
+    cache.update(&path_str, mtime_ms, file_size, parsed_bytes, head, to_cached(&records));
+    cache.set_codex_state(&path_str, state);
+    Some(records)
+}
 
-    for ((date, _model), record) in &date_model_map {
+/// Build a `CostSummary` from a set of records for a given date range.
+///
+/// Each record is priced on its own (so per-request tiers and cache TTLs
+/// apply) before being aggregated per day and model.
+fn build_summary(records: Vec<ParsedRecord>, days: u32, today: NaiveDate) -> CostSummary {
+    let mut date_model_map: HashMap<(NaiveDate, String), TokenCostSnapshot> = HashMap::new();
+    for record in &records {
         let pricing_entry = pricing::lookup(&record.model);
         let (input_cost, output_cost, cache_read_cost, cache_creation_cost) =
             if let Some(p) = pricing_entry {
-                pricing::calculate_cost(
+                let (input, output, cache_read, cache_creation) = pricing::calculate_cost(
                     p,
                     record.input_tokens,
                     record.output_tokens,
                     record.cache_read_tokens,
                     record.cache_creation_tokens,
-                )
+                    record.cache_creation_1h_tokens,
+                );
+                // Regional Bedrock endpoints carry a surcharge over the base price
+                let m = match record.provider {
+                    Provider::Bedrock => pricing::bedrock_multiplier(&record.model),
+                    _ => 1.0,
+                };
+                (input * m, output * m, cache_read * m, cache_creation * m)
             } else {
                 (0.0, 0.0, 0.0, 0.0)
             };
 
-        let total_cost = input_cost + output_cost + cache_read_cost + cache_creation_cost;
-
-        let snapshot = TokenCostSnapshot {
-            model: record.model.clone(),
-            input_tokens: record.input_tokens,
-            output_tokens: record.output_tokens,
-            cache_read_tokens: record.cache_read_tokens,
-            cache_creation_tokens: record.cache_creation_tokens,
-            input_cost,
-            output_cost,
-            cache_read_cost,
-            cache_creation_cost,
-            total_cost,
-        };
+        let entry = date_model_map
+            .entry((record.date, record.model.clone()))
+            .or_insert_with(|| empty_snapshot(&record.model, pricing_entry.is_some()));
+        entry.input_tokens += record.input_tokens;
+        entry.output_tokens += record.output_tokens;
+        entry.cache_read_tokens += record.cache_read_tokens;
+        entry.cache_creation_tokens += record.cache_creation_tokens;
+        entry.input_cost += input_cost;
+        entry.output_cost += output_cost;
+        entry.cache_read_cost += cache_read_cost;
+        entry.cache_creation_cost += cache_creation_cost;
+        entry.total_cost += input_cost + output_cost + cache_read_cost + cache_creation_cost;
+    }
 
-        daily_map.entry(*date).or_default().push(snapshot.clone());
+    let mut daily_map: HashMap<NaiveDate, Vec<TokenCostSnapshot>> = HashMap::new();
+    let mut model_totals: HashMap<String, TokenCostSnapshot> = HashMap::new();
 
+    for ((date, _model), snapshot) in date_model_map {
         let model_entry = model_totals
-            .entry(record.model.clone())
-            .or_insert(TokenCostSnapshot {
-                model: record.model.clone(),
-                input_tokens: 0,
-                output_tokens: 0,
-                cache_read_tokens: 0,
-                cache_creation_tokens: 0,
-                input_cost: 0.0,
-                output_cost: 0.0,
-                cache_read_cost: 0.0,
-                cache_creation_cost: 0.0,
-                total_cost: 0.0,
-            });
-        model_entry.input_tokens += record.input_tokens;
-        model_entry.output_tokens += record.output_tokens;
-        model_entry.cache_read_tokens += record.cache_read_tokens;
-        model_entry.cache_creation_tokens += record.cache_creation_tokens;
-        model_entry.input_cost += input_cost;
-        model_entry.output_cost += output_cost;
-        model_entry.cache_read_cost += cache_read_cost;
-        model_entry.cache_creation_cost += cache_creation_cost;
-        model_entry.total_cost += total_cost;
+            .entry(snapshot.model.clone())
+            .or_insert_with(|| empty_snapshot(&snapshot.model, snapshot.priced));
+        model_entry.input_tokens += snapshot.input_tokens;
+        model_entry.output_tokens += snapshot.output_tokens;
+        model_entry.cache_read_tokens += snapshot.cache_read_tokens;
+        model_entry.cache_creation_tokens += snapshot.cache_creation_tokens;
+        model_entry.input_cost += snapshot.input_cost;
+        model_entry.output_cost += snapshot.output_cost;
+        model_entry.cache_read_cost += snapshot.cache_read_cost;
+        model_entry.cache_creation_cost += snapshot.cache_creation_cost;
+        model_entry.total_cost += snapshot.total_cost;
+
+        daily_map.entry(date).or_default().push(snapshot);
     }
 
     let mut daily: Vec<DailyReport> = daily_map
@@ -572,7 +802,7 @@ fn build_summary(records: Vec<ParsedRecord>, days: u32, today: NaiveDate) -> Cos
             }
         })
         .collect();
-    daily.sort_by(|a, b| b.date.cmp(&a.date));
+    daily.sort_by_key(|d| std::cmp::Reverse(d.date));
 
     let mut by_model: Vec<TokenCostSnapshot> = model_totals.into_values().collect();
     by_model.sort_by(|a, b| {
@@ -588,84 +818,97 @@ fn build_summary(records: Vec<ParsedRecord>, days: u32, today: NaiveDate) -> Cos
         .map(|d| d.total_cost)
         .unwrap_or(0.0);
 
+    let mut unpriced_models: Vec<String> = by_model
+        .iter()
+        .filter(|m| !m.priced)
+        .map(|m| m.model.clone())
+        .collect();
+    unpriced_models.sort();
+
     CostSummary {
         total_cost,
         today_cost,
         days,
         by_model,
         daily,
+        unpriced_models,
+        converted: None,
+        by_workspace: Vec::new(),
+        by_api_key: Vec::new(),
+    }
+}
+
+fn empty_snapshot(model: &str, priced: bool) -> TokenCostSnapshot {
+    TokenCostSnapshot {
+        model: model.to_string(),
+        input_tokens: 0,
+        output_tokens: 0,
+        cache_read_tokens: 0,
+        cache_creation_tokens: 0,
+        input_cost: 0.0,
+        output_cost: 0.0,
+        cache_read_cost: 0.0,
+        cache_creation_cost: 0.0,
+        total_cost: 0.0,
+        priced,
     }
 }
 
 // ── Main scan entry point ─────────────────────────────────────────────
 
-/// Scan all session files and build a cost summary per provider.
-pub fn scan(days: u32) -> Result<HashMap<Provider, CostSummary>> {
+/// Parse (or load from cache) every discovered session file and return all records.
+fn collect_records() -> Vec<ParsedRecord> {
     let mut cache = CostCache::load();
-
-    let cutoff = chrono::Utc::now().date_naive() - chrono::Duration::days(days as i64);
-    let today = chrono::Utc::now().date_naive();
-
     let mut all_records: Vec<ParsedRecord> = Vec::new();
+    let mut seen_paths: HashSet<String> = HashSet::new();
 
-    // ── Claude / Vertex AI files ──
+    // ── Claude / Vertex AI / Bedrock files ──
     let claude_files = discover_claude_files();
     for file_path in &claude_files {
-        let path_str = file_path.to_string_lossy().to_string();
-        let mtime_ms = file_mtime_ms(file_path);
-        let file_size = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
-
-        if cache.is_unchanged(&path_str, mtime_ms, file_size) {
-            let cached = cache.get_records(&path_str);
-            if !cached.is_empty() {
-                all_records.extend(from_cached(cached));
-                continue;
-            }
-            // Empty records → stale entry, fall through to re-parse
-        }
-
-        let offset = cache.resume_offset(&path_str, mtime_ms);
-
-        match parse_claude_file(file_path, offset) {
-            Ok((records, parsed_bytes)) => {
-                let cached = to_cached(&records);
-                all_records.extend(records);
-                cache.update(&path_str, mtime_ms, file_size, parsed_bytes, cached);
-            }
-            Err(_) => continue,
+        seen_paths.insert(file_path.to_string_lossy().to_string());
+        if let Some(records) = scan_claude_file(&mut cache, file_path) {
+            all_records.extend(records);
         }
     }
 
     // ── Codex files ──
     let codex_files = discover_codex_files();
     for file_path in &codex_files {
-        let path_str = file_path.to_string_lossy().to_string();
-        let mtime_ms = file_mtime_ms(file_path);
-        let file_size = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
-
-        if cache.is_unchanged(&path_str, mtime_ms, file_size) {
-            let cached = cache.get_records(&path_str);
-            if !cached.is_empty() {
-                all_records.extend(from_cached(cached));
-                continue;
-            }
-            // Empty records → stale entry, fall through to re-parse
+        seen_paths.insert(file_path.to_string_lossy().to_string());
+        if let Some(records) = scan_codex_file(&mut cache, file_path) {
+            all_records.extend(records);
         }
+    }
 
-        let offset = cache.resume_offset(&path_str, mtime_ms);
+    cache.retain_paths(&seen_paths);
+    let _ = cache.save();
 
-        match parse_codex_file(file_path, offset) {
-            Ok((records, parsed_bytes)) => {
-                let cached = to_cached(&records);
-                all_records.extend(records);
-                cache.update(&path_str, mtime_ms, file_size, parsed_bytes, cached);
-            }
-            Err(_) => continue,
-        }
-    }
+    all_records
+}
+
+/// Scan all session files and build a cost summary per provider.
+pub fn scan(days: u32) -> Result<HashMap<Provider, CostSummary>> {
+    Ok(summarize_by_provider(collect_records(), days))
+}
+
+/// `scan(days)` and `scan_filtered(filters, since)` from a single pass over
+/// the session files.
+pub fn scan_with_filters(
+    days: u32,
+    filters: &[CostFilter],
+    since: NaiveDate,
+) -> Result<(HashMap<Provider, CostSummary>, Vec<CostSummary>)> {
+    let records = collect_records();
+    let filtered = summarize_filtered(&records, filters, since);
+    Ok((summarize_by_provider(records, days), filtered))
+}
+
+fn summarize_by_provider(records: Vec<ParsedRecord>, days: u32) -> HashMap<Provider, CostSummary> {
+    let cutoff = chrono::Utc::now().date_naive() - chrono::Duration::days(days as i64);
+    let today = chrono::Utc::now().date_naive();
 
     // Filter to date range
-    let all_records: Vec<ParsedRecord> = all_records
+    let all_records: Vec<ParsedRecord> = records
         .into_iter()
         .filter(|r| r.date >= cutoff)
         .collect();
@@ -684,10 +927,124 @@ pub fn scan(days: u32) -> Result<HashMap<Provider, CostSummary>> {
     for (provider, records) in by_provider {
         result.insert(provider, build_summary(records, days, today));
     }
+    result
+}
+
+/// Restricts a scan to records of one provider, model and/or project.
+/// `model` and `project` may be globs; `project` matches the session's
+/// working directory either in full or by its last path component.
+#[derive(Debug, Clone, Default)]
+pub struct CostFilter {
+    pub provider: Option<Provider>,
+    pub model: Option<String>,
+    pub project: Option<String>,
+}
+
+impl CostFilter {
+    fn matches(&self, record: &ParsedRecord) -> bool {
+        if self.provider.is_some_and(|p| p != record.provider) {
+            return false;
+        }
+        if let Some(model) = &self.model {
+            if !glob_matches(model, &record.model) {
+                return false;
+            }
+        }
+        if let Some(project) = &self.project {
+            let Some(cwd) = &record.project else {
+                return false;
+            };
+            let name = std::path::Path::new(cwd)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !glob_matches(project, cwd) && !glob_matches(project, &name) {
+                return false;
+            }
+        }
+        true
+    }
+}
 
-    let _ = cache.save();
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    if !pattern.contains(['*', '?']) {
+        return pattern == value;
+    }
+    regex::Regex::new(&pricing::glob_to_regex(pattern))
+        .map(|re| re.is_match(value))
+        .unwrap_or(false)
+}
+
+/// Build one cost summary per filter from records dated `since` or later.
+pub fn scan_filtered(filters: &[CostFilter], since: NaiveDate) -> Result<Vec<CostSummary>> {
+    Ok(summarize_filtered(&collect_records(), filters, since))
+}
+
+fn summarize_filtered(
+    records: &[ParsedRecord],
+    filters: &[CostFilter],
+    since: NaiveDate,
+) -> Vec<CostSummary> {
+    let today = chrono::Local::now().date_naive();
+    let days = (today - since).num_days().max(0) as u32 + 1;
+    filters
+        .iter()
+        .map(|f| {
+            let matching = records
+                .iter()
+                .filter(|r| r.date >= since && f.matches(r))
+                .cloned()
+                .collect();
+            build_summary(matching, days, today)
+        })
+        .collect()
+}
+
+/// Aggregate token volumes of models without a pricing entry, sorted by
+/// total tokens (largest first).
+fn summarize_unpriced(records: Vec<ParsedRecord>) -> Vec<UnpricedModel> {
+    let mut by_model: HashMap<(Provider, String), UnpricedModel> = HashMap::new();
+    for r in records {
+        if pricing::lookup(&r.model).is_some() {
+            continue;
+        }
+        let entry = by_model
+            .entry((r.provider, r.model.clone()))
+            .or_insert_with(|| UnpricedModel {
+                provider: r.provider,
+                model: r.model.clone(),
+                input_tokens: 0,
+                output_tokens: 0,
+                cache_read_tokens: 0,
+                cache_creation_tokens: 0,
+            });
+        entry.input_tokens += r.input_tokens;
+        entry.output_tokens += r.output_tokens;
+        entry.cache_read_tokens += r.cache_read_tokens;
+        entry.cache_creation_tokens += r.cache_creation_tokens;
+    }
+
+    let mut models: Vec<UnpricedModel> = by_model.into_values().collect();
+    models.sort_by_key(|m| {
+        std::cmp::Reverse(
+            m.input_tokens + m.output_tokens + m.cache_read_tokens + m.cache_creation_tokens,
+        )
+    });
+    models
+}
 
-    Ok(result)
+/// List every model seen in the session logs that has no pricing entry.
+/// `days` limits the scan window; None scans all logs.
+pub fn scan_unpriced(days: Option<u32>) -> Result<Vec<UnpricedModel>> {
+    let records = collect_records();
+    let records = match days {
+        Some(days) => {
+            let cutoff = chrono::Utc::now().date_naive() - chrono::Duration::days(days as i64);
+            records.into_iter().filter(|r| r.date >= cutoff).collect()
+        }
+        None => records,
+    };
+    Ok(summarize_unpriced(records))
 }
 
 #[cfg(test)]
@@ -753,7 +1110,7 @@ mod tests {
         writeln!(f, r#"{{"type":"assistant","message":{{"model":"claude-sonnet-4-5","usage":{{"input_tokens":2000,"output_tokens":400}},"id":"msg_2"}},"requestId":"req_2","timestamp":"2025-02-24T11:00:00Z"}}"#).unwrap();
         drop(f);
 
-        let (records, _) = parse_claude_file(&file_path, 0).unwrap();
+        let (records, _) = parse_claude_file(&file_path, 0, Vec::new()).unwrap();
         assert_eq!(records.len(), 2);
         assert_eq!(records[0].provider, Provider::Claude);
         assert_eq!(records[0].input_tokens, 1000);
@@ -781,45 +1138,9 @@ mod tests {
 
         let _ = std::fs::File::create(project.join("memory.md"));
 
-        // Manually replicate discovery logic for test isolation
         let projects_dir = root.join("projects");
         let mut files: Vec<PathBuf> = Vec::new();
-        if let Ok(projects) = std::fs::read_dir(&projects_dir) {
-            for project_entry in projects.flatten() {
-                let project_path = project_entry.path();
-                if !project_path.is_dir() {
-                    continue;
-                }
-                if let Ok(entries) = std::fs::read_dir(&project_path) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if path.is_file()
-                            && path.extension().and_then(|e| e.to_str()) == Some("jsonl")
-                        {
-                            files.push(path);
-                        }
-                    }
-                }
-                if let Ok(subdirs) = std::fs::read_dir(&project_path) {
-                    for subdir in subdirs.flatten() {
-                        let sa_dir = subdir.path().join("subagents");
-                        if !sa_dir.is_dir() {
-                            continue;
-                        }
-                        if let Ok(sa_entries) = std::fs::read_dir(&sa_dir) {
-                            for sa_entry in sa_entries.flatten() {
-                                let path = sa_entry.path();
-                                if path.is_file()
-                                    && path.extension().and_then(|e| e.to_str()) == Some("jsonl")
-                                {
-                                    files.push(path);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        collect_claude_projects(&projects_dir, &mut files);
 
         assert_eq!(files.len(), 2);
         let names: Vec<String> = files
@@ -845,13 +1166,342 @@ mod tests {
         writeln!(f, r#"{{"type":"assistant","message":{{"model":"claude-sonnet-4-5","usage":{{"input_tokens":100,"output_tokens":200}},"id":"msg_1"}},"requestId":"req_1","timestamp":"2025-02-24T10:00:00Z"}}"#).unwrap();
         drop(f);
 
-        let (records, _) = parse_claude_file(&file_path, 0).unwrap();
+        let (records, _) = parse_claude_file(&file_path, 0, Vec::new()).unwrap();
         assert_eq!(records.len(), 1);
         assert_eq!(records[0].output_tokens, 200);
 
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    // ── Incremental parsing tests ─────────────────────────────────────
+
+    fn claude_line(msg_id: &str, input: u64, output: u64) -> String {
+        format!(
+            r#"{{"type":"assistant","message":{{"model":"claude-sonnet-4-5","usage":{{"input_tokens":{},"output_tokens":{}}},"id":"{}"}},"requestId":"req_{}","timestamp":"2025-02-24T10:00:00Z"}}"#,
+            input, output, msg_id, msg_id
+        )
+    }
+
+    fn append_lines(path: &PathBuf, lines: &[String]) {
+        use std::io::Write;
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        for line in lines {
+            writeln!(f, "{}", line).unwrap();
+        }
+    }
+
+    #[test]
+    fn incremental_append_resumes_without_double_counting() {
+        let dir = std::env::temp_dir().join("ait_test_incremental_append");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("session.jsonl");
+        let mut cache = CostCache::default();
+
+        append_lines(&file_path, &[claude_line("msg_1", 100, 10)]);
+        let records = scan_claude_file(&mut cache, &file_path).unwrap();
+        assert_eq!(records.len(), 1);
+        let first_offset = cache.files.values().next().unwrap().parsed_bytes;
+
+        // A later streaming chunk of msg_1 plus a new message are appended.
+        append_lines(
+            &file_path,
+            &[claude_line("msg_1", 100, 200), claude_line("msg_2", 50, 5)],
+        );
+        let path_str = file_path.to_string_lossy().to_string();
+        let size = std::fs::metadata(&file_path).unwrap().len();
+        assert_eq!(
+            cache.resume_offset(&path_str, size, |len| head_hash(&file_path, len)),
+            first_offset
+        );
+
+        let records = scan_claude_file(&mut cache, &file_path).unwrap();
+        assert_eq!(records.len(), 2);
+        let msg_1 = records
+            .iter()
+            .find(|r| r.dedup_key.as_deref() == Some("msg_1:req_msg_1"))
+            .unwrap();
+        assert_eq!(msg_1.output_tokens, 200);
+        let total_input: u64 = records.iter().map(|r| r.input_tokens).sum();
+        assert_eq!(total_input, 150);
+
+        // Dedup state survives a cache round-trip to disk.
+        let json = serde_json::to_string(&cache).unwrap();
+        let mut cache: CostCache = serde_json::from_str(&json).unwrap();
+        append_lines(&file_path, &[claude_line("msg_2", 50, 80)]);
+        let records = scan_claude_file(&mut cache, &file_path).unwrap();
+        assert_eq!(records.len(), 2);
+        let output: u64 = records.iter().map(|r| r.output_tokens).sum();
+        assert_eq!(output, 280);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn incremental_truncation_reparses_from_start() {
+        let dir = std::env::temp_dir().join("ait_test_incremental_truncate");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("session.jsonl");
+        let mut cache = CostCache::default();
+
+        append_lines(
+            &file_path,
+            &[claude_line("msg_1", 100, 10), claude_line("msg_2", 100, 10)],
+        );
+        assert_eq!(scan_claude_file(&mut cache, &file_path).unwrap().len(), 2);
+
+        std::fs::remove_file(&file_path).unwrap();
+        append_lines(&file_path, &[claude_line("msg_3", 7, 1)]);
+        let records = scan_claude_file(&mut cache, &file_path).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].input_tokens, 7);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn incremental_rotation_reparses_from_start() {
+        let dir = std::env::temp_dir().join("ait_test_incremental_rotate");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("session.jsonl");
+        let mut cache = CostCache::default();
+
+        append_lines(&file_path, &[claude_line("msg_a", 100, 10)]);
+        assert_eq!(scan_claude_file(&mut cache, &file_path).unwrap().len(), 1);
+
+        // Replaced by a different, larger file at the same path.
+        std::fs::rename(&file_path, dir.join("session.jsonl.1")).unwrap();
+        append_lines(
+            &file_path,
+            &[claude_line("msg_b", 200, 20), claude_line("msg_c", 300, 30)],
+        );
+        let records = scan_claude_file(&mut cache, &file_path).unwrap();
+        assert_eq!(records.len(), 2);
+        let total_input: u64 = records.iter().map(|r| r.input_tokens).sum();
+        assert_eq!(total_input, 500);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn incremental_partial_line_is_not_consumed() {
+        use std::io::Write;
+        let dir = std::env::temp_dir().join("ait_test_incremental_partial");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("session.jsonl");
+        let mut cache = CostCache::default();
+
+        let complete = claude_line("msg_1", 100, 10);
+        let pending = claude_line("msg_2", 40, 4);
+        let (head, tail) = pending.split_at(pending.len() / 2);
+        append_lines(&file_path, std::slice::from_ref(&complete));
+        let mut f = std::fs::OpenOptions::new().append(true).open(&file_path).unwrap();
+        write!(f, "{}", head).unwrap();
+        drop(f);
+
+        let records = scan_claude_file(&mut cache, &file_path).unwrap();
+        assert_eq!(records.len(), 1);
+        let entry = cache.files.values().next().unwrap();
+        assert_eq!(entry.parsed_bytes, complete.len() as u64 + 1);
+
+        append_lines(&file_path, &[tail.to_string()]);
+        let records = scan_claude_file(&mut cache, &file_path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].input_tokens, 40);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    fn record(provider: Provider, model: &str, input: u64, output: u64) -> ParsedRecord {
+        ParsedRecord {
+            provider,
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: output,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            cache_creation_1h_tokens: 0,
+            date: NaiveDate::from_ymd_opt(2026, 2, 24).unwrap(),
+            project: None,
+            dedup_key: None,
+        }
+    }
+
+    #[test]
+    fn build_summary_flags_unpriced_models() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 24).unwrap();
+        let summary = build_summary(
+            vec![
+                record(Provider::Claude, "claude-sonnet-4-5", 1_000_000, 0),
+                record(Provider::Claude, "mystery-model-x", 5_000, 500),
+            ],
+            30,
+            today,
+        );
+        assert_eq!(summary.unpriced_models, vec!["mystery-model-x".to_string()]);
+        let mystery = summary.by_model.iter().find(|m| m.model == "mystery-model-x").unwrap();
+        assert!(!mystery.priced);
+        assert_eq!(mystery.total_cost, 0.0);
+        let sonnet = summary.by_model.iter().find(|m| m.model == "claude-sonnet-4-5").unwrap();
+        assert!(sonnet.priced);
+        assert!(summary.daily[0].costs.iter().any(|c| !c.priced));
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["unpriced_models"][0], "mystery-model-x");
+    }
+
+    #[test]
+    fn build_summary_omits_unpriced_list_when_all_priced() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 24).unwrap();
+        let summary = build_summary(
+            vec![record(Provider::Codex, "gpt-5", 100, 10)],
+            30,
+            today,
+        );
+        assert!(summary.unpriced_models.is_empty());
+        let json = serde_json::to_value(&summary).unwrap();
+        assert!(json.get("unpriced_models").is_none());
+    }
+
+    #[test]
+    fn build_summary_prices_each_request_separately() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 24).unwrap();
+        // Two 150K-input requests: each stays under the 200K long-context
+        // threshold even though their sum does not.
+        let a = record(Provider::Claude, "claude-sonnet-4-5", 150_000, 0);
+        let b = record(Provider::Claude, "claude-sonnet-4-5", 150_000, 0);
+        let summary = build_summary(vec![a, b], 30, today);
+        assert!((summary.total_cost - 0.9).abs() < 1e-9); // 300K * $3/MTok
+
+        let long = record(Provider::Claude, "claude-sonnet-4-5", 300_000, 0);
+        let summary = build_summary(vec![long], 30, today);
+        assert!((summary.total_cost - 1.8).abs() < 1e-9); // 300K * $6/MTok
+    }
+
+    #[test]
+    fn build_summary_applies_bedrock_regional_pricing() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 24).unwrap();
+        let regional = record(
+            Provider::Bedrock,
+            "us.anthropic.claude-sonnet-4-5-20250929-v1:0",
+            100_000,
+            0,
+        );
+        let summary = build_summary(vec![regional], 30, today);
+        assert!((summary.total_cost - 0.33).abs() < 1e-9); // $3/MTok + 10%
+
+        let global = record(
+            Provider::Bedrock,
+            "global.anthropic.claude-sonnet-4-5-20250929-v1:0",
+            100_000,
+            0,
+        );
+        let summary = build_summary(vec![global], 30, today);
+        assert!((summary.total_cost - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_claude_file_reads_cache_ttl_breakdown() {
+        let dir = std::env::temp_dir().join("ait_test_claude_cache_ttl");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("session.jsonl");
+        append_lines(
+            &file_path,
+            &[r#"{"type":"assistant","message":{"model":"claude-sonnet-4-5","usage":{"input_tokens":10,"output_tokens":5,"cache_creation_input_tokens":3000,"cache_creation":{"ephemeral_5m_input_tokens":1000,"ephemeral_1h_input_tokens":2000}},"id":"msg_1"},"requestId":"req_1","timestamp":"2026-02-24T10:00:00Z"}"#.to_string()],
+        );
+
+        let (records, _) = parse_claude_file(&file_path, 0, Vec::new()).unwrap();
+        assert_eq!(records[0].cache_creation_tokens, 3000);
+        assert_eq!(records[0].cache_creation_1h_tokens, 2000);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cost_filter_matches_scope() {
+        let mut r = record(Provider::Claude, "claude-sonnet-4-5", 10, 1);
+        r.project = Some("/home/dev/work/aitracker".to_string());
+
+        assert!(CostFilter::default().matches(&r));
+        let by_provider = CostFilter {
+            provider: Some(Provider::Codex),
+            ..Default::default()
+        };
+        assert!(!by_provider.matches(&r));
+        let by_model = CostFilter {
+            model: Some("claude-sonnet-*".to_string()),
+            ..Default::default()
+        };
+        assert!(by_model.matches(&r));
+        let by_name = CostFilter {
+            project: Some("aitracker".to_string()),
+            ..Default::default()
+        };
+        assert!(by_name.matches(&r));
+        let by_path = CostFilter {
+            project: Some("/home/dev/work/*".to_string()),
+            ..Default::default()
+        };
+        assert!(by_path.matches(&r));
+
+        r.project = None;
+        assert!(!by_name.matches(&r));
+    }
+
+    #[test]
+    fn parse_records_carry_project() {
+        let dir = std::env::temp_dir().join("ait_test_project_cwd");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let claude_path = dir.join("claude.jsonl");
+        append_lines(
+            &claude_path,
+            &[r#"{"type":"assistant","cwd":"/work/alpha","message":{"model":"claude-sonnet-4-5","usage":{"input_tokens":10,"output_tokens":5},"id":"msg_1"},"requestId":"req_1","timestamp":"2026-02-24T10:00:00Z"}"#.to_string()],
+        );
+        let (records, _) = parse_claude_file(&claude_path, 0, Vec::new()).unwrap();
+        assert_eq!(records[0].project.as_deref(), Some("/work/alpha"));
+
+        let codex_path = dir.join("codex.jsonl");
+        append_lines(
+            &codex_path,
+            &[
+                r#"{"type":"session_meta","timestamp":"2026-02-24T09:59:00Z","payload":{"cwd":"/work/beta"}}"#.to_string(),
+                codex_turn_line("2026-02-24T10:00:00Z", "gpt-5"),
+                codex_total_line("2026-02-24T10:01:00Z", 100, 10),
+            ],
+        );
+        let (records, _, state) =
+            parse_codex_file(&codex_path, 0, Vec::new(), CodexSessionState::default()).unwrap();
+        assert_eq!(records[0].project.as_deref(), Some("/work/beta"));
+        assert_eq!(state.cwd.as_deref(), Some("/work/beta"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn summarize_unpriced_aggregates_volumes() {
+        let models = summarize_unpriced(vec![
+            record(Provider::Claude, "mystery-a", 10, 1),
+            record(Provider::Claude, "mystery-a", 20, 2),
+            record(Provider::Codex, "mystery-b", 1_000, 100),
+            record(Provider::Codex, "gpt-5", 1_000_000, 0),
+        ]);
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].model, "mystery-b");
+        assert_eq!(models[1].model, "mystery-a");
+        assert_eq!(models[1].input_tokens, 30);
+        assert_eq!(models[1].output_tokens, 3);
+    }
+
     // ── Codex tests ───────────────────────────────────────────────────
 
     #[test]
@@ -898,22 +1548,28 @@ mod tests {
         let mut f = std::fs::File::create(&file_path).unwrap();
         // turn_context sets model
         writeln!(f, r#"{{"type":"turn_context","timestamp":"2026-02-24T10:00:00Z","payload":{{"model":"gpt-5.3-codex"}}}}"#).unwrap();
-        // First cumulative token_count (intermediate — should be replaced)
+        // First cumulative token_count
         writeln!(f, r#"{{"type":"event_msg","timestamp":"2026-02-24T10:01:00Z","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":500,"output_tokens":100,"cached_input_tokens":200}},"model_name":"gpt-5.3-codex"}}}}}}"#).unwrap();
-        // Second cumulative token_count for same model (final — should be kept)
-        writeln!(f, r#"{{"type":"event_msg","timestamp":"2026-02-24T10:02:00Z","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":300,"output_tokens":50}}}}}}}}"#).unwrap();
+        // Second cumulative token_count for same model — only the delta is added
+        writeln!(f, r#"{{"type":"event_msg","timestamp":"2026-02-24T10:02:00Z","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":800,"output_tokens":150,"cached_input_tokens":200}}}}}}}}"#).unwrap();
         drop(f);
 
-        let (records, _) = parse_codex_file(&file_path, 0).unwrap();
-        // Both events are for "gpt-5.3-codex" — only the last one should survive
-        assert_eq!(records.len(), 1);
+        let (records, _, state) =
+            parse_codex_file(&file_path, 0, Vec::new(), CodexSessionState::default()).unwrap();
+        // One record per request, each holding only that request's delta
+        assert_eq!(records.len(), 2);
 
         assert_eq!(records[0].provider, Provider::Codex);
         assert_eq!(records[0].model, "gpt-5.3-codex");
-        assert_eq!(records[0].input_tokens, 300);
-        assert_eq!(records[0].output_tokens, 50);
-        assert_eq!(records[0].cache_read_tokens, 0);
+        assert_eq!(records[0].input_tokens, 500);
+        assert_eq!(records[0].output_tokens, 100);
+        assert_eq!(records[0].cache_read_tokens, 200);
         assert_eq!(records[0].cache_creation_tokens, 0);
+        assert_eq!(records[1].input_tokens, 300);
+        assert_eq!(records[1].output_tokens, 50);
+        assert_eq!(records[1].cache_read_tokens, 0);
+        assert_eq!(state.total_input_tokens, 800);
+        assert_eq!(state.model.as_deref(), Some("gpt-5.3-codex"));
 
         let _ = std::fs::remove_dir_all(&dir);
     }
@@ -932,13 +1588,139 @@ mod tests {
         writeln!(f, r#"{{"type":"event_msg","timestamp":"2026-02-24T10:01:00Z","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":100,"output_tokens":50}},"model_name":"gpt-5"}}}}}}"#).unwrap();
         drop(f);
 
-        let (records, _) = parse_codex_file(&file_path, 0).unwrap();
+        let (records, _, _) =
+            parse_codex_file(&file_path, 0, Vec::new(), CodexSessionState::default()).unwrap();
         assert_eq!(records.len(), 1);
         assert_eq!(records[0].model, "gpt-5");
 
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    fn codex_total_line(ts: &str, input: u64, output: u64) -> String {
+        format!(
+            r#"{{"type":"event_msg","timestamp":"{}","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":{},"output_tokens":{}}}}}}}}}"#,
+            ts, input, output
+        )
+    }
+
+    fn codex_turn_line(ts: &str, model: &str) -> String {
+        format!(
+            r#"{{"type":"turn_context","timestamp":"{}","payload":{{"model":"{}"}}}}"#,
+            ts, model
+        )
+    }
+
+    #[test]
+    fn parse_codex_session_spanning_midnight() {
+        let dir = std::env::temp_dir().join("ait_test_codex_midnight");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("session.jsonl");
+        append_lines(
+            &file_path,
+            &[
+                codex_turn_line("2026-02-24T23:50:00Z", "gpt-5"),
+                codex_total_line("2026-02-24T23:55:00Z", 1000, 100),
+                codex_total_line("2026-02-25T00:05:00Z", 1600, 180),
+            ],
+        );
+
+        let (records, _, _) =
+            parse_codex_file(&file_path, 0, Vec::new(), CodexSessionState::default()).unwrap();
+        assert_eq!(records.len(), 2);
+        let day1 = NaiveDate::from_ymd_opt(2026, 2, 24).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2026, 2, 25).unwrap();
+        let r1 = records.iter().find(|r| r.date == day1).unwrap();
+        let r2 = records.iter().find(|r| r.date == day2).unwrap();
+        assert_eq!((r1.input_tokens, r1.output_tokens), (1000, 100));
+        assert_eq!((r2.input_tokens, r2.output_tokens), (600, 80));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parse_codex_model_switch_attributes_deltas() {
+        let dir = std::env::temp_dir().join("ait_test_codex_switch");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("session.jsonl");
+        append_lines(
+            &file_path,
+            &[
+                codex_turn_line("2026-02-24T10:00:00Z", "gpt-5"),
+                codex_total_line("2026-02-24T10:01:00Z", 400, 40),
+                codex_turn_line("2026-02-24T10:02:00Z", "gpt-5.3-codex"),
+                codex_total_line("2026-02-24T10:03:00Z", 1000, 90),
+            ],
+        );
+
+        let (records, _, _) =
+            parse_codex_file(&file_path, 0, Vec::new(), CodexSessionState::default()).unwrap();
+        assert_eq!(records.len(), 2);
+        let gpt5 = records.iter().find(|r| r.model == "gpt-5").unwrap();
+        let codex = records.iter().find(|r| r.model == "gpt-5.3-codex").unwrap();
+        assert_eq!((gpt5.input_tokens, gpt5.output_tokens), (400, 40));
+        assert_eq!((codex.input_tokens, codex.output_tokens), (600, 50));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parse_codex_counter_reset_counts_new_total() {
+        let mut state = CodexSessionState {
+            model: None,
+            cwd: None,
+            total_input_tokens: 1000,
+            total_output_tokens: 100,
+            total_cached_tokens: 0,
+        };
+        let total = CodexTokenUsage {
+            input_tokens: Some(300),
+            output_tokens: Some(30),
+            cached_input_tokens: None,
+        };
+        assert_eq!(codex_delta(&mut state, &total), (300, 30, 0));
+        assert_eq!(state.total_input_tokens, 300);
+    }
+
+    #[test]
+    fn incremental_codex_resume_does_not_double_count() {
+        let dir = std::env::temp_dir().join("ait_test_codex_incremental");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("session.jsonl");
+        let mut cache = CostCache::default();
+
+        append_lines(
+            &file_path,
+            &[
+                codex_turn_line("2026-02-24T10:00:00Z", "gpt-5"),
+                codex_total_line("2026-02-24T10:01:00Z", 1000, 100),
+            ],
+        );
+        let records = scan_codex_file(&mut cache, &file_path).unwrap();
+        assert_eq!(records[0].input_tokens, 1000);
+
+        // Round-trip through JSON as the on-disk cache would.
+        let json = serde_json::to_string(&cache).unwrap();
+        let mut cache: CostCache = serde_json::from_str(&json).unwrap();
+
+        append_lines(&file_path, &[codex_total_line("2026-02-24T10:05:00Z", 1500, 160)]);
+        let path_str = file_path.to_string_lossy().to_string();
+        let size = std::fs::metadata(&file_path).unwrap().len();
+        assert!(cache.resume_offset(&path_str, size, |len| head_hash(&file_path, len)) > 0);
+
+        let records = scan_codex_file(&mut cache, &file_path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].model, "gpt-5");
+        assert_eq!(records[1].input_tokens, 500);
+        assert_eq!(records[1].output_tokens, 60);
+        let total: u64 = records.iter().map(|r| r.input_tokens).sum();
+        assert_eq!(total, 1500);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     // ── Vertex AI detection tests ─────────────────────────────────────
 
     #[test]
@@ -962,6 +1744,36 @@ mod tests {
         assert!(!detect_vertex_ai("msg_123", "req_456", "claude-opus-4-5"));
     }
 
+    #[test]
+    fn bedrock_detection() {
+        assert!(detect_bedrock("msg_bdrk_01abc", "", "claude-sonnet-4-5"));
+        assert!(detect_bedrock("", "req_bdrk_01", "claude-sonnet-4-5"));
+        assert!(detect_bedrock("msg_1", "", "us.anthropic.claude-sonnet-4-5-20250929-v1:0"));
+        assert!(detect_bedrock("msg_1", "", "anthropic.claude-3-5-sonnet-20241022-v2:0"));
+        assert!(!detect_bedrock("msg_123", "req_456", "claude-opus-4-5"));
+        assert!(!detect_bedrock("msg_vrtx_1", "", "claude-opus-4-5@20251101"));
+    }
+
+    #[test]
+    fn bedrock_in_claude_file() {
+        use std::io::Write;
+        let dir = std::env::temp_dir().join("ait_test_bedrock");
+        let _ = std::fs::create_dir_all(&dir);
+        let file_path = dir.join("bedrock_test.jsonl");
+
+        let mut f = std::fs::File::create(&file_path).unwrap();
+        writeln!(f, r#"{{"type":"assistant","message":{{"model":"claude-sonnet-4-5","usage":{{"input_tokens":1000,"output_tokens":200}},"id":"msg_1"}},"requestId":"req_1","timestamp":"2025-02-24T10:00:00Z"}}"#).unwrap();
+        writeln!(f, r#"{{"type":"assistant","message":{{"model":"us.anthropic.claude-sonnet-4-5-20250929-v1:0","usage":{{"input_tokens":500,"output_tokens":100}},"id":"msg_bdrk_2"}},"timestamp":"2025-02-24T11:00:00Z"}}"#).unwrap();
+        writeln!(f, r#"{{"type":"assistant","message":{{"model":"claude-opus-4-5@20251101","usage":{{"input_tokens":300,"output_tokens":60}},"id":"msg_vrtx_3"}},"timestamp":"2025-02-24T12:00:00Z"}}"#).unwrap();
+        drop(f);
+
+        let (records, _) = parse_claude_file(&file_path, 0, Vec::new()).unwrap();
+        let providers: Vec<Provider> = records.iter().map(|r| r.provider).collect();
+        assert_eq!(providers, [Provider::Claude, Provider::Bedrock, Provider::VertexAi]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn vertex_ai_in_claude_file() {
         use std::io::Write;
@@ -978,7 +1790,7 @@ mod tests {
         writeln!(f, r#"{{"type":"assistant","message":{{"model":"claude-sonnet-4-5","usage":{{"input_tokens":300,"output_tokens":60}},"id":"msg_vrtx_3"}},"requestId":"req_3","timestamp":"2025-02-24T12:00:00Z"}}"#).unwrap();
         drop(f);
 
-        let (records, _) = parse_claude_file(&file_path, 0).unwrap();
+        let (records, _) = parse_claude_file(&file_path, 0, Vec::new()).unwrap();
         assert_eq!(records.len(), 3);
 
         assert_eq!(records[0].provider, Provider::Claude);
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+
+use crate::core::config::AppConfig;
+use crate::core::models::cost::{ConvertedCost, CostSummary};
+use crate::core::models::credits::{ConvertedCredits, CreditsSnapshot};
+use crate::core::models::usage::UsageSnapshot;
+
+/// Currency assumed when a provider does not report one. Token costs are
+/// always computed in this currency.
+pub const BASE_CURRENCY: &str = "USD";
+
+/// Normalize a currency code ("usd", " Usd ") to its ISO form ("USD").
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
+
+/// Display symbol for common currencies; others are shown by code.
+pub fn symbol(code: &str) -> Option<&'static str> {
+    match normalize_code(code).as_str() {
+        "USD" => Some("$"),
+        "EUR" => Some("€"),
+        "GBP" => Some("£"),
+        "CNY" | "JPY" => Some("¥"),
+        "INR" => Some("₹"),
+        "KRW" => Some("₩"),
+        _ => None,
+    }
+}
+
+/// Converts amounts into the configured `settings.display_currency` using the
+/// static `[exchange_rates]` table (units of each currency per 1 USD).
+#[derive(Debug, Clone)]
+pub struct CurrencyConverter {
+    target: String,
+    rates: BTreeMap<String, f64>,
+}
+
+impl CurrencyConverter {
+    /// Build a converter from config, or `None` when no display currency is set.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let target = config.settings.display_currency.as_deref()?;
+        Some(Self::new(target, &config.exchange_rates))
+    }
+
+    pub fn new(target: &str, rates: &BTreeMap<String, f64>) -> Self {
+        let rates = rates
+            .iter()
+            .map(|(code, rate)| (normalize_code(code), *rate))
+            .filter(|(_, rate)| rate.is_finite() && *rate > 0.0)
+            .collect();
+        Self {
+            target: normalize_code(target),
+            rates,
+        }
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Units of one USD in `code`; USD itself is always 1.
+    fn per_usd(&self, code: &str) -> Option<f64> {
+        let code = normalize_code(code);
+        if code == BASE_CURRENCY {
+            return Some(1.0);
+        }
+        self.rates.get(&code).copied()
+    }
+
+    /// Factor to multiply an amount in `from` by to get the display currency,
+    /// or `None` when either side has no rate.
+    pub fn rate(&self, from: &str) -> Option<f64> {
+        Some(self.per_usd(&self.target)? / self.per_usd(from)?)
+    }
+
+    /// Attach display-currency amounts to a credits snapshot. Snapshots already
+    /// in the display currency, in a currency without a rate, or not in money
+    /// at all, are left as is.
+    pub fn convert_credits(&self, credits: &mut CreditsSnapshot) {
+        if !credits.is_monetary() {
+            return;
+        }
+        let from = credits
+            .currency
+            .as_deref()
+            .map(normalize_code)
+            .unwrap_or_else(|| BASE_CURRENCY.to_string());
+        if from == self.target {
+            return;
+        }
+        let Some(rate) = self.rate(&from) else {
+            return;
+        };
+        credits.converted = Some(ConvertedCredits {
+            currency: self.target.clone(),
+            rate,
+            remaining: credits.remaining * rate,
+            used: credits.used.map(|v| v * rate),
+            limit: credits.limit.map(|v| v * rate),
+        });
+    }
+
+    /// Convert the credits of each `[[generic_http]]` endpoint in `usage`.
+    pub fn convert_endpoints(&self, usage: &mut UsageSnapshot) {
+        for endpoint in &mut usage.endpoints {
+            if let Some(credits) = &mut endpoint.credits {
+                self.convert_credits(credits);
+            }
+        }
+    }
+
+    /// Attach display-currency totals to a cost summary (costs are in USD).
+    pub fn convert_cost(&self, cost: &mut CostSummary) {
+        if self.target == BASE_CURRENCY {
+            return;
+        }
+        let Some(rate) = self.rate(BASE_CURRENCY) else {
+            return;
+        };
+        cost.converted = Some(ConvertedCost {
+            currency: self.target.clone(),
+            rate,
+            total_cost: cost.total_cost * rate,
+            today_cost: cost.today_cost * rate,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rates() -> BTreeMap<String, f64> {
+        let mut rates = BTreeMap::new();
+        rates.insert("eur".to_string(), 0.9);
+        rates.insert("CNY".to_string(), 7.2);
+        rates
+    }
+
+    fn credits(currency: Option<&str>, remaining: f64) -> CreditsSnapshot {
+        CreditsSnapshot {
+            remaining,
+            has_credits: true,
+            unlimited: false,
+            used: Some(10.0),
+            limit: None,
+            currency: currency.map(String::from),
+            unit: None,
+            period: None,
+            converted: None,
+        }
+    }
+
+    #[test]
+    fn symbol_known_and_unknown() {
+        assert_eq!(symbol("usd"), Some("$"));
+        assert_eq!(symbol("CNY"), Some("¥"));
+        assert_eq!(symbol("CHF"), None);
+    }
+
+    #[test]
+    fn rate_goes_through_usd() {
+        let fx = CurrencyConverter::new("EUR", &rates());
+        assert!((fx.rate("USD").unwrap() - 0.9).abs() < 1e-12);
+        assert!((fx.rate("cny").unwrap() - 0.125).abs() < 1e-12);
+        assert!(fx.rate("CHF").is_none());
+    }
+
+    #[test]
+    fn convert_credits_keeps_original() {
+        let fx = CurrencyConverter::new("USD", &rates());
+        let mut c = credits(Some("cny"), 72.0);
+        fx.convert_credits(&mut c);
+        assert!((c.remaining - 72.0).abs() < 1e-12);
+        let converted = c.converted.unwrap();
+        assert_eq!(converted.currency, "USD");
+        assert!((converted.remaining - 10.0).abs() < 1e-9);
+        assert!((converted.used.unwrap() - 10.0 / 7.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn convert_credits_skips_same_currency_and_missing_rate() {
+        let fx = CurrencyConverter::new("USD", &rates());
+        let mut c = credits(None, 5.0);
+        fx.convert_credits(&mut c);
+        assert!(c.converted.is_none());
+
+        let mut c = credits(Some("chf"), 5.0);
+        fx.convert_credits(&mut c);
+        assert!(c.converted.is_none());
+    }
+
+    #[test]
+    fn convert_credits_skips_non_monetary_units() {
+        let fx = CurrencyConverter::new("EUR", &rates());
+        let mut c = credits(None, 3420.0);
+        c.unit = Some("credits".to_string());
+        fx.convert_credits(&mut c);
+        assert!(c.converted.is_none());
+    }
+
+    #[test]
+    fn convert_cost_from_usd() {
+        let fx = CurrencyConverter::new("cny", &rates());
+        let mut cost = CostSummary {
+            total_cost: 10.0,
+            today_cost: 1.0,
+            days: 30,
+            by_model: vec![],
+            daily: vec![],
+            unpriced_models: vec![],
+            converted: None,
+            by_workspace: Vec::new(),
+            by_api_key: Vec::new(),
+        };
+        fx.convert_cost(&mut cost);
+        let converted = cost.converted.unwrap();
+        assert_eq!(converted.currency, "CNY");
+        assert!((converted.total_cost - 72.0).abs() < 1e-9);
+        assert!((converted.today_cost - 7.2).abs() < 1e-9);
+    }
+}
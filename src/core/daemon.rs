@@ -0,0 +1,214 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+
+use crate::core::config::AppConfig;
+use crate::core::models::report::UsageReport;
+use crate::core::providers::Provider;
+
+/// How long a client waits for the daemon before fetching directly.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Largest message accepted in either direction.
+pub const MAX_MESSAGE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// A request sent to the daemon: one JSON object per connection, terminated
+/// by a newline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Request {
+    Ping,
+    /// Latest usage for the given providers (all tracked ones when empty).
+    Usage {
+        #[serde(default)]
+        providers: Vec<Provider>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Pong {
+        version: String,
+        pid: u32,
+        providers: Vec<Provider>,
+        last_refresh: Option<DateTime<Utc>>,
+    },
+    Usage {
+        report: UsageReport,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// `$XDG_RUNTIME_DIR/ait/daemon.sock`, or a private `run` directory in the
+/// cache directory when there is no runtime directory.
+pub fn socket_path() -> PathBuf {
+    std::env::var("XDG_RUNTIME_DIR")
+        .map(|dir| PathBuf::from(dir).join("ait"))
+        .unwrap_or_else(|_| AppConfig::cache_dir().join("run"))
+        .join("daemon.sock")
+}
+
+/// Send one request and wait for the response.
+pub async fn request(path: &Path, request: &Request) -> Result<Response> {
+    let exchange = async {
+        let mut stream = UnixStream::connect(path)
+            .await
+            .with_context(|| format!("Failed to connect to {}", path.display()))?;
+        let mut line = serde_json::to_vec(request)?;
+        line.push(b'\n');
+        stream.write_all(&line).await?;
+        stream.shutdown().await?;
+
+        let mut buf = Vec::new();
+        (&mut stream)
+            .take(MAX_MESSAGE_BYTES)
+            .read_to_end(&mut buf)
+            .await
+            .context("Failed to read daemon response")?;
+        serde_json::from_slice(&buf).context("Failed to parse daemon response")
+    };
+    tokio::time::timeout(CLIENT_TIMEOUT, exchange)
+        .await
+        .context("Daemon did not respond")?
+}
+
+/// Usage for `providers` from a running daemon, or `None` when no daemon is
+/// running or it cannot answer for every requested provider yet.
+pub async fn query_usage(providers: &[Provider]) -> Option<UsageReport> {
+    let path = socket_path();
+    if !path.exists() {
+        return None;
+    }
+    let request = Request::Usage {
+        providers: providers.to_vec(),
+    };
+    match self::request(&path, &request).await.ok()? {
+        Response::Usage { report } if covers(&report, providers) => Some(report),
+        _ => None,
+    }
+}
+
+/// Whether every requested provider has either usage or an error in `report`.
+fn covers(report: &UsageReport, providers: &[Provider]) -> bool {
+    providers.iter().all(|p| {
+        report.providers.iter().any(|r| r.provider == *p)
+            || report.errors.iter().any(|f| f.provider == *p)
+    })
+}
+
+/// systemd user unit running `exe daemon run`.
+pub fn systemd_unit(exe: &Path) -> String {
+    format!(
+        "[Unit]
+Description=ait provider usage daemon
+After=network-online.target
+
+[Service]
+ExecStart={} daemon run
+# Shell environment is not inherited; put provider tokens (KEY=value) here
+EnvironmentFile=-%h/.config/ait/daemon.env
+Restart=on-failure
+RestartSec=10
+
+[Install]
+WantedBy=default.target
+",
+        systemd_quote(&exe.to_string_lossy())
+    )
+}
+
+/// Quote a word for a systemd unit file, so paths with spaces stay one
+/// argument and `%` is not read as a specifier.
+fn systemd_quote(word: &str) -> String {
+    let escaped = word
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%");
+    format!("\"{}\"", escaped)
+}
+
+/// `~/.config/systemd/user/ait.service`, respecting XDG_CONFIG_HOME.
+pub fn systemd_unit_path() -> PathBuf {
+    std::env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("~"))
+                .join(".config")
+        })
+        .join("systemd")
+        .join("user")
+        .join("ait.service")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::report::{ProviderFailure, ProviderReport};
+    use crate::core::models::usage::UsageSnapshot;
+    use crate::core::providers::error::{ErrorKind, ErrorReport};
+
+    #[test]
+    fn request_wire_format() {
+        let json = serde_json::to_string(&Request::Usage {
+            providers: vec![Provider::Claude, Provider::OpenRouter],
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"method":"usage","providers":["claude","open_router"]}"#);
+
+        let ping: Request = serde_json::from_str(r#"{"method":"ping"}"#).unwrap();
+        assert!(matches!(ping, Request::Ping));
+        let all: Request = serde_json::from_str(r#"{"method":"usage"}"#).unwrap();
+        assert!(matches!(all, Request::Usage { providers } if providers.is_empty()));
+    }
+
+    #[test]
+    fn covers_requires_every_provider() {
+        let mut report = UsageReport::new(Utc::now());
+        let usage = UsageSnapshot {
+            provider: Provider::Claude,
+            source: "oauth".into(),
+            primary: None,
+            secondary: None,
+            tertiary: None,
+            identity: None,
+            local: None,
+            endpoints: Vec::new(),
+        };
+        report
+            .providers
+            .push(ProviderReport::new(usage, None, None, Utc::now(), true));
+        report.errors.push(ProviderFailure {
+            provider: Provider::Warp,
+            error: ErrorReport {
+                kind: ErrorKind::AuthMissing,
+                message: "WARP_TOKEN env var not set".into(),
+                status: None,
+            },
+        });
+
+        assert!(covers(&report, &[Provider::Claude, Provider::Warp]));
+        assert!(!covers(&report, &[Provider::Claude, Provider::Kiro]));
+    }
+
+    #[test]
+    fn systemd_unit_runs_daemon() {
+        let unit = systemd_unit(Path::new("/usr/local/bin/ait"));
+        assert!(unit.contains("ExecStart=\"/usr/local/bin/ait\" daemon run\n"));
+        assert!(unit.contains("WantedBy=default.target"));
+    }
+
+    #[test]
+    fn systemd_unit_quotes_exec_path() {
+        let unit = systemd_unit(Path::new("/home/me/My Tools/100%/ait"));
+        assert!(unit.contains("ExecStart=\"/home/me/My Tools/100%%/ait\" daemon run\n"));
+        assert_eq!(systemd_quote(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
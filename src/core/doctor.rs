@@ -0,0 +1,894 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::core::auth::{claude_credentials_path, codex_auth_path, decode_jwt_claims};
+use crate::core::config::AppConfig;
+use crate::core::cookies::CookieSource;
+use crate::core::cost::cache::{self, CostCache, CACHE_VERSION};
+use crate::core::cost::scanner::{self, LogDir};
+use crate::core::process::which;
+use crate::core::aws;
+use crate::core::providers::{
+    antigravity, augment, bedrock, codex, cursor, factory, gemini, generic_http, jetbrains, litellm, openai_admin, vertex_ai,
+    Provider,
+};
+
+/// What a diagnostic check looked at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckKind {
+    File,
+    Env,
+    Binary,
+    Process,
+    Token,
+    Endpoint,
+}
+
+impl CheckKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Env => "env",
+            Self::Binary => "binary",
+            Self::Process => "process",
+            Self::Token => "token",
+            Self::Endpoint => "endpoint",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Found,
+    Missing,
+    Expired,
+    Invalid,
+    /// Informational, e.g. an endpoint override in effect.
+    Info,
+}
+
+/// A single credential source, token or override. `detail` never holds a
+/// secret value: only paths, hosts, expiry times and parse errors.
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub kind: CheckKind,
+    pub name: String,
+    pub status: CheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl Check {
+    fn new(kind: CheckKind, name: impl Into<String>, status: CheckStatus) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            status,
+            detail: None,
+        }
+    }
+
+    fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    fn invalid(mut self, detail: String) -> Self {
+        self.status = CheckStatus::Invalid;
+        self.detail = Some(detail);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderDiagnosis {
+    pub provider: Provider,
+    pub enabled: bool,
+    /// False for providers that are not implemented yet.
+    pub implemented: bool,
+    pub checks: Vec<Check>,
+}
+
+impl ProviderDiagnosis {
+    /// Whether at least one credential source was found.
+    pub fn has_credentials(&self) -> bool {
+        self.checks.iter().any(|c| {
+            c.status == CheckStatus::Found
+                && matches!(
+                    c.kind,
+                    CheckKind::File | CheckKind::Env | CheckKind::Binary | CheckKind::Process
+                )
+        })
+    }
+
+    /// Whether a source was found but is unusable (expired or unreadable).
+    pub fn has_problems(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|c| matches!(c.status, CheckStatus::Expired | CheckStatus::Invalid))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigDiagnosis {
+    pub path: PathBuf,
+    pub exists: bool,
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheDiagnosis {
+    pub path: PathBuf,
+    pub exists: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    pub expected_version: u64,
+    pub files: usize,
+    pub records: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CacheDiagnosis {
+    /// A missing cache is fine (it is rebuilt on the next scan); a corrupt or
+    /// stale one is reported.
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none() && (!self.exists || self.version == Some(self.expected_version))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub config: ConfigDiagnosis,
+    pub providers: Vec<ProviderDiagnosis>,
+    pub log_dirs: Vec<LogDir>,
+    pub cache: CacheDiagnosis,
+}
+
+/// Collect diagnostics for `providers`. Only reads local files, env vars and
+/// the process table; no network calls are made.
+pub fn diagnose(config: &AppConfig, providers: &[Provider]) -> DoctorReport {
+    let config_path = AppConfig::config_path();
+    let config_diag = ConfigDiagnosis {
+        exists: config_path.exists(),
+        issues: config.validate(),
+        path: config_path,
+    };
+
+    let now = Utc::now();
+    let providers = providers
+        .iter()
+        .map(|&provider| ProviderDiagnosis {
+            provider,
+            enabled: config
+                .providers
+                .iter()
+                .any(|c| c.enabled && Provider::from_id(&c.id) == Some(provider)),
+            implemented: !provider.is_stub(),
+            checks: {
+                let mut checks = provider_checks(config, provider, now);
+                checks.extend(cookie_check(config, provider));
+                checks
+            },
+        })
+        .collect();
+
+    DoctorReport {
+        config: config_diag,
+        providers,
+        log_dirs: scanner::log_dirs(),
+        cache: diagnose_cache(&cache::cache_path()),
+    }
+}
+
+fn provider_checks(config: &AppConfig, provider: Provider, now: DateTime<Utc>) -> Vec<Check> {
+    match provider {
+        Provider::Claude => claude_checks(now),
+        Provider::Codex => codex_checks(now),
+        Provider::Copilot => {
+            let mut checks = env_checks("GITHUB_TOKEN", now);
+            checks.push(binary_check("gh"));
+            checks
+        }
+        Provider::Gemini => gemini_checks(now),
+        Provider::Warp => env_checks("WARP_TOKEN", now),
+        Provider::Kimi => env_checks("KIMI_TOKEN", now),
+        Provider::KimiK2 => env_checks("KIMI_K2_API_KEY", now),
+        Provider::OpenRouter => env_checks("OPENROUTER_API_KEY", now),
+        Provider::MiniMax => {
+            let mut checks = env_checks("MINIMAX_API_TOKEN", now);
+            checks.extend(endpoint_env("MINIMAX_API_HOST"));
+            checks
+        }
+        Provider::Zai => {
+            let mut checks = env_checks("Z_AI_API_KEY", now);
+            checks.extend(endpoint_env("Z_AI_API_HOST"));
+            checks.extend(
+                endpoint_env("Z_AI_QUOTA_URL").map(|c| c.with_detail("deprecated and ignored")),
+            );
+            checks
+        }
+        Provider::Kiro => vec![binary_check("kiro-cli")],
+        Provider::JetBrains => jetbrains_checks(),
+        Provider::Antigravity => antigravity_checks(),
+        Provider::Synthetic => env_checks("SYNTHETIC_API_KEY", now),
+        Provider::Cursor => cursor_checks(now),
+        Provider::Ollama => {
+            let mut checks = vec![binary_check("ollama")];
+            checks.extend(endpoint_env("OLLAMA_HOST"));
+            // The cloud key is optional; only report it when set
+            if std::env::var("OLLAMA_API_KEY").is_ok_and(|k| !k.is_empty()) {
+                checks.extend(env_checks("OLLAMA_API_KEY", now));
+            }
+            checks
+        }
+        Provider::VertexAi => vertex_ai_checks(config),
+        Provider::Bedrock => bedrock_checks(config),
+        Provider::Augment => augment_checks(config),
+        Provider::Factory => factory_checks(config, now),
+        Provider::AnthropicAdmin => env_checks("ANTHROPIC_ADMIN_KEY", now),
+        Provider::OpenAiAdmin => {
+            let mut checks = env_checks("OPENAI_ADMIN_KEY", now);
+            checks.push(
+                Check::new(CheckKind::Endpoint, "project", CheckStatus::Info).with_detail(
+                    openai_admin::configured_project(config)
+                        .unwrap_or_else(|| "not set; rate limits are skipped".to_string()),
+                ),
+            );
+            checks
+        }
+        Provider::DeepSeek => env_checks("DEEPSEEK_API_KEY", now),
+        Provider::Mistral => env_checks("MISTRAL_API_KEY", now),
+        Provider::Groq => env_checks("GROQ_API_KEY", now),
+        Provider::Xai => {
+            let mut checks = env_checks("XAI_API_KEY", now);
+            // The management key is optional; only report it when set
+            if std::env::var("XAI_MANAGEMENT_KEY").is_ok_and(|k| !k.is_empty()) {
+                checks.extend(env_checks("XAI_MANAGEMENT_KEY", now));
+            }
+            checks
+        }
+        Provider::Together => env_checks("TOGETHER_API_KEY", now),
+        Provider::GenericHttp => generic_http_checks(config, now),
+        Provider::LiteLlm => {
+            let mut checks = env_checks(litellm::API_KEY_ENV, now);
+            checks.push(match litellm::proxy_url() {
+                Some(url) => Check::new(CheckKind::Endpoint, litellm::PROXY_URL_ENV, CheckStatus::Info)
+                    .with_detail(url),
+                None => Check::new(CheckKind::Endpoint, litellm::PROXY_URL_ENV, CheckStatus::Missing),
+            });
+            checks
+        }
+        _ => Vec::new(),
+    }
+}
+
+// --- Provider-specific checks ---
+
+fn claude_checks(now: DateTime<Utc>) -> Vec<Check> {
+    let path = claude_credentials_path();
+    let mut checks = Vec::new();
+    let Some(json) = json_file_check(&path, &mut checks) else {
+        return checks;
+    };
+    let oauth = &json["claudeAiOauth"];
+    if let Some(token) = oauth["accessToken"].as_str() {
+        let expires_at = oauth["expiresAt"]
+            .as_i64()
+            .and_then(|ms| Utc.timestamp_millis_opt(ms).single());
+        match expires_at {
+            Some(at) => checks.push(expiry_check("accessToken", at, now)),
+            None => checks.extend(jwt_check("accessToken", token, now)),
+        }
+    } else {
+        checks.push(
+            Check::new(CheckKind::Token, "accessToken", CheckStatus::Missing)
+                .with_detail("no claudeAiOauth.accessToken in credentials file"),
+        );
+    }
+    checks
+}
+
+fn codex_checks(now: DateTime<Utc>) -> Vec<Check> {
+    let mut checks = Vec::new();
+    if let Ok(home) = std::env::var("CODEX_HOME") {
+        checks.push(Check::new(CheckKind::Env, "CODEX_HOME", CheckStatus::Info).with_detail(home));
+    }
+    let path = codex_auth_path();
+    if let Some(json) = json_file_check(&path, &mut checks) {
+        if let Some(token) = json["tokens"]["access_token"].as_str() {
+            match jwt_check("access_token", token, now) {
+                Some(check) => checks.push(check),
+                None => checks.push(Check::new(
+                    CheckKind::Token,
+                    "access_token",
+                    CheckStatus::Found,
+                )),
+            }
+        } else if json["OPENAI_API_KEY"].as_str().is_some_and(|k| !k.is_empty()) {
+            checks.push(
+                Check::new(CheckKind::Token, "OPENAI_API_KEY", CheckStatus::Found)
+                    .with_detail("API key from auth.json"),
+            );
+        } else {
+            checks.push(
+                Check::new(CheckKind::Token, "access_token", CheckStatus::Missing)
+                    .with_detail("no tokens or OPENAI_API_KEY in auth.json"),
+            );
+        }
+    }
+    if let Some(base) = codex::base_url_override() {
+        checks.push(
+            Check::new(CheckKind::Endpoint, "chatgpt_base_url", CheckStatus::Info)
+                .with_detail(base),
+        );
+    }
+    checks
+}
+
+fn gemini_checks(now: DateTime<Utc>) -> Vec<Check> {
+    let path = gemini::gemini_oauth_path();
+    let mut checks = Vec::new();
+    let Some(json) = json_file_check(&path, &mut checks) else {
+        return checks;
+    };
+    let expires_at = json["expiry_date"]
+        .as_i64()
+        .and_then(|ms| Utc.timestamp_millis_opt(ms).single());
+    let can_refresh = json["refresh_token"].as_str().is_some_and(|t| !t.is_empty());
+    match expires_at {
+        Some(at) => {
+            let mut check = expiry_check("access_token", at, now);
+            // An expired access token is refreshed automatically
+            if check.status == CheckStatus::Expired && can_refresh {
+                check.status = CheckStatus::Info;
+                let detail = check.detail.take().unwrap_or_default();
+                check.detail = Some(format!("{}, will be refreshed", detail));
+            }
+            checks.push(check);
+        }
+        None => checks.push(
+            Check::new(CheckKind::Token, "access_token", CheckStatus::Info)
+                .with_detail("no expiry_date, will be refreshed"),
+        ),
+    }
+    if !can_refresh {
+        checks.push(Check::new(
+            CheckKind::Token,
+            "refresh_token",
+            CheckStatus::Missing,
+        ));
+    }
+    checks
+}
+
+fn jetbrains_checks() -> Vec<Check> {
+    let mut checks = Vec::new();
+    for root in jetbrains::ide_roots() {
+        if !root.is_dir() {
+            checks.push(file_status(&root, false));
+        }
+    }
+    let dirs = jetbrains::candidate_dirs();
+    for dir in &dirs {
+        let path = dir.join(jetbrains::QUOTA_FILE);
+        let exists = path.is_file();
+        checks.push(file_status(&path, exists));
+    }
+    if dirs.is_empty() {
+        checks.push(
+            Check::new(CheckKind::File, jetbrains::QUOTA_FILE, CheckStatus::Missing)
+                .with_detail("no IDE options directories found"),
+        );
+    }
+    checks
+}
+
+fn antigravity_checks() -> Vec<Check> {
+    let mut pgrep = binary_check("pgrep");
+    if pgrep.status != CheckStatus::Found {
+        return vec![pgrep];
+    }
+    // Only a tool here; the running process is the credential source
+    pgrep.status = CheckStatus::Info;
+    let name = antigravity::process_name();
+    // Plain `pgrep -f` prints only pids; the command line holds a CSRF token
+    let pids = std::process::Command::new("pgrep")
+        .args(["-f", name])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).split_whitespace().count())
+        .unwrap_or(0);
+    let check = if pids > 0 {
+        Check::new(CheckKind::Process, name, CheckStatus::Found)
+            .with_detail(format!("{} running", pids))
+    } else {
+        Check::new(CheckKind::Process, name, CheckStatus::Missing).with_detail("not running")
+    };
+    vec![pgrep, check]
+}
+
+fn cursor_checks(now: DateTime<Utc>) -> Vec<Check> {
+    let path = cursor::state_db_path();
+    if !path.exists() {
+        return vec![file_status(&path, false)];
+    }
+    let mut checks = vec![file_status(&path, true)];
+    match cursor::read_auth(&path) {
+        Ok(auth) => checks.push(
+            jwt_check("accessToken", &auth.access_token, now).unwrap_or_else(|| {
+                Check::new(CheckKind::Token, "accessToken", CheckStatus::Found)
+            }),
+        ),
+        Err(e) => checks.push(
+            Check::new(CheckKind::Token, "accessToken", CheckStatus::Missing)
+                .with_detail(format!("{:#}", e)),
+        ),
+    }
+    checks
+}
+
+fn vertex_ai_checks(config: &AppConfig) -> Vec<Check> {
+    let path = vertex_ai::credentials_path();
+    let mut checks = Vec::new();
+    let Some(json) = json_file_check(&path, &mut checks) else {
+        return checks;
+    };
+    match json["type"].as_str() {
+        // A refresh token or service-account key never expires on its own
+        Some(kind @ ("authorized_user" | "service_account")) => {
+            checks.push(Check::new(CheckKind::Token, kind, CheckStatus::Found))
+        }
+        other => checks.push(
+            Check::new(CheckKind::Token, "type", CheckStatus::Invalid).with_detail(format!(
+                "unsupported credentials type {}",
+                other.unwrap_or("(none)")
+            )),
+        ),
+    }
+    let creds_project = json["project_id"]
+        .as_str()
+        .or_else(|| json["quota_project_id"].as_str());
+    let region = vertex_ai::resolve_region(config);
+    checks.push(match vertex_ai::resolve_project(config, creds_project) {
+        Some(project) => Check::new(CheckKind::Endpoint, "project", CheckStatus::Info)
+            .with_detail(format!("{} ({})", project, region)),
+        None => Check::new(CheckKind::Endpoint, "project", CheckStatus::Missing)
+            .with_detail("set `project` in config.toml or GOOGLE_CLOUD_PROJECT"),
+    });
+    checks
+}
+
+/// Cost comes from the Claude logs either way; credentials only add quotas.
+fn bedrock_checks(config: &AppConfig) -> Vec<Check> {
+    let profile = aws::profile();
+    let mut checks = Vec::new();
+    if std::env::var("AWS_ACCESS_KEY_ID").is_ok_and(|k| !k.is_empty()) {
+        checks.push(Check::new(
+            CheckKind::Env,
+            "AWS_ACCESS_KEY_ID",
+            CheckStatus::Found,
+        ));
+    } else {
+        let path = aws::credentials_path();
+        checks.push(file_status(&path, path.exists()));
+    }
+    checks.push(match aws::load_credentials(&profile) {
+        Some(_) => Check::new(CheckKind::Token, "access key", CheckStatus::Found),
+        None => Check::new(CheckKind::Token, "access key", CheckStatus::Missing)
+            .with_detail(format!("no static keys for profile {}; quotas are skipped", profile)),
+    });
+    checks.push(
+        Check::new(CheckKind::Endpoint, "region", CheckStatus::Info).with_detail(format!(
+            "{} (profile {})",
+            bedrock::resolve_region(config, &profile),
+            profile
+        )),
+    );
+    checks
+}
+
+fn augment_checks(config: &AppConfig) -> Vec<Check> {
+    if std::env::var("AUGMENT_SESSION_AUTH").is_ok_and(|s| !s.is_empty()) {
+        return vec![Check::new(
+            CheckKind::Env,
+            "AUGMENT_SESSION_AUTH",
+            CheckStatus::Found,
+        )];
+    }
+    let mut checks = Vec::new();
+    let Some(json) = json_file_check(&augment::session_path(config), &mut checks) else {
+        return checks;
+    };
+    let has_token = json["accessToken"].as_str().is_some_and(|t| !t.is_empty());
+    checks.push(if has_token {
+        Check::new(CheckKind::Token, "accessToken", CheckStatus::Found)
+    } else {
+        Check::new(CheckKind::Token, "accessToken", CheckStatus::Missing)
+            .with_detail("run `auggie login`")
+    });
+    if let Some(tenant) = json["tenantURL"].as_str() {
+        checks.push(
+            Check::new(CheckKind::Endpoint, "tenantURL", CheckStatus::Info).with_detail(tenant),
+        );
+    }
+    checks
+}
+
+fn factory_checks(config: &AppConfig, now: DateTime<Utc>) -> Vec<Check> {
+    if std::env::var("FACTORY_API_KEY").is_ok_and(|k| !k.is_empty()) {
+        return env_checks("FACTORY_API_KEY", now);
+    }
+    let mut checks = Vec::new();
+    let Some(json) = json_file_check(&factory::auth_path(config), &mut checks) else {
+        return checks;
+    };
+    match json["access_token"].as_str() {
+        Some(token) => checks.push(
+            jwt_check("access_token", token, now).unwrap_or_else(|| {
+                Check::new(CheckKind::Token, "access_token", CheckStatus::Found)
+            }),
+        ),
+        None => checks.push(
+            Check::new(CheckKind::Token, "access_token", CheckStatus::Missing)
+                .with_detail("run `droid` and sign in"),
+        ),
+    }
+    checks
+}
+
+fn generic_http_checks(config: &AppConfig, now: DateTime<Utc>) -> Vec<Check> {
+    let endpoints = generic_http::configured(config);
+    if endpoints.is_empty() {
+        return vec![Check::new(CheckKind::Endpoint, "[[generic_http]]", CheckStatus::Missing)
+            .with_detail("no endpoint in config.toml")];
+    }
+    let mut checks = Vec::new();
+    for endpoint in endpoints {
+        // Only the host: the path or query may carry a secret
+        let host = generic_http::url_host(&endpoint.url);
+        let mut check = Check::new(CheckKind::Endpoint, &endpoint.name, CheckStatus::Info)
+            .with_detail(host);
+        if let Err(msg) = endpoint.validate() {
+            check = check.invalid(msg);
+        }
+        checks.push(check);
+        for reference in endpoint.secret_refs() {
+            if let Some(var) = reference.strip_prefix("env:") {
+                checks.extend(env_checks(var, now));
+            } else if let Some(path) = reference.strip_prefix("file:") {
+                let path = crate::core::config::expand_home(path);
+                checks.push(file_status(&path, path.exists()));
+            }
+        }
+    }
+    checks
+}
+
+// --- Generic checks ---
+
+/// The `cookie` source configured for `provider`. Values are never printed.
+fn cookie_check(config: &AppConfig, provider: Provider) -> Option<Check> {
+    let spec = config
+        .providers
+        .iter()
+        .find(|p| Provider::from_id(&p.id) == Some(provider))?
+        .cookie
+        .as_deref()?;
+    let check = match CookieSource::parse(spec) {
+        Ok(CookieSource::File(path)) => file_status(&path, path.exists()),
+        Ok(CookieSource::Auto) => Check::new(CheckKind::File, "cookie", CheckStatus::Info)
+            .with_detail("searching installed browsers"),
+        Ok(CookieSource::Browser(browser)) => {
+            Check::new(CheckKind::File, "cookie", CheckStatus::Info)
+                .with_detail(format!("from {}", browser.id()))
+        }
+        Ok(CookieSource::Header(_)) => Check::new(CheckKind::File, "cookie", CheckStatus::Info)
+            .with_detail("set in config"),
+        Err(msg) => Check::new(CheckKind::File, "cookie", CheckStatus::Invalid).with_detail(msg),
+    };
+    Some(check)
+}
+
+fn file_status(path: &Path, exists: bool) -> Check {
+    let status = if exists {
+        CheckStatus::Found
+    } else {
+        CheckStatus::Missing
+    };
+    Check::new(CheckKind::File, path.display().to_string(), status)
+}
+
+/// Check a JSON credentials file, returning its contents when it parses.
+fn json_file_check(path: &Path, checks: &mut Vec<Check>) -> Option<serde_json::Value> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            checks.push(file_status(path, false));
+            return None;
+        }
+        Err(e) => {
+            checks.push(file_status(path, true).invalid(e.to_string()));
+            return None;
+        }
+    };
+    match serde_json::from_str(&content) {
+        Ok(json) => {
+            checks.push(file_status(path, true));
+            Some(json)
+        }
+        Err(e) => {
+            checks.push(file_status(path, true).invalid(format!("invalid JSON: {}", e)));
+            None
+        }
+    }
+}
+
+/// Check a credential env var and, when it holds a JWT, its expiry.
+fn env_checks(var: &str, now: DateTime<Utc>) -> Vec<Check> {
+    match std::env::var(var) {
+        Ok(value) if !value.is_empty() => {
+            let mut checks = vec![Check::new(CheckKind::Env, var, CheckStatus::Found)];
+            checks.extend(jwt_check(var, &value, now));
+            checks
+        }
+        Ok(_) => vec![Check::new(CheckKind::Env, var, CheckStatus::Missing).with_detail("empty")],
+        Err(_) => vec![Check::new(CheckKind::Env, var, CheckStatus::Missing)],
+    }
+}
+
+/// An endpoint override env var, reported only when set.
+fn endpoint_env(var: &str) -> Option<Check> {
+    let value = std::env::var(var).ok()?;
+    Some(Check::new(CheckKind::Endpoint, var, CheckStatus::Info).with_detail(value))
+}
+
+fn binary_check(name: &str) -> Check {
+    match which(name) {
+        Some(path) => Check::new(CheckKind::Binary, name, CheckStatus::Found)
+            .with_detail(path.display().to_string()),
+        None => Check::new(CheckKind::Binary, name, CheckStatus::Missing).with_detail("not in PATH"),
+    }
+}
+
+/// Expiry of a JWT from its `exp` claim; `None` for opaque tokens.
+fn jwt_check(name: &str, token: &str, now: DateTime<Utc>) -> Option<Check> {
+    let claims = decode_jwt_claims(token).ok()?;
+    let exp = claims.get("exp")?.as_i64()?;
+    let expires_at = Utc.timestamp_opt(exp, 0).single()?;
+    Some(expiry_check(name, expires_at, now))
+}
+
+fn expiry_check(name: &str, expires_at: DateTime<Utc>, now: DateTime<Utc>) -> Check {
+    if expires_at <= now {
+        Check::new(CheckKind::Token, name, CheckStatus::Expired)
+            .with_detail(format!("expired {} ago", format_age(now - expires_at)))
+    } else {
+        Check::new(CheckKind::Token, name, CheckStatus::Found)
+            .with_detail(format!("expires in {}", format_age(expires_at - now)))
+    }
+}
+
+/// Compact duration like "3d 4h", "2h 15m" or "12m".
+fn format_age(duration: chrono::Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, mins) = (minutes / 1440, (minutes / 60) % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else {
+        format!("{}m", mins)
+    }
+}
+
+// --- Cost cache ---
+
+fn diagnose_cache(path: &Path) -> CacheDiagnosis {
+    let mut diag = CacheDiagnosis {
+        path: path.to_path_buf(),
+        exists: false,
+        size_bytes: None,
+        version: None,
+        expected_version: CACHE_VERSION,
+        files: 0,
+        records: 0,
+        error: None,
+    };
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return diag,
+        Err(e) => {
+            diag.exists = true;
+            diag.error = Some(e.to_string());
+            return diag;
+        }
+    };
+    diag.exists = true;
+    diag.size_bytes = Some(content.len() as u64);
+    match serde_json::from_str::<CostCache>(&content) {
+        Ok(cache) => {
+            diag.version = Some(cache.version);
+            diag.files = cache.files.len();
+            diag.records = cache.files.values().map(|f| f.records.len()).sum();
+        }
+        Err(e) => diag.error = Some(format!("unreadable, will be rebuilt: {}", e)),
+    }
+    diag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+
+    fn jwt_with_exp(exp: i64) -> String {
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(format!(r#"{{"exp":{}}}"#, exp));
+        format!("eyJhbGciOiJub25lIn0.{}.sig", payload)
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn jwt_check_reports_expiry() {
+        let future = jwt_with_exp(now().timestamp() + 3 * 86_400 + 4 * 3600);
+        let check = jwt_check("tok", &future, now()).unwrap();
+        assert_eq!(check.status, CheckStatus::Found);
+        assert_eq!(check.detail.as_deref(), Some("expires in 3d 4h"));
+
+        let past = jwt_with_exp(now().timestamp() - 2 * 3600 - 5 * 60);
+        let check = jwt_check("tok", &past, now()).unwrap();
+        assert_eq!(check.status, CheckStatus::Expired);
+        assert_eq!(check.detail.as_deref(), Some("expired 2h 5m ago"));
+    }
+
+    #[test]
+    fn jwt_check_ignores_opaque_tokens() {
+        assert!(jwt_check("tok", "sk-or-v1-abcdef", now()).is_none());
+    }
+
+    #[test]
+    fn env_checks_never_include_value() {
+        let var = "AIT_DOCTOR_TEST_SECRET";
+        let secret = jwt_with_exp(now().timestamp() + 600);
+        std::env::set_var(var, &secret);
+        let checks = env_checks(var, now());
+        std::env::remove_var(var);
+
+        assert_eq!(checks[0].status, CheckStatus::Found);
+        assert_eq!(checks[1].kind, CheckKind::Token);
+        let json = serde_json::to_string(&checks).unwrap();
+        assert!(!json.contains(&secret));
+        assert!(!json.contains(secret.split('.').nth(1).unwrap()));
+    }
+
+    #[test]
+    fn cookie_check_hides_header_value() {
+        let mut config = AppConfig::default();
+        config.providers = vec![crate::core::config::ProviderConfig {
+            id: "cursor".into(),
+            enabled: true,
+            source: "auto".into(),
+            api_key: None,
+            timeout_secs: None,
+            cookie: Some("WorkosCursorSessionToken=secret-value".into()),
+            project: None,
+            region: None,
+            credentials: None,
+            currency: None,
+        }];
+        let check = cookie_check(&config, Provider::Cursor).unwrap();
+        assert_eq!(check.status, CheckStatus::Info);
+        assert!(!serde_json::to_string(&check).unwrap().contains("secret-value"));
+        assert!(cookie_check(&config, Provider::Claude).is_none());
+
+        config.providers[0].cookie = Some("netscape".into());
+        let check = cookie_check(&config, Provider::Cursor).unwrap();
+        assert_eq!(check.status, CheckStatus::Invalid);
+    }
+
+    #[test]
+    fn generic_http_checks_show_host_and_references() {
+        let mut config = AppConfig::default();
+        assert_eq!(generic_http_checks(&config, now())[0].status, CheckStatus::Missing);
+
+        config.generic_http = vec![
+            toml::from_str(
+                r#"
+                name = "gateway"
+                url = "https://gateway.example.com/usage?key=inline-secret"
+                headers = { Authorization = "Bearer ${env:AIT_DOCTOR_GATEWAY_TOKEN}" }
+                "#,
+            )
+            .unwrap(),
+        ];
+        let checks = generic_http_checks(&config, now());
+        assert_eq!(checks[0].name, "gateway");
+        assert_eq!(checks[0].detail.as_deref(), Some("gateway.example.com"));
+        assert_eq!(checks[1].name, "AIT_DOCTOR_GATEWAY_TOKEN");
+        assert_eq!(checks[1].status, CheckStatus::Missing);
+        assert!(!serde_json::to_string(&checks).unwrap().contains("inline-secret"));
+    }
+
+    #[test]
+    fn json_file_check_statuses() {
+        let dir = std::env::temp_dir().join("ait_test_doctor_files");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut checks = Vec::new();
+        assert!(json_file_check(&dir.join("missing.json"), &mut checks).is_none());
+        assert_eq!(checks[0].status, CheckStatus::Missing);
+
+        let bad = dir.join("bad.json");
+        std::fs::write(&bad, "{\"accessToken\": \"secret-value").unwrap();
+        assert!(json_file_check(&bad, &mut checks).is_none());
+        assert_eq!(checks[1].status, CheckStatus::Invalid);
+        assert!(!checks[1].detail.as_ref().unwrap().contains("secret-value"));
+
+        let good = dir.join("good.json");
+        std::fs::write(&good, r#"{"a": 1}"#).unwrap();
+        assert_eq!(json_file_check(&good, &mut checks).unwrap()["a"], 1);
+        assert_eq!(checks[2].status, CheckStatus::Found);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn diagnose_cache_health() {
+        let dir = std::env::temp_dir().join("ait_test_doctor_cache");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cost-cache.json");
+
+        let diag = diagnose_cache(&path);
+        assert!(!diag.exists);
+        assert!(diag.is_healthy());
+
+        std::fs::write(
+            &path,
+            format!(
+                r#"{{"version":{},"files":{{"/a.jsonl":{{"mtime_ms":1,"size":2,"parsed_bytes":2,"records":[]}}}}}}"#,
+                CACHE_VERSION
+            ),
+        )
+        .unwrap();
+        let diag = diagnose_cache(&path);
+        assert_eq!(diag.files, 1);
+        assert!(diag.is_healthy());
+
+        std::fs::write(&path, r#"{"version":1,"files":{}}"#).unwrap();
+        assert!(!diagnose_cache(&path).is_healthy());
+
+        std::fs::write(&path, "not json").unwrap();
+        let diag = diagnose_cache(&path);
+        assert!(diag.error.is_some());
+        assert!(!diag.is_healthy());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn has_credentials_and_problems() {
+        let diag = ProviderDiagnosis {
+            provider: Provider::Claude,
+            enabled: true,
+            implemented: true,
+            checks: vec![
+                Check::new(CheckKind::File, "/x", CheckStatus::Found),
+                Check::new(CheckKind::Token, "accessToken", CheckStatus::Expired),
+            ],
+        };
+        assert!(diag.has_credentials());
+        assert!(diag.has_problems());
+    }
+}
@@ -81,6 +81,18 @@ pub fn format_reset_datetime(resets_at: &DateTime<Utc>) -> String {
     format!("Resets {} at {}", day_str, time_str)
 }
 
+/// Returns "updated 40s ago", "updated 5m ago" or "updated 2h ago" relative to now.
+pub fn format_updated_ago(updated_at: &DateTime<Utc>) -> String {
+    let seconds = (Utc::now() - *updated_at).num_seconds().max(0);
+    if seconds < 60 {
+        format!("updated {}s ago", seconds)
+    } else if seconds < 3600 {
+        format!("updated {}m ago", seconds / 60)
+    } else {
+        format!("updated {}h ago", seconds / 3600)
+    }
+}
+
 /// Returns "[████████░░░░]" where █ = remaining portion, ░ = used portion.
 /// Width is the number of block characters inside the brackets (default 12).
 pub fn format_usage_bar(used_percent: f64, width: usize) -> String {
@@ -94,9 +106,46 @@ pub fn format_usage_bar(used_percent: f64, width: usize) -> String {
     format!("[{}{}]", filled, empty)
 }
 
-/// Returns "$123.45 remaining".
-pub fn format_credits(remaining: f64) -> String {
-    format!("${:.2} remaining", remaining)
+/// Returns "$123.45", "¥88.00" or "CHF 12.00" depending on the currency code.
+pub fn format_money(amount: f64, currency: &str) -> String {
+    match crate::core::currency::symbol(currency) {
+        Some(symbol) => format!("{}{:.2}", symbol, amount),
+        None => format!(
+            "{} {:.2}",
+            crate::core::currency::normalize_code(currency),
+            amount
+        ),
+    }
+}
+
+/// Returns "3420 credits" or "12.50 credits", for amounts in a vendor's
+/// own units rather than money.
+pub fn format_units(amount: f64, unit: &str) -> String {
+    if amount.fract() == 0.0 {
+        format!("{} {}", amount, unit)
+    } else {
+        format!("{:.2} {}", amount, unit)
+    }
+}
+
+/// Returns "3.4 GB", "274 MB" or "512 B" (decimal units, as Ollama reports them).
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [(u64, &str); 3] = [(1_000_000_000_000, "TB"), (1_000_000_000, "GB"), (1_000_000, "MB")];
+    for (size, unit) in UNITS {
+        if bytes >= size {
+            return format!("{:.1} {}", bytes as f64 / size as f64, unit);
+        }
+    }
+    if bytes >= 1_000 {
+        format!("{} KB", bytes / 1_000)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Returns "$123.45 remaining" (in the given currency).
+pub fn format_credits(remaining: f64, currency: &str) -> String {
+    format!("{} remaining", format_money(remaining, currency))
 }
 
 #[cfg(test)]
@@ -112,6 +161,15 @@ mod tests {
         assert_eq!(format_remaining_percent(110.0), "0% remaining");
     }
 
+    #[test]
+    fn format_bytes_units() {
+        assert_eq!(format_bytes(3_400_000_000), "3.4 GB");
+        assert_eq!(format_bytes(274_000_000), "274.0 MB");
+        assert_eq!(format_bytes(1_500_000_000_000), "1.5 TB");
+        assert_eq!(format_bytes(2_048), "2 KB");
+        assert_eq!(format_bytes(512), "512 B");
+    }
+
     #[test]
     fn format_reset_countdown_past() {
         let past = Utc::now() - Duration::seconds(10);
@@ -141,6 +199,16 @@ mod tests {
         assert!(result.contains('d'));
     }
 
+    #[test]
+    fn format_updated_ago_units() {
+        let now = Utc::now();
+        assert_eq!(format_updated_ago(&(now - Duration::seconds(40))), "updated 40s ago");
+        assert_eq!(format_updated_ago(&(now - Duration::minutes(5))), "updated 5m ago");
+        assert_eq!(format_updated_ago(&(now - Duration::hours(2))), "updated 2h ago");
+        // Clock skew never yields a negative age
+        assert_eq!(format_updated_ago(&(now + Duration::seconds(30))), "updated 0s ago");
+    }
+
     #[test]
     fn format_usage_bar_width() {
         // 0% used — all filled
@@ -158,8 +226,21 @@ mod tests {
 
     #[test]
     fn format_credits_two_decimals() {
-        assert_eq!(format_credits(123.45), "$123.45 remaining");
-        assert_eq!(format_credits(0.0), "$0.00 remaining");
-        assert_eq!(format_credits(5.0), "$5.00 remaining");
+        assert_eq!(format_credits(123.45, "usd"), "$123.45 remaining");
+        assert_eq!(format_credits(0.0, "USD"), "$0.00 remaining");
+        assert_eq!(format_credits(5.0, "USD"), "$5.00 remaining");
+    }
+
+    #[test]
+    fn format_units_drops_whole_decimals() {
+        assert_eq!(format_units(3420.0, "credits"), "3420 credits");
+        assert_eq!(format_units(12.5, "credits"), "12.50 credits");
+    }
+
+    #[test]
+    fn format_money_uses_symbol_or_code() {
+        assert_eq!(format_money(12.5, "cny"), "¥12.50");
+        assert_eq!(format_money(3.0, "EUR"), "€3.00");
+        assert_eq!(format_money(7.0, "chf"), "CHF 7.00");
     }
 }
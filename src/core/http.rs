@@ -0,0 +1,474 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::core::config::AppConfig;
+use crate::core::providers::Provider;
+
+/// User-Agent sent with every request unless a provider sets its own.
+pub const USER_AGENT: &str = concat!("ait/", env!("CARGO_PKG_VERSION"));
+
+fn default_timeout_secs() -> u64 {
+    15
+}
+fn default_connect_timeout_secs() -> u64 {
+    5
+}
+fn default_retries() -> u32 {
+    2
+}
+fn default_backoff_ms() -> u64 {
+    500
+}
+fn default_max_backoff_secs() -> u64 {
+    10
+}
+
+/// The `[http]` config section, shared by all providers.
+///
+/// `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` are honored from the
+/// environment; `proxy` overrides them (still respecting `NO_PROXY`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpSettings {
+    /// Total time allowed for one request, per attempt
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// Retries after a 429/5xx response or a connection failure
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+    /// Base delay, doubled on each retry and jittered
+    #[serde(default = "default_backoff_ms")]
+    pub backoff_ms: u64,
+    /// Longest delay waited before a retry, including `Retry-After`
+    #[serde(default = "default_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// PEM bundle of extra root certificates (e.g. a corporate MITM proxy CA)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<PathBuf>,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+            connect_timeout_secs: default_connect_timeout_secs(),
+            retries: default_retries(),
+            backoff_ms: default_backoff_ms(),
+            max_backoff_secs: default_max_backoff_secs(),
+            proxy: None,
+            ca_bundle: None,
+        }
+    }
+}
+
+impl HttpSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.timeout_secs == 0 || self.connect_timeout_secs == 0 {
+            return Err("timeouts must be at least 1 second".to_string());
+        }
+        if let Some(proxy) = &self.proxy {
+            if !proxy.starts_with("http://") && !proxy.starts_with("https://") {
+                return Err(format!(
+                    "proxy '{}' must be an http:// or https:// URL",
+                    proxy
+                ));
+            }
+        }
+        if let Some(path) = &self.ca_bundle {
+            if !path.is_file() {
+                return Err(format!("ca_bundle '{}' not found", path.display()));
+            }
+        }
+        Ok(())
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            retries: self.retries,
+            base: Duration::from_millis(self.backoff_ms),
+            max: Duration::from_secs(self.max_backoff_secs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    retries: u32,
+    base: Duration,
+    max: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay before retry `attempt` (0-based), or `None` to give up. A
+    /// `Retry-After` longer than `max` is not waited for.
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>, jitter: f64) -> Option<Duration> {
+        if attempt >= self.retries {
+            return None;
+        }
+        if let Some(wait) = retry_after {
+            return (wait <= self.max).then_some(wait);
+        }
+        // Exponential backoff with equal jitter: half fixed, half random
+        let exp = self.base.saturating_mul(1 << attempt.min(16)).min(self.max);
+        let half = exp / 2;
+        Some(half + half.mul_f64(jitter.clamp(0.0, 1.0)))
+    }
+}
+
+fn is_idempotent(method: &reqwest::Method) -> bool {
+    use reqwest::Method;
+    [
+        Method::GET,
+        Method::HEAD,
+        Method::OPTIONS,
+        Method::TRACE,
+        Method::PUT,
+        Method::DELETE,
+    ]
+    .contains(method)
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || matches!(status.as_u16(), 500 | 502 | 503 | 504)
+}
+
+/// Parse `Retry-After` as delay-seconds or an HTTP date.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((at - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Uniform random value in [0, 1) from the std hasher's per-process seed.
+fn jitter() -> f64 {
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// HTTP client shared by all providers: one connection pool, consistent
+/// timeouts, User-Agent, proxy and TLS settings, and retries with backoff.
+/// Cheap to clone.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    settings: HttpSettings,
+    timeout: Duration,
+    provider_timeouts: HashMap<Provider, Duration>,
+}
+
+impl HttpClient {
+    /// Build from `[http]` plus per-provider `timeout_secs` overrides.
+    pub fn from_config(config: &AppConfig) -> Result<Self> {
+        let mut http = Self::new(&config.http)?;
+        http.provider_timeouts = config
+            .providers
+            .iter()
+            .filter_map(|p| {
+                let provider = Provider::from_id(&p.id)?;
+                Some((provider, Duration::from_secs(p.timeout_secs?)))
+            })
+            .collect();
+        Ok(http)
+    }
+
+    pub fn new(settings: &HttpSettings) -> Result<Self> {
+        let mut builder = Self::base_builder(settings)?;
+        if let Some(proxy) = &settings.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .with_context(|| format!("Invalid proxy URL: {}", proxy))?
+                .no_proxy(reqwest::NoProxy::from_env());
+            builder = builder.proxy(proxy);
+        }
+        let client = builder.build().context("Failed to build HTTP client")?;
+        Ok(Self {
+            client,
+            settings: settings.clone(),
+            timeout: Duration::from_secs(settings.timeout_secs),
+            provider_timeouts: HashMap::new(),
+        })
+    }
+
+    fn base_builder(settings: &HttpSettings) -> Result<reqwest::ClientBuilder> {
+        let mut builder = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(Duration::from_secs(settings.connect_timeout_secs));
+        if let Some(path) = &settings.ca_bundle {
+            let pem = std::fs::read(path)
+                .with_context(|| format!("Failed to read CA bundle {}", path.display()))?;
+            let certs = reqwest::Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("Invalid CA bundle {}", path.display()))?;
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        Ok(builder)
+    }
+
+    /// The same client with `provider`'s timeout override applied, if any.
+    pub fn for_provider(&self, provider: Provider) -> Self {
+        let mut http = self.clone();
+        if let Some(timeout) = self.provider_timeouts.get(&provider) {
+            http.timeout = *timeout;
+        }
+        http
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url).timeout(self.timeout)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url).timeout(self.timeout)
+    }
+
+    /// Builder for a separate client talking to a loopback service: same
+    /// User-Agent and timeouts, but never routed through a proxy.
+    pub fn local_builder(&self) -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(Duration::from_secs(self.settings.connect_timeout_secs))
+            .timeout(self.timeout)
+            .no_proxy()
+    }
+
+    /// Send `request`, retrying 429/5xx responses and connection failures
+    /// with jittered exponential backoff, honoring `Retry-After`. The last
+    /// response is returned as is once retries are exhausted. Only
+    /// idempotent methods are retried; a POST is sent once.
+    pub async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let idempotent = request
+            .try_clone()
+            .and_then(|r| r.build().ok())
+            .is_some_and(|r| is_idempotent(r.method()));
+        if !idempotent {
+            return request.send().await;
+        }
+        self.send_repeatable(request).await
+    }
+
+    /// `send` with retries whatever the method, for a POST that only reads
+    /// (a query or status API) and is safe to repeat.
+    pub async fn send_repeatable(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let policy = self.settings.retry_policy();
+        let mut attempt = 0;
+        loop {
+            // Streaming bodies cannot be replayed; send those once
+            let Some(current) = request.try_clone() else {
+                return request.send().await;
+            };
+            let delay = match current.send().await {
+                Ok(response) if is_retryable(response.status()) => {
+                    let retry_after = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| parse_retry_after(v, Utc::now()));
+                    match policy.delay(attempt, retry_after, jitter()) {
+                        Some(delay) => delay,
+                        None => return Ok(response),
+                    }
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if e.is_connect() => match policy.delay(attempt, None, jitter()) {
+                    Some(delay) => delay,
+                    None => return Err(e),
+                },
+                Err(e) => return Err(e),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn policy() -> RetryPolicy {
+        HttpSettings::default().retry_policy()
+    }
+
+    #[test]
+    fn backoff_grows_with_jitter_and_caps() {
+        let p = policy();
+        assert_eq!(p.delay(0, None, 0.0), Some(Duration::from_millis(250)));
+        assert_eq!(p.delay(0, None, 1.0), Some(Duration::from_millis(500)));
+        assert_eq!(p.delay(1, None, 0.0), Some(Duration::from_millis(500)));
+        assert_eq!(p.delay(2, None, 0.5), None);
+
+        let long = RetryPolicy {
+            retries: 10,
+            ..p
+        };
+        assert_eq!(long.delay(9, None, 1.0), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn retry_after_is_honored_up_to_max() {
+        let p = policy();
+        assert_eq!(
+            p.delay(0, Some(Duration::from_secs(3)), 0.5),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(p.delay(0, Some(Duration::from_secs(60)), 0.5), None);
+    }
+
+    #[test]
+    fn parse_retry_after_formats() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 7, 28, 0).unwrap();
+        assert_eq!(parse_retry_after("5", now), Some(Duration::from_secs(5)));
+        assert_eq!(
+            parse_retry_after("Sun, 18 Oct 2026 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 18 Oct 2026 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable(StatusCode::NOT_IMPLEMENTED));
+    }
+
+    #[test]
+    fn only_idempotent_methods_retry_by_default() {
+        assert!(is_idempotent(&reqwest::Method::GET));
+        assert!(is_idempotent(&reqwest::Method::DELETE));
+        assert!(!is_idempotent(&reqwest::Method::POST));
+        assert!(!is_idempotent(&reqwest::Method::PATCH));
+    }
+
+    /// Attempts a POST and a GET make against a server that always answers
+    /// 503, with and without the opt-in.
+    #[tokio::test]
+    async fn post_is_sent_once_unless_repeatable() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket
+                    .write_all(
+                        b"HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\n\
+                          Content-Length: 0\r\nConnection: close\r\n\r\n",
+                    )
+                    .await;
+            }
+        });
+
+        let http = HttpClient::new(&HttpSettings::default()).unwrap();
+        let mut attempts = Vec::new();
+        for (post, repeatable) in [(true, false), (true, true), (false, false)] {
+            hits.store(0, Ordering::SeqCst);
+            let request = if post {
+                http.post(&url).body("{}")
+            } else {
+                http.get(&url)
+            };
+            let response = if repeatable {
+                http.send_repeatable(request).await
+            } else {
+                http.send(request).await
+            };
+            assert_eq!(response.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+            attempts.push(hits.load(Ordering::SeqCst));
+        }
+        let retries = HttpSettings::default().retries as usize;
+        assert_eq!(attempts, [1, retries + 1, retries + 1]);
+    }
+
+    #[test]
+    fn jitter_in_unit_range() {
+        for _ in 0..100 {
+            let j = jitter();
+            assert!((0.0..1.0).contains(&j));
+        }
+    }
+
+    #[test]
+    fn parse_http_settings_and_provider_timeout() {
+        let toml_str = r#"
+[http]
+timeout_secs = 30
+retries = 0
+proxy = "http://proxy.corp:3128"
+
+[[providers]]
+id = "codex"
+timeout_secs = 60
+"#;
+        let config: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.http.timeout_secs, 30);
+        assert_eq!(config.http.retries, 0);
+        assert_eq!(config.http.backoff_ms, 500);
+
+        let http = HttpClient::from_config(&config).unwrap();
+        assert_eq!(http.timeout(), Duration::from_secs(30));
+        assert_eq!(
+            http.for_provider(Provider::Codex).timeout(),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            http.for_provider(Provider::Claude).timeout(),
+            Duration::from_secs(30)
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_settings() {
+        let mut settings = HttpSettings {
+            proxy: Some("socks5://localhost:1080".to_string()),
+            ..Default::default()
+        };
+        assert!(settings.validate().unwrap_err().contains("proxy"));
+        settings.proxy = None;
+        settings.ca_bundle = Some(PathBuf::from("/nonexistent/ca.pem"));
+        assert!(settings.validate().unwrap_err().contains("ca_bundle"));
+        settings.ca_bundle = None;
+        settings.timeout_secs = 0;
+        assert!(settings.validate().is_err());
+    }
+}
@@ -1,8 +1,17 @@
 pub mod auth;
+pub mod aws;
+pub mod budget;
 pub mod config;
+pub mod cookies;
 pub mod cost;
+pub mod currency;
+pub mod daemon;
+pub mod doctor;
 pub mod formatter;
+pub mod http;
 pub mod models;
 pub mod process;
 pub mod providers;
 pub mod status;
+pub mod usage_cache;
+pub mod value;
@@ -1,7 +1,14 @@
 use chrono::NaiveDate;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::core::providers::Provider;
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
 pub struct TokenCostSnapshot {
     pub model: String,
     pub input_tokens: u64,
@@ -13,20 +20,66 @@ pub struct TokenCostSnapshot {
     pub cache_read_cost: f64,
     pub cache_creation_cost: f64,
     pub total_cost: f64,
+    /// False when no pricing entry matched the model; costs are then 0 and
+    /// the totals understate actual spend.
+    #[serde(default = "default_true")]
+    pub priced: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
 pub struct DailyReport {
     pub date: NaiveDate,
     pub costs: Vec<TokenCostSnapshot>,
     pub total_cost: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
 pub struct CostSummary {
     pub total_cost: f64,
     pub today_cost: f64,
     pub days: u32,
     pub by_model: Vec<TokenCostSnapshot>,
     pub daily: Vec<DailyReport>,
+    /// Models seen in the logs without a pricing entry (not included in totals).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unpriced_models: Vec<String>,
+    /// Totals in the configured display currency (costs are computed in USD).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converted: Option<ConvertedCost>,
+    /// Spend per workspace or project, from organization reports.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub by_workspace: Vec<GroupCost>,
+    /// Spend per API key, from organization reports.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub by_api_key: Vec<GroupCost>,
+}
+
+/// Tokens and spend of one workspace, project or API key.
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
+pub struct GroupCost {
+    pub id: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_cost: f64,
+}
+
+/// Cost totals converted to `settings.display_currency`.
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
+pub struct ConvertedCost {
+    pub currency: String,
+    /// Multiplier applied to the USD amounts
+    pub rate: f64,
+    pub total_cost: f64,
+    pub today_cost: f64,
+}
+
+/// Token volume of a model seen in session logs that has no pricing entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnpricedModel {
+    pub provider: Provider,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
 }
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
 pub struct CreditsSnapshot {
-    /// Remaining credit balance in dollars
+    /// Remaining credit balance, in `currency` (dollars by default) or `unit`
     pub remaining: f64,
     /// Whether the account has any credits
     pub has_credits: bool,
@@ -17,7 +18,34 @@ pub struct CreditsSnapshot {
     /// Currency code (e.g., "usd")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency: Option<String>,
+    /// Unit of credits that are not money (e.g. "credits"). Such amounts
+    /// are never converted, shown as money or counted against budgets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
     /// Billing period (e.g., "Monthly")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub period: Option<String>,
+    /// Amounts in the configured display currency, when it differs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converted: Option<ConvertedCredits>,
+}
+
+impl CreditsSnapshot {
+    /// Whether the amounts are money rather than a vendor's own units.
+    pub fn is_monetary(&self) -> bool {
+        self.unit.is_none()
+    }
+}
+
+/// Credit amounts converted to `settings.display_currency`.
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
+pub struct ConvertedCredits {
+    pub currency: String,
+    /// Multiplier applied to the original amounts
+    pub rate: f64,
+    pub remaining: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<f64>,
 }
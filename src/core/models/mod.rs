@@ -1,4 +1,5 @@
 pub mod cost;
 pub mod credits;
+pub mod report;
 pub mod status;
 pub mod usage;
@@ -0,0 +1,381 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::core::budget::BudgetStatus;
+use crate::core::models::cost::CostSummary;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::models::status::StatusInfo;
+use crate::core::models::usage::{EndpointUsage, LocalModels, ProviderIdentity, RateWindow, UsageSnapshot};
+use crate::core::providers::error::ErrorReport;
+use crate::core::providers::Provider;
+
+/// Version of the `ait usage` JSON envelope. Bump it once per release that
+/// changes the output shape and publish the new schema as
+/// `schema/usage-report.v<N>.json`.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Top-level JSON document printed by `ait usage --json`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UsageReport {
+    /// Version of this document's shape
+    pub schema_version: u32,
+    pub generated_at: DateTime<Utc>,
+    /// Providers that were fetched successfully, in the requested order
+    pub providers: Vec<ProviderReport>,
+    /// Providers that failed or did not answer within `--timeout`
+    pub errors: Vec<ProviderFailure>,
+    /// Token costs per provider, from local session logs or organization
+    /// cost reports
+    pub cost: Vec<ProviderCost>,
+    /// Configured `[[budgets]]` evaluated for the current period
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub budgets: Vec<BudgetStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderReport {
+    pub provider: Provider,
+    /// How the data was obtained ("oauth", "cli", "api")
+    pub source: String,
+    /// Primary rate window (usually session/5-hour)
+    pub primary: Option<RateWindow>,
+    /// Secondary rate window (usually weekly/7-day)
+    pub secondary: Option<RateWindow>,
+    /// Tertiary rate window (model-specific, e.g. Sonnet limit)
+    pub tertiary: Option<RateWindow>,
+    pub identity: Option<ProviderIdentity>,
+    /// Models on a local inference server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<LocalModels>,
+    /// Per-endpoint results when several `[[generic_http]]` endpoints are
+    /// configured
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<EndpointUsage>,
+    pub credits: Option<CreditsSnapshot>,
+    /// Provider health, with `--status`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<StatusInfo>,
+    /// When the usage data was fetched from the provider
+    pub updated_at: DateTime<Utc>,
+    /// Whether the data was served from the response cache
+    pub cached: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderFailure {
+    pub provider: Provider,
+    pub error: ErrorReport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProviderCost {
+    pub provider: Provider,
+    #[serde(flatten)]
+    pub summary: CostSummary,
+}
+
+impl ProviderReport {
+    pub fn new(
+        usage: UsageSnapshot,
+        credits: Option<CreditsSnapshot>,
+        status: Option<StatusInfo>,
+        updated_at: DateTime<Utc>,
+        cached: bool,
+    ) -> Self {
+        Self {
+            provider: usage.provider,
+            source: usage.source,
+            primary: usage.primary,
+            secondary: usage.secondary,
+            tertiary: usage.tertiary,
+            identity: usage.identity,
+            local: usage.local,
+            endpoints: usage.endpoints,
+            credits,
+            status,
+            updated_at,
+            cached,
+        }
+    }
+
+    /// The usage part as a snapshot, for rendering.
+    pub fn usage(&self) -> UsageSnapshot {
+        UsageSnapshot {
+            provider: self.provider,
+            source: self.source.clone(),
+            primary: self.primary.clone(),
+            secondary: self.secondary.clone(),
+            tertiary: self.tertiary.clone(),
+            identity: self.identity.clone(),
+            local: self.local.clone(),
+            endpoints: self.endpoints.clone(),
+        }
+    }
+}
+
+impl UsageReport {
+    pub fn new(generated_at: DateTime<Utc>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            generated_at,
+            providers: Vec::new(),
+            errors: Vec::new(),
+            cost: Vec::new(),
+            budgets: Vec::new(),
+        }
+    }
+}
+
+/// JSON Schema of `UsageReport`, as printed by `ait schema`.
+pub fn usage_report_schema() -> serde_json::Value {
+    let mut schema = serde_json::to_value(schemars::schema_for!(UsageReport))
+        .expect("generated schema is valid JSON");
+    schema["title"] = format!("ait usage report v{}", SCHEMA_VERSION).into();
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::cost::TokenCostSnapshot;
+    use crate::core::models::status::StatusIndicator;
+    use crate::core::providers::error::ErrorKind;
+    use chrono::{NaiveDate, TimeZone};
+    use std::path::PathBuf;
+
+    // Snapshots are compared as parsed JSON, so formatting is free to change.
+    // A failing test means the output shape changed: if the current version
+    // was released, bump SCHEMA_VERSION and add new snapshot files instead of
+    // editing published ones; otherwise update the unreleased snapshots.
+    fn snapshot(name: &str) -> serde_json::Value {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("schema").join(name);
+        let content = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "missing snapshot {} for schema_version {}",
+                path.display(),
+                SCHEMA_VERSION
+            )
+        });
+        serde_json::from_str(&content).unwrap()
+    }
+
+    fn sample_report() -> UsageReport {
+        let at = Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap();
+        let mut report = UsageReport::new(at);
+        report.providers.push(ProviderReport::new(
+            UsageSnapshot {
+                provider: Provider::Claude,
+                source: "oauth".into(),
+                primary: Some(RateWindow {
+                    used_percent: 42.0,
+                    window_minutes: 300,
+                    resets_at: Some(Utc.with_ymd_and_hms(2026, 1, 15, 14, 0, 0).unwrap()),
+                    reset_description: None,
+                }),
+                secondary: None,
+                tertiary: None,
+                identity: Some(ProviderIdentity {
+                    email: Some("dev@example.com".into()),
+                    organization: None,
+                    plan: Some("max".into()),
+                }),
+                local: None,
+                endpoints: Vec::new(),
+            },
+            None,
+            Some(StatusInfo {
+                indicator: StatusIndicator::Operational,
+                description: Some("All Systems Operational".into()),
+            }),
+            at,
+            false,
+        ));
+        report.providers.push(ProviderReport::new(
+            UsageSnapshot {
+                provider: Provider::OpenRouter,
+                source: "api".into(),
+                primary: None,
+                secondary: None,
+                tertiary: None,
+                identity: None,
+                local: None,
+                endpoints: Vec::new(),
+            },
+            Some(CreditsSnapshot {
+                remaining: 7.5,
+                has_credits: true,
+                unlimited: false,
+                used: Some(2.5),
+                limit: Some(10.0),
+                currency: None,
+                unit: None,
+                period: None,
+                converted: None,
+            }),
+            None,
+            at - chrono::Duration::seconds(40),
+            true,
+        ));
+        report.providers.push(ProviderReport::new(
+            UsageSnapshot {
+                provider: Provider::Ollama,
+                source: "api".into(),
+                primary: None,
+                secondary: None,
+                tertiary: None,
+                identity: None,
+                local: Some(LocalModels {
+                    host: "http://127.0.0.1:11434".into(),
+                    loaded: vec![crate::core::models::usage::LoadedModel {
+                        name: "llama3.2:3b".into(),
+                        vram_bytes: 3_400_000_000,
+                        ram_bytes: 0,
+                        expires_at: Some(Utc.with_ymd_and_hms(2026, 1, 15, 12, 4, 0).unwrap()),
+                    }],
+                    installed: 3,
+                    installed_bytes: 22_274_000_000,
+                }),
+                endpoints: Vec::new(),
+            },
+            None,
+            None,
+            at,
+            false,
+        ));
+        report.providers.push(ProviderReport::new(
+            UsageSnapshot {
+                provider: Provider::GenericHttp,
+                source: "api".into(),
+                primary: None,
+                secondary: None,
+                tertiary: None,
+                identity: None,
+                local: None,
+                endpoints: vec![
+                    EndpointUsage {
+                        name: "gateway".into(),
+                        primary: Some(RateWindow {
+                            used_percent: 25.0,
+                            window_minutes: 1,
+                            resets_at: None,
+                            reset_description: Some("12000 of 48000 tokens/min".into()),
+                        }),
+                        secondary: None,
+                        tertiary: None,
+                        identity: None,
+                        credits: None,
+                        error: None,
+                    },
+                    EndpointUsage {
+                        name: "quota".into(),
+                        primary: None,
+                        secondary: None,
+                        tertiary: None,
+                        identity: None,
+                        credits: None,
+                        error: Some("HTTP 503: upstream unavailable".into()),
+                    },
+                ],
+            },
+            None,
+            None,
+            at,
+            false,
+        ));
+        report.errors.push(ProviderFailure {
+            provider: Provider::Warp,
+            error: ErrorReport {
+                kind: ErrorKind::AuthMissing,
+                message: "WARP_TOKEN env var not set".into(),
+                status: None,
+            },
+        });
+        let cost = TokenCostSnapshot {
+            model: "claude-sonnet-4-5".into(),
+            input_tokens: 1000,
+            output_tokens: 200,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            input_cost: 0.003,
+            output_cost: 0.003,
+            cache_read_cost: 0.0,
+            cache_creation_cost: 0.0,
+            total_cost: 0.006,
+            priced: true,
+        };
+        report.cost.push(ProviderCost {
+            provider: Provider::Claude,
+            summary: CostSummary {
+                total_cost: 0.006,
+                today_cost: 0.006,
+                days: 30,
+                by_model: vec![cost.clone()],
+                daily: vec![crate::core::models::cost::DailyReport {
+                    date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+                    costs: vec![cost],
+                    total_cost: 0.006,
+                }],
+                unpriced_models: vec![],
+                converted: None,
+                by_workspace: Vec::new(),
+                by_api_key: Vec::new(),
+            },
+        });
+        report.budgets.push(BudgetStatus {
+            name: "claude".into(),
+            amount: 100.0,
+            currency: "USD".into(),
+            period: crate::core::budget::BudgetPeriod::Monthly,
+            provider: Some("claude".into()),
+            model: None,
+            project: None,
+            source: crate::core::budget::SpendSource::Cost,
+            period_start: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            resets_on: NaiveDate::from_ymd_opt(2026, 2, 1).unwrap(),
+            spent: Some(20.0),
+            used_percent: Some(20.0),
+            projected: Some(43.0),
+            exceeded: false,
+            error: None,
+        });
+        report
+    }
+
+    #[test]
+    fn schema_matches_published_snapshot() {
+        let name = format!("usage-report.v{}.json", SCHEMA_VERSION);
+        assert_eq!(
+            usage_report_schema(),
+            snapshot(&name),
+            "JSON schema changed without a schema_version bump"
+        );
+    }
+
+    #[test]
+    fn output_matches_snapshot() {
+        let name = format!("examples/usage-report.v{}.json", SCHEMA_VERSION);
+        let output = serde_json::to_value(sample_report()).unwrap();
+        assert_eq!(
+            output,
+            snapshot(&name),
+            "JSON output changed without a schema_version bump"
+        );
+    }
+
+    #[test]
+    fn envelope_carries_version() {
+        let json = serde_json::to_value(sample_report()).unwrap();
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["generated_at"], "2026-01-15T12:00:00Z");
+        assert_eq!(json["providers"][1]["provider"], "open_router");
+        assert!(json["providers"][1].get("status").is_none());
+        assert_eq!(json["providers"][1]["updated_at"], "2026-01-15T11:59:20Z");
+        assert_eq!(json["providers"][1]["cached"], true);
+        assert_eq!(json["errors"][0]["error"]["kind"], "auth_missing");
+        assert_eq!(json["cost"][0]["provider"], "claude");
+        assert_eq!(json["cost"][0]["total_cost"], 0.006);
+        assert_eq!(json["budgets"][0]["source"], "cost");
+        assert_eq!(json["budgets"][0]["resets_on"], "2026-02-01");
+    }
+}
@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use crate::core::models::credits::CreditsSnapshot;
 use crate::core::providers::Provider;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
 pub struct RateWindow {
     /// Percentage of the rate limit that has been used (0.0 - 100.0)
     pub used_percent: f64,
@@ -14,14 +16,14 @@ pub struct RateWindow {
     pub reset_description: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
 pub struct ProviderIdentity {
     pub email: Option<String>,
     pub organization: Option<String>,
     pub plan: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
 pub struct UsageSnapshot {
     pub provider: Provider,
     pub source: String, // "oauth", "cli", "api"
@@ -33,4 +35,48 @@ pub struct UsageSnapshot {
     pub tertiary: Option<RateWindow>,
     /// Provider identity (email, plan, org)
     pub identity: Option<ProviderIdentity>,
+    /// Models on a local inference server (e.g. Ollama)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<LocalModels>,
+    /// Per-endpoint results when several `[[generic_http]]` endpoints are
+    /// configured
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<EndpointUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
+pub struct EndpointUsage {
+    /// The endpoint's `name` in config.toml
+    pub name: String,
+    pub primary: Option<RateWindow>,
+    pub secondary: Option<RateWindow>,
+    pub tertiary: Option<RateWindow>,
+    pub identity: Option<ProviderIdentity>,
+    pub credits: Option<CreditsSnapshot>,
+    /// Why this endpoint could not be read; the others are still reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
+pub struct LocalModels {
+    /// Server the models were read from
+    pub host: String,
+    /// Models currently loaded in memory
+    pub loaded: Vec<LoadedModel>,
+    /// Number of models available on disk
+    pub installed: u32,
+    /// Total size of the installed models in bytes
+    pub installed_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema, Deserialize)]
+pub struct LoadedModel {
+    pub name: String,
+    /// Bytes held in GPU memory
+    pub vram_bytes: u64,
+    /// Bytes held in system memory (offloaded to the CPU)
+    pub ram_bytes: u64,
+    /// When the model is unloaded if idle; `None` when kept loaded
+    pub expires_at: Option<DateTime<Utc>>,
 }
@@ -1,8 +1,13 @@
 use anyhow::Result;
 
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 
 /// Amp usage provider (stub).
-pub async fn fetch() -> Result<FetchResult> {
-    anyhow::bail!("Amp requires browser cookies (not yet supported)")
+pub async fn fetch(_http: &HttpClient) -> Result<FetchResult> {
+    Err(ProviderError::Unsupported(
+        "Amp requires browser cookies (not yet supported)".into(),
+    )
+    .into())
 }
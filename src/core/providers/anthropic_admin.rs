@@ -0,0 +1,411 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::core::cost::org::{self, CostLine, CostPart, UsageLine};
+use crate::core::http::HttpClient;
+use crate::core::models::usage::{ProviderIdentity, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::FetchResult;
+use crate::core::providers::Provider;
+
+const API_URL: &str = "https://api.anthropic.com/v1/organizations";
+const API_VERSION: &str = "2023-06-01";
+
+/// Admin API key (`sk-ant-admin...`), created by an organization admin.
+const ADMIN_KEY_ENV: &str = "ANTHROPIC_ADMIN_KEY";
+
+/// Days of reports to summarize, matching the session-log scan.
+const DAYS: u32 = 30;
+
+/// One page of a usage or cost report.
+#[derive(Deserialize)]
+struct ReportPage<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<Bucket<T>>,
+    #[serde(default)]
+    has_more: bool,
+    next_page: Option<String>,
+}
+
+/// A daily bucket of a report.
+#[derive(Deserialize)]
+struct Bucket<T> {
+    starting_at: DateTime<Utc>,
+    #[serde(default = "Vec::new")]
+    results: Vec<T>,
+}
+
+#[derive(Deserialize, Default)]
+struct CacheCreation {
+    #[serde(default)]
+    ephemeral_5m_input_tokens: u64,
+    #[serde(default)]
+    ephemeral_1h_input_tokens: u64,
+}
+
+/// Message tokens of one model, API key and workspace.
+#[derive(Deserialize)]
+struct UsageResult {
+    model: Option<String>,
+    api_key_id: Option<String>,
+    workspace_id: Option<String>,
+    #[serde(default)]
+    uncached_input_tokens: u64,
+    #[serde(default)]
+    cache_creation: CacheCreation,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+}
+
+/// A charge of one workspace and line item. `amount` is a decimal string
+/// in cents.
+#[derive(Deserialize)]
+struct CostResult {
+    amount: String,
+    workspace_id: Option<String>,
+    description: Option<String>,
+    cost_type: Option<String>,
+    model: Option<String>,
+    token_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Organization {
+    name: Option<String>,
+}
+
+fn load_key() -> Result<String> {
+    std::env::var(ADMIN_KEY_ENV)
+        .ok()
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| {
+            ProviderError::AuthMissing(format!(
+                "{} not set — create an Admin API key in the Anthropic Console",
+                ADMIN_KEY_ENV
+            ))
+            .into()
+        })
+}
+
+async fn get<T: DeserializeOwned>(
+    http: &HttpClient,
+    key: &str,
+    path: &str,
+    query: &[(&str, String)],
+) -> Result<T> {
+    let request = http
+        .get(&format!("{}/{}", API_URL, path))
+        .header("x-api-key", key)
+        .header("anthropic-version", API_VERSION)
+        .query(query);
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to Anthropic Admin API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProviderError::unauthorized(
+            "Unauthorized — check ANTHROPIC_ADMIN_KEY (an Admin API key, not a regular API key)",
+        )
+        .into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+    response
+        .json()
+        .await
+        .context("Failed to parse Anthropic Admin API response")
+}
+
+/// All daily buckets of a report, following `next_page`.
+async fn get_report<T: DeserializeOwned>(
+    http: &HttpClient,
+    key: &str,
+    path: &str,
+    query: Vec<(&str, String)>,
+) -> Result<Vec<Bucket<T>>> {
+    let mut buckets = Vec::new();
+    let mut page: Option<String> = None;
+    loop {
+        let mut query = query.clone();
+        query.extend(page.take().map(|p| ("page", p)));
+        let data: ReportPage<T> = get(http, key, path, &query).await?;
+        buckets.extend(data.data);
+        match data.next_page {
+            Some(next) if data.has_more => page = Some(next),
+            _ => return Ok(buckets),
+        }
+    }
+}
+
+fn cost_part(token_type: Option<&str>) -> CostPart {
+    match token_type {
+        Some("uncached_input_tokens") => CostPart::Input,
+        Some("output_tokens") => CostPart::Output,
+        Some("cache_read_input_tokens") => CostPart::CacheRead,
+        Some(t) if t.starts_with("cache_creation") => CostPart::CacheCreation,
+        _ => CostPart::Other,
+    }
+}
+
+/// Charges in USD. Web search and code execution have no model and are
+/// listed under their description.
+fn cost_lines(buckets: &[Bucket<CostResult>]) -> Vec<CostLine> {
+    buckets
+        .iter()
+        .flat_map(|bucket| {
+            bucket.results.iter().filter_map(move |r| {
+                let cents: f64 = r.amount.parse().ok()?;
+                let model = r
+                    .model
+                    .clone()
+                    .or_else(|| r.description.clone())
+                    .or_else(|| r.cost_type.clone())
+                    .unwrap_or_else(|| "other".to_string());
+                Some(CostLine {
+                    date: bucket.starting_at.date_naive(),
+                    model,
+                    part: cost_part(r.token_type.as_deref()),
+                    amount: cents / 100.0,
+                    workspace: workspace(&r.workspace_id),
+                })
+            })
+        })
+        .collect()
+}
+
+/// Usage without an API key comes from the Console Workbench.
+fn usage_lines(buckets: &[Bucket<UsageResult>]) -> Vec<UsageLine> {
+    buckets
+        .iter()
+        .flat_map(|bucket| {
+            bucket.results.iter().map(move |r| UsageLine {
+                date: bucket.starting_at.date_naive(),
+                model: r.model.clone().unwrap_or_else(|| "unknown".to_string()),
+                api_key: r.api_key_id.clone().unwrap_or_else(|| "console".to_string()),
+                workspace: workspace(&r.workspace_id),
+                input_tokens: r.uncached_input_tokens,
+                output_tokens: r.output_tokens,
+                cache_read_tokens: r.cache_read_input_tokens,
+                cache_creation_tokens: r.cache_creation.ephemeral_5m_input_tokens
+                    + r.cache_creation.ephemeral_1h_input_tokens,
+            })
+        })
+        .collect()
+}
+
+/// The default workspace has no id.
+fn workspace(id: &Option<String>) -> String {
+    id.clone().unwrap_or_else(|| "default".to_string())
+}
+
+fn report_start(today: NaiveDate) -> String {
+    let start = today - Duration::days(DAYS as i64 - 1);
+    format!("{}T00:00:00Z", start)
+}
+
+/// Fetch the organization's message usage and costs for the last 30 days.
+/// The Admin API exposes no rate limits, so no windows are reported.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let key = load_key()?;
+    let today = Utc::now().date_naive();
+    let starting_at = report_start(today);
+
+    let usage: Vec<Bucket<UsageResult>> = get_report(
+        http,
+        &key,
+        "usage_report/messages",
+        vec![
+            ("starting_at", starting_at.clone()),
+            ("bucket_width", "1d".to_string()),
+            ("limit", DAYS.to_string()),
+            ("group_by[]", "model".to_string()),
+            ("group_by[]", "api_key_id".to_string()),
+            ("group_by[]", "workspace_id".to_string()),
+        ],
+    )
+    .await?;
+    let costs: Vec<Bucket<CostResult>> = get_report(
+        http,
+        &key,
+        "cost_report",
+        vec![
+            ("starting_at", starting_at),
+            ("limit", DAYS.to_string()),
+            ("group_by[]", "workspace_id".to_string()),
+            ("group_by[]", "description".to_string()),
+        ],
+    )
+    .await?;
+    let summary = org::build_summary(&cost_lines(&costs), &usage_lines(&usage), DAYS, today);
+
+    let identity = get::<Organization>(http, &key, "me", &[])
+        .await
+        .ok()
+        .and_then(|o| o.name)
+        .map(|name| ProviderIdentity {
+            email: None,
+            organization: Some(name),
+            plan: None,
+        });
+
+    let snapshot = UsageSnapshot {
+        provider: Provider::AnthropicAdmin,
+        source: "api".to_string(),
+        primary: None,
+        secondary: None,
+        tertiary: None,
+        identity,
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage: snapshot,
+        credits: None,
+        cost: Some(summary),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USAGE_REPORT: &str = r#"{
+        "data": [{
+            "starting_at": "2026-10-17T00:00:00Z",
+            "ending_at": "2026-10-18T00:00:00Z",
+            "results": [
+                {
+                    "uncached_input_tokens": 120000,
+                    "cache_creation": {"ephemeral_1h_input_tokens": 1000, "ephemeral_5m_input_tokens": 4000},
+                    "cache_read_input_tokens": 300000,
+                    "output_tokens": 20000,
+                    "server_tool_use": {"web_search_requests": 2},
+                    "api_key_id": "apikey_01Rj2N8SVvo6BePZj99NhmiT",
+                    "workspace_id": "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ",
+                    "model": "claude-sonnet-4-5-20250929",
+                    "service_tier": "standard",
+                    "context_window": "0-200k"
+                },
+                {
+                    "uncached_input_tokens": 5000,
+                    "cache_read_input_tokens": 0,
+                    "output_tokens": 800,
+                    "api_key_id": null,
+                    "workspace_id": null,
+                    "model": "claude-haiku-4-5"
+                }
+            ]
+        }],
+        "has_more": false,
+        "next_page": null
+    }"#;
+
+    const COST_REPORT: &str = r#"{
+        "data": [{
+            "starting_at": "2026-10-17T00:00:00Z",
+            "ending_at": "2026-10-18T00:00:00Z",
+            "results": [
+                {
+                    "currency": "USD",
+                    "amount": "36.00",
+                    "workspace_id": "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ",
+                    "description": "Claude Sonnet 4.5 Usage - Input Tokens",
+                    "cost_type": "tokens",
+                    "context_window": "0-200k",
+                    "model": "claude-sonnet-4-5-20250929",
+                    "service_tier": "standard",
+                    "token_type": "uncached_input_tokens"
+                },
+                {
+                    "currency": "USD",
+                    "amount": "30.00",
+                    "workspace_id": "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ",
+                    "description": "Claude Sonnet 4.5 Usage - Output Tokens",
+                    "cost_type": "tokens",
+                    "model": "claude-sonnet-4-5-20250929",
+                    "token_type": "output_tokens"
+                },
+                {
+                    "currency": "USD",
+                    "amount": "2.0",
+                    "workspace_id": null,
+                    "description": "Web Search",
+                    "cost_type": "web_search",
+                    "model": null,
+                    "token_type": null
+                }
+            ]
+        }],
+        "has_more": true,
+        "next_page": "page_MjAyNi0xMC0xOA=="
+    }"#;
+
+    #[test]
+    fn parses_usage_report() {
+        let page: ReportPage<UsageResult> = serde_json::from_str(USAGE_REPORT).unwrap();
+        assert!(!page.has_more);
+        let lines = usage_lines(&page.data);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].date, NaiveDate::from_ymd_opt(2026, 10, 17).unwrap());
+        assert_eq!(lines[0].model, "claude-sonnet-4-5-20250929");
+        assert_eq!(lines[0].input_tokens, 120_000);
+        assert_eq!(lines[0].cache_creation_tokens, 5_000);
+        assert_eq!(lines[0].cache_read_tokens, 300_000);
+        // Workbench usage in the default workspace
+        assert_eq!(lines[1].api_key, "console");
+        assert_eq!(lines[1].workspace, "default");
+        assert_eq!(lines[1].cache_creation_tokens, 0);
+    }
+
+    #[test]
+    fn parses_cost_report_in_cents() {
+        let page: ReportPage<CostResult> = serde_json::from_str(COST_REPORT).unwrap();
+        assert!(page.has_more);
+        assert_eq!(page.next_page.as_deref(), Some("page_MjAyNi0xMC0xOA=="));
+        let lines = cost_lines(&page.data);
+        assert_eq!(lines.len(), 3);
+        assert!((lines[0].amount - 0.36).abs() < 1e-9);
+        assert_eq!(lines[0].part, CostPart::Input);
+        assert_eq!(lines[1].part, CostPart::Output);
+        assert_eq!(lines[2].model, "Web Search");
+        assert_eq!(lines[2].part, CostPart::Other);
+        assert_eq!(lines[2].workspace, "default");
+    }
+
+    #[test]
+    fn reports_map_onto_cost_summary() {
+        let usage: ReportPage<UsageResult> = serde_json::from_str(USAGE_REPORT).unwrap();
+        let costs: ReportPage<CostResult> = serde_json::from_str(COST_REPORT).unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let summary = org::build_summary(
+            &cost_lines(&costs.data),
+            &usage_lines(&usage.data),
+            DAYS,
+            today,
+        );
+
+        assert!((summary.total_cost - 0.68).abs() < 1e-9);
+        assert!((summary.today_cost - 0.68).abs() < 1e-9);
+        let sonnet = &summary.by_model[0];
+        assert_eq!(sonnet.model, "claude-sonnet-4-5-20250929");
+        assert!((sonnet.output_cost - 0.30).abs() < 1e-9);
+        assert_eq!(sonnet.output_tokens, 20_000);
+        assert_eq!(summary.by_workspace[0].id, "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ");
+        assert_eq!(summary.by_api_key[0].id, "apikey_01Rj2N8SVvo6BePZj99NhmiT");
+    }
+
+    #[test]
+    fn report_starts_at_midnight_thirty_days_back() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        assert_eq!(report_start(today), "2026-09-19T00:00:00Z");
+    }
+}
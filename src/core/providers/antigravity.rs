@@ -2,17 +2,24 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 
 use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
 // --- Process discovery ---
 
-fn detect_language_server() -> Result<(String, u16)> {
-    let process_name = if cfg!(target_os = "macos") {
+/// Executable name of the Antigravity language server on this platform.
+pub fn process_name() -> &'static str {
+    if cfg!(target_os = "macos") {
         "language_server_macos"
     } else {
         "language_server_linux"
-    };
+    }
+}
+
+fn detect_language_server() -> Result<(String, u16)> {
+    let process_name = process_name();
 
     let output = std::process::Command::new("pgrep")
         .args(["-a", "language_server"])
@@ -20,7 +27,7 @@ fn detect_language_server() -> Result<(String, u16)> {
         .context("Failed to run pgrep")?;
 
     if !output.status.success() {
-        anyhow::bail!("Antigravity language server not running");
+        return Err(ProviderError::Network("Antigravity language server not running".into()).into());
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -42,7 +49,7 @@ fn detect_language_server() -> Result<(String, u16)> {
         return Ok((csrf_token, port));
     }
 
-    anyhow::bail!("Antigravity language server not running");
+    Err(ProviderError::Network("Antigravity language server not running".into()).into())
 }
 
 fn extract_arg(line: &str, flag: &str) -> Option<String> {
@@ -107,7 +114,7 @@ struct QuotaInfo {
 }
 
 /// Fetch usage data from the Antigravity language server.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let (csrf_token, port) = detect_language_server()?;
 
     let url = format!(
@@ -116,30 +123,33 @@ pub async fn fetch() -> Result<FetchResult> {
     );
 
     if !url.starts_with("https://127.0.0.1:") && !url.starts_with("https://[::1]:") {
-        anyhow::bail!(
+        return Err(ProviderError::Unsupported(format!(
             "Antigravity: refusing to disable TLS verification for non-localhost: {}",
             url
-        );
+        ))
+        .into());
     }
 
-    let client = reqwest::Client::builder()
+    let client = http
+        .local_builder()
         .danger_accept_invalid_certs(true)
         .build()
         .context("Failed to build HTTP client")?;
 
-    let response = client
+    let request = client
         .post(&url)
         .header("Content-Type", "application/json")
         .header("x-csrf-token", &csrf_token)
-        .body("{}")
-        .send()
+        .body("{}");
+    let response = http
+        .send_repeatable(request)
         .await
         .context("Failed to connect to Antigravity language server")?;
 
     let status = response.status();
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let data: UserStatusResponse = response
@@ -170,11 +180,14 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary: None,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
     Ok(FetchResult {
         usage,
         credits: None,
+        cost: None,
     })
 }
 
@@ -1,8 +1,250 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
 
-use crate::core::providers::fetch::FetchResult;
+use crate::core::config::AppConfig;
+use crate::core::http::HttpClient;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{validate_endpoint, FetchResult};
+use crate::core::providers::Provider;
 
-/// Augment usage provider (stub).
-pub async fn fetch() -> Result<FetchResult> {
-    anyhow::bail!("Augment requires browser cookies (not yet supported)")
+/// Session JSON the Augment CLI accepts in place of its session file.
+const SESSION_ENV: &str = "AUGMENT_SESSION_AUTH";
+
+/// Session written by `auggie login` (and shared with the IDE extensions).
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Session {
+    access_token: String,
+    #[serde(rename = "tenantURL")]
+    tenant_url: String,
+}
+
+/// Credit balance of the signed-in user for the current billing cycle.
+#[derive(Deserialize)]
+struct CreditInfo {
+    usage_units_remaining: Option<f64>,
+    usage_units_consumed_this_billing_cycle: Option<f64>,
+    current_billing_cycle_end_date_iso: Option<String>,
+}
+
+/// The `credentials` override for augment in config.toml, else
+/// `~/.augment/session.json`.
+pub fn session_path(config: &AppConfig) -> PathBuf {
+    config
+        .providers
+        .iter()
+        .find(|p| p.id == Provider::Augment.id())
+        .and_then(|p| p.credentials_path())
+        .unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("~"))
+                .join(".augment")
+                .join("session.json")
+        })
+}
+
+fn parse_session(json: &str, origin: &str) -> Result<Session> {
+    let session: Session = serde_json::from_str(json).map_err(|e| {
+        ProviderError::Parse(format!("Invalid Augment session in {}: {}", origin, e))
+    })?;
+    if session.access_token.is_empty() {
+        return Err(ProviderError::AuthMissing(format!("No access token in {}", origin)).into());
+    }
+    Ok(session)
+}
+
+/// Session from `AUGMENT_SESSION_AUTH`, else the session file.
+fn load_session(path: &Path) -> Result<Session> {
+    if let Ok(json) = std::env::var(SESSION_ENV) {
+        if !json.is_empty() {
+            return parse_session(&json, SESSION_ENV);
+        }
+    }
+    let content = std::fs::read_to_string(path).map_err(|_| {
+        ProviderError::AuthMissing(format!(
+            "No Augment session at {} — run `auggie login`",
+            path.display()
+        ))
+    })?;
+    parse_session(&content, &path.display().to_string())
+}
+
+/// Credits consumed this cycle out of consumed + remaining, resetting at
+/// the end of the billing cycle.
+fn credit_window(info: &CreditInfo) -> Option<RateWindow> {
+    let remaining = info.usage_units_remaining?;
+    let used = info.usage_units_consumed_this_billing_cycle.unwrap_or(0.0);
+    let total = used + remaining;
+    if total <= 0.0 {
+        return None;
+    }
+    Some(RateWindow {
+        used_percent: (used / total * 100.0).min(100.0),
+        window_minutes: 0,
+        resets_at: info
+            .current_billing_cycle_end_date_iso
+            .as_deref()
+            .and_then(|s| s.parse::<DateTime<Utc>>().ok()),
+        reset_description: Some(format!("{} of {} credits", used.round(), total.round())),
+    })
+}
+
+/// The balance in Augment credits, which are not money.
+fn parse_credits(info: &CreditInfo) -> Option<CreditsSnapshot> {
+    let remaining = info.usage_units_remaining?;
+    let used = info.usage_units_consumed_this_billing_cycle;
+    Some(CreditsSnapshot {
+        remaining,
+        has_credits: remaining > 0.0,
+        unlimited: false,
+        used,
+        limit: used.map(|u| u + remaining),
+        currency: None,
+        unit: Some("credits".to_string()),
+        period: Some("Billing cycle".to_string()),
+        converted: None,
+    })
+}
+
+/// Fetch the credit balance from the user's Augment tenant.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let config = AppConfig::load().unwrap_or_default();
+    let session = load_session(&session_path(&config))?;
+    let url = format!(
+        "{}/get-credit-info",
+        session.tenant_url.trim_end_matches('/')
+    );
+    validate_endpoint(&url, "Augment")?;
+
+    let request = http
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", session.access_token))
+        .header("Content-Type", "application/json")
+        .body("{}");
+    let response = http
+        .send_repeatable(request)
+        .await
+        .context("Failed to send request to Augment API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProviderError::unauthorized("Unauthorized — run `auggie login` again").into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    let info: CreditInfo = response
+        .json()
+        .await
+        .context("Failed to parse Augment credit response")?;
+
+    let usage = UsageSnapshot {
+        provider: Provider::Augment,
+        source: "cli".to_string(),
+        primary: credit_window(&info),
+        secondary: None,
+        tertiary: None,
+        identity: None,
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage,
+        credits: parse_credits(&info),
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ProviderConfig;
+
+    const CREDIT_INFO: &str = r#"{
+        "usage_units_remaining": 3420.0,
+        "usage_units_consumed_this_billing_cycle": 580.0,
+        "usage_units_pending": 0.0,
+        "usage_balance_status": "USAGE_BALANCE_STATUS_OK",
+        "current_billing_cycle_end_date_iso": "2026-11-01T00:00:00Z"
+    }"#;
+
+    #[test]
+    fn parses_credit_info() {
+        let info: CreditInfo = serde_json::from_str(CREDIT_INFO).unwrap();
+        let window = credit_window(&info).unwrap();
+        assert!((window.used_percent - 14.5).abs() < 1e-9);
+        assert_eq!(
+            window.resets_at,
+            Some("2026-11-01T00:00:00Z".parse().unwrap())
+        );
+        assert_eq!(
+            window.reset_description.as_deref(),
+            Some("580 of 4000 credits")
+        );
+
+        let credits = parse_credits(&info).unwrap();
+        assert_eq!(credits.remaining, 3420.0);
+        assert_eq!(credits.used, Some(580.0));
+        assert_eq!(credits.limit, Some(4000.0));
+        assert_eq!(credits.unit.as_deref(), Some("credits"));
+        assert!(!credits.is_monetary());
+        assert!(credits.has_credits);
+    }
+
+    #[test]
+    fn missing_balance_has_no_window() {
+        let info: CreditInfo = serde_json::from_str("{}").unwrap();
+        assert!(credit_window(&info).is_none());
+        assert!(parse_credits(&info).is_none());
+
+        let empty: CreditInfo = serde_json::from_str(r#"{"usage_units_remaining": 0.0}"#).unwrap();
+        assert!(credit_window(&empty).is_none());
+        assert!(!parse_credits(&empty).unwrap().has_credits);
+    }
+
+    #[test]
+    fn parses_session_file() {
+        let session = parse_session(
+            r#"{"accessToken": "tok", "tenantURL": "https://d7.api.augmentcode.com/", "scopes": ["email"]}"#,
+            "session.json",
+        )
+        .unwrap();
+        assert_eq!(session.access_token, "tok");
+        assert_eq!(session.tenant_url, "https://d7.api.augmentcode.com/");
+
+        let err = parse_session(r#"{"accessToken": "", "tenantURL": "x"}"#, "session.json")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("No access token"));
+        assert!(parse_session("{}", "session.json").is_err());
+    }
+
+    #[test]
+    fn configured_session_path_wins() {
+        let mut config = AppConfig::default();
+        assert!(session_path(&config).ends_with(".augment/session.json"));
+        config.providers.push(ProviderConfig {
+            id: "augment".into(),
+            enabled: true,
+            source: "auto".into(),
+            api_key: None,
+            timeout_secs: None,
+            cookie: None,
+            project: None,
+            region: None,
+            credentials: Some("/opt/augment/session.json".into()),
+            currency: None,
+        });
+        assert_eq!(
+            session_path(&config),
+            PathBuf::from("/opt/augment/session.json")
+        );
+    }
 }
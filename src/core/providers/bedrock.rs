@@ -0,0 +1,612 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::core::aws::{self, AwsCredentials, Signer};
+use crate::core::config::AppConfig;
+use crate::core::cost::pricing::{self, BedrockEndpoint};
+use crate::core::http::HttpClient;
+use crate::core::models::usage::{ProviderIdentity, RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{per_minute_window, FetchResult};
+use crate::core::providers::Provider;
+
+const QUOTAS_TARGET: &str = "ServiceQuotasV20190624.ListServiceQuotas";
+const METRICS_TARGET: &str = "GraniteServiceVersion20100801.GetMetricData";
+
+/// How far back CloudWatch is read; the peak minute in it is reported.
+/// Bedrock metrics arrive a minute or two late.
+const LOOKBACK_MINUTES: i64 = 10;
+
+// --- Service Quotas ---
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListQuotasResponse {
+    #[serde(default)]
+    quotas: Vec<ServiceQuota>,
+    next_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ServiceQuota {
+    quota_name: String,
+    value: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Tokens,
+    Requests,
+}
+
+/// A per-minute Anthropic quota, parsed from a name such as
+/// "Cross-region model inference tokens per minute for Anthropic Claude
+/// Sonnet 4.5 V1".
+#[derive(Debug, PartialEq)]
+struct QuotaName {
+    endpoint: BedrockEndpoint,
+    unit: Unit,
+    /// Normalized model name, e.g. `claude-sonnet-4-5`.
+    family: String,
+    version: Option<u32>,
+}
+
+fn parse_quota_name(name: &str) -> Option<QuotaName> {
+    let lower = name.to_lowercase();
+    let endpoint = if lower.starts_with("global cross-region") {
+        BedrockEndpoint::Global
+    } else if lower.starts_with("cross-region") {
+        BedrockEndpoint::Geographic
+    } else if lower.starts_with("on-demand") {
+        BedrockEndpoint::InRegion
+    } else {
+        return None;
+    };
+    let unit = if lower.contains(" tokens per minute ") {
+        Unit::Tokens
+    } else if lower.contains(" requests per minute ") {
+        Unit::Requests
+    } else {
+        return None;
+    };
+
+    let model = lower.split(" for anthropic ").nth(1)?;
+    let mut words: Vec<&str> = model.split_whitespace().collect();
+    let version = match words.last() {
+        Some(w) if w.len() > 1 && w.starts_with('v') && w[1..].parse::<u32>().is_ok() => {
+            let v = w[1..].parse().ok();
+            words.pop();
+            v
+        }
+        _ => None,
+    };
+    if words.first() != Some(&"claude") {
+        return None;
+    }
+    Some(QuotaName {
+        endpoint,
+        unit,
+        family: words.join("-").replace('.', "-"),
+        version,
+    })
+}
+
+/// `2` for `anthropic.claude-3-5-sonnet-20241022-v2:0`.
+fn model_version(model_id: &str) -> Option<u32> {
+    let id = model_id.split(':').next()?;
+    let (_, v) = id.rsplit_once("-v")?;
+    v.parse().ok()
+}
+
+fn signed_post(
+    http: &HttpClient,
+    signer: &Signer<'_>,
+    host: &str,
+    target: &str,
+    content_type: &str,
+    body: serde_json::Value,
+) -> Result<reqwest::RequestBuilder> {
+    let url = reqwest::Url::parse(&format!("https://{}/", host))?;
+    let body = serde_json::to_vec(&body)?;
+    let headers = [("content-type", content_type), ("x-amz-target", target)];
+    let mut request = http
+        .post(url.as_str())
+        .header("Content-Type", content_type)
+        .header("X-Amz-Target", target);
+    for (name, value) in signer.sign("POST", &url, &headers, &body, Utc::now()) {
+        request = request.header(name, value);
+    }
+    Ok(request.body(body))
+}
+
+async fn send_signed(
+    http: &HttpClient,
+    request: reqwest::RequestBuilder,
+    service: &str,
+) -> Result<reqwest::Response> {
+    // Signed read-only queries (metrics, listings), safe to repeat
+    let response = http
+        .send_repeatable(request)
+        .await
+        .with_context(|| format!("Failed to send request to AWS {}", service))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(ProviderError::AuthExpired {
+            message: format!(
+                "AWS rejected the credentials for {} — check the profile and its IAM permissions",
+                service
+            ),
+            status: Some(status.as_u16()),
+        }
+        .into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+    Ok(response)
+}
+
+async fn list_quotas(http: &HttpClient, signer: &Signer<'_>) -> Result<Vec<ServiceQuota>> {
+    let host = format!("servicequotas.{}.amazonaws.com", signer.region);
+    let mut quotas = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let mut body = json!({ "ServiceCode": "bedrock", "MaxResults": 100 });
+        if let Some(token) = &next_token {
+            body["NextToken"] = json!(token);
+        }
+        let request = signed_post(
+            http,
+            signer,
+            &host,
+            QUOTAS_TARGET,
+            "application/x-amz-json-1.1",
+            body,
+        )?;
+        let page: ListQuotasResponse = send_signed(http, request, "Service Quotas")
+            .await?
+            .json()
+            .await
+            .context("Failed to parse Service Quotas response")?;
+        quotas.extend(page.quotas);
+        next_token = page.next_token.filter(|t| !t.is_empty());
+        if next_token.is_none() {
+            return Ok(quotas);
+        }
+    }
+}
+
+// --- CloudWatch ---
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MetricDataResponse {
+    #[serde(default)]
+    metric_data_results: Vec<MetricDataResult>,
+    next_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MetricDataResult {
+    id: String,
+    label: String,
+    #[serde(default)]
+    timestamps: Vec<f64>,
+    #[serde(default)]
+    values: Vec<f64>,
+}
+
+/// Per-minute token and request counts of one model id.
+#[derive(Debug, Default, PartialEq)]
+struct ModelUsage {
+    tokens: HashMap<i64, f64>,
+    requests: HashMap<i64, f64>,
+}
+
+impl ModelUsage {
+    fn peak(&self, unit: Unit) -> f64 {
+        let minutes = match unit {
+            Unit::Tokens => &self.tokens,
+            Unit::Requests => &self.requests,
+        };
+        minutes.values().copied().fold(0.0, f64::max)
+    }
+}
+
+fn metric_query(id: &str, metric: &str) -> serde_json::Value {
+    json!({
+        "Id": id,
+        "Expression": format!(
+            "SEARCH('{{AWS/Bedrock,ModelId}} MetricName=\"{}\"', 'Sum', 60)",
+            metric
+        ),
+        "Label": "${PROP('Dim.ModelId')}",
+        "ReturnData": true,
+    })
+}
+
+/// Sum input and output tokens per minute and model; invocations count as
+/// requests. Bedrock's token quotas are charged for both directions.
+fn model_usage(results: &[MetricDataResult]) -> HashMap<String, ModelUsage> {
+    let mut usage: HashMap<String, ModelUsage> = HashMap::new();
+    for result in results {
+        let entry = usage.entry(result.label.clone()).or_default();
+        let minutes = if result.id.starts_with("tokens") {
+            &mut entry.tokens
+        } else if result.id.starts_with("invocations") {
+            &mut entry.requests
+        } else {
+            continue;
+        };
+        for (ts, value) in result.timestamps.iter().zip(&result.values) {
+            *minutes.entry(*ts as i64).or_default() += value;
+        }
+    }
+    usage
+}
+
+async fn metric_data(
+    http: &HttpClient,
+    signer: &Signer<'_>,
+    now: DateTime<Utc>,
+) -> Result<Vec<MetricDataResult>> {
+    let host = format!("monitoring.{}.amazonaws.com", signer.region);
+    let start = now - Duration::minutes(LOOKBACK_MINUTES);
+    let mut results = Vec::new();
+    let mut next_token: Option<String> = None;
+    loop {
+        let mut body = json!({
+            "MetricDataQueries": [
+                metric_query("tokens_in", "InputTokenCount"),
+                metric_query("tokens_out", "OutputTokenCount"),
+                metric_query("invocations", "Invocations"),
+            ],
+            "StartTime": start.timestamp(),
+            "EndTime": now.timestamp(),
+        });
+        if let Some(token) = &next_token {
+            body["NextToken"] = json!(token);
+        }
+        let request = signed_post(
+            http,
+            signer,
+            &host,
+            METRICS_TARGET,
+            "application/x-amz-json-1.0",
+            body,
+        )?;
+        let page: MetricDataResponse = send_signed(http, request, "CloudWatch")
+            .await?
+            .json()
+            .await
+            .context("Failed to parse CloudWatch response")?;
+        results.extend(page.metric_data_results);
+        next_token = page.next_token.filter(|t| !t.is_empty());
+        if next_token.is_none() {
+            return Ok(results);
+        }
+    }
+}
+
+// --- Windows ---
+
+#[derive(Debug)]
+struct ModelQuota {
+    model: String,
+    unit: Unit,
+    used: f64,
+    limit: f64,
+}
+
+impl ModelQuota {
+    fn used_percent(&self) -> f64 {
+        self.used / self.limit * 100.0
+    }
+
+    fn to_window(&self, now: DateTime<Utc>) -> RateWindow {
+        let unit = match self.unit {
+            Unit::Tokens => "tokens",
+            Unit::Requests => "requests",
+        };
+        per_minute_window(&self.model, self.used, self.limit, unit, now)
+    }
+}
+
+/// Pair each model id seen in CloudWatch with the quota of its endpoint,
+/// model and version. Quotas without usage are dropped, as the account
+/// lists one per model whether it is used or not.
+fn model_quotas(quotas: &[ServiceQuota], usage: &HashMap<String, ModelUsage>) -> Vec<ModelQuota> {
+    let parsed: Vec<(QuotaName, f64)> = quotas
+        .iter()
+        .filter_map(|q| Some((parse_quota_name(&q.quota_name)?, q.value?)))
+        .filter(|(_, limit)| *limit > 0.0)
+        .collect();
+
+    let mut out: Vec<ModelQuota> = Vec::new();
+    for (model_id, minutes) in usage {
+        let Some(endpoint) = pricing::bedrock_endpoint(model_id) else {
+            continue;
+        };
+        let family = pricing::normalize_model(model_id);
+        let version = model_version(model_id);
+        for (name, limit) in &parsed {
+            let matches = name.endpoint == endpoint
+                && name.family == family
+                && (name.version.is_none() || version.is_none() || name.version == version);
+            let used = minutes.peak(name.unit);
+            if matches && used > 0.0 {
+                out.push(ModelQuota {
+                    model: family.clone(),
+                    unit: name.unit,
+                    used,
+                    limit: *limit,
+                });
+            }
+        }
+    }
+    out.sort_by(|a, b| b.used_percent().total_cmp(&a.used_percent()));
+    out
+}
+
+/// Busiest token quota as primary, busiest request quota as secondary and
+/// the next busiest of either as tertiary.
+fn select_windows(quotas: &[ModelQuota], now: DateTime<Utc>) -> [Option<RateWindow>; 3] {
+    let tokens = quotas.iter().position(|q| q.unit == Unit::Tokens);
+    let requests = quotas.iter().position(|q| q.unit == Unit::Requests);
+    let rest = (0..quotas.len()).find(|&i| Some(i) != tokens && Some(i) != requests);
+    [tokens, requests, rest].map(|i| i.map(|i| quotas[i].to_window(now)))
+}
+
+/// Region from the `bedrock` entry in config.toml, else the AWS profile.
+pub fn resolve_region(config: &AppConfig, profile: &str) -> String {
+    config
+        .providers
+        .iter()
+        .find(|p| p.id == Provider::Bedrock.id())
+        .and_then(|p| p.region.clone())
+        .or_else(|| aws::region(profile))
+        .unwrap_or_else(|| aws::DEFAULT_REGION.to_string())
+}
+
+async fn quota_windows(
+    http: &HttpClient,
+    creds: &AwsCredentials,
+    region: &str,
+    now: DateTime<Utc>,
+) -> Result<[Option<RateWindow>; 3]> {
+    let quotas_signer = Signer {
+        creds,
+        region,
+        service: "servicequotas",
+    };
+    let metrics_signer = Signer {
+        creds,
+        region,
+        service: "monitoring",
+    };
+    let (quotas, results) = tokio::try_join!(
+        list_quotas(http, &quotas_signer),
+        metric_data(http, &metrics_signer, now),
+    )?;
+    Ok(select_windows(
+        &model_quotas(&quotas, &model_usage(&results)),
+        now,
+    ))
+}
+
+/// Bedrock cost comes from the Claude Code logs and is merged in by the
+/// caller. Quota windows are added when AWS credentials are available.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let profile = aws::profile();
+    let Some(creds) = aws::load_credentials(&profile) else {
+        return Ok(FetchResult {
+            usage: UsageSnapshot {
+                provider: Provider::Bedrock,
+                source: "logs".to_string(),
+                primary: None,
+                secondary: None,
+                tertiary: None,
+                identity: None,
+                local: None,
+                endpoints: Vec::new(),
+            },
+            credits: None,
+            cost: None,
+        });
+    };
+
+    let config = AppConfig::load().unwrap_or_default();
+    let region = resolve_region(&config, &profile);
+    let now = Utc::now();
+    let [primary, secondary, tertiary] = quota_windows(http, &creds, &region, now).await?;
+
+    Ok(FetchResult {
+        usage: UsageSnapshot {
+            provider: Provider::Bedrock,
+            source: "aws".to_string(),
+            primary,
+            secondary,
+            tertiary,
+            identity: Some(ProviderIdentity {
+                email: None,
+                organization: Some(format!("{} ({})", profile, region)),
+                plan: None,
+            }),
+            local: None,
+            endpoints: Vec::new(),
+        },
+        credits: None,
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ProviderConfig;
+
+    #[test]
+    fn parses_quota_names() {
+        assert_eq!(
+            parse_quota_name(
+                "Cross-region model inference tokens per minute for Anthropic Claude Sonnet 4.5 V1"
+            ),
+            Some(QuotaName {
+                endpoint: BedrockEndpoint::Geographic,
+                unit: Unit::Tokens,
+                family: "claude-sonnet-4-5".into(),
+                version: Some(1),
+            })
+        );
+        assert_eq!(
+            parse_quota_name(
+                "Global cross-region model inference requests per minute for Anthropic Claude Opus 4.6"
+            ),
+            Some(QuotaName {
+                endpoint: BedrockEndpoint::Global,
+                unit: Unit::Requests,
+                family: "claude-opus-4-6".into(),
+                version: None,
+            })
+        );
+        let old = parse_quota_name(
+            "On-demand model inference requests per minute for Anthropic Claude 3.5 Sonnet V2",
+        )
+        .unwrap();
+        assert_eq!(old.endpoint, BedrockEndpoint::InRegion);
+        assert_eq!(old.family, "claude-3-5-sonnet");
+        assert_eq!(old.version, Some(2));
+
+        // Daily limits and other vendors are ignored
+        assert!(parse_quota_name(
+            "Model invocation max tokens per day for Anthropic Claude Sonnet 4.5 V1"
+        )
+        .is_none());
+        assert!(parse_quota_name(
+            "On-demand model inference tokens per minute for Meta Llama 3 70B"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn model_versions() {
+        assert_eq!(
+            model_version("anthropic.claude-3-5-sonnet-20241022-v2:0"),
+            Some(2)
+        );
+        assert_eq!(
+            model_version("us.anthropic.claude-sonnet-4-5-20250929-v1:0"),
+            Some(1)
+        );
+        assert_eq!(model_version("claude-sonnet-4-5"), None);
+    }
+
+    const QUOTAS: &str = r#"{
+        "Quotas": [
+            {"QuotaName": "Cross-region model inference tokens per minute for Anthropic Claude Sonnet 4.5 V1", "Value": 200000.0},
+            {"QuotaName": "Cross-region model inference requests per minute for Anthropic Claude Sonnet 4.5 V1", "Value": 200.0},
+            {"QuotaName": "On-demand model inference tokens per minute for Anthropic Claude Sonnet 4.5 V1", "Value": 100000.0},
+            {"QuotaName": "Global cross-region model inference tokens per minute for Anthropic Claude Haiku 4.5", "Value": 400000.0},
+            {"QuotaName": "Cross-region model inference tokens per minute for Anthropic Claude Opus 4.6", "Value": 100000.0},
+            {"QuotaName": "Model units no-commitment Provisioned Throughputs across custom models"}
+        ],
+        "NextToken": null
+    }"#;
+
+    const METRICS: &str = r#"{
+        "MetricDataResults": [
+            {"Id": "tokens_in", "Label": "us.anthropic.claude-sonnet-4-5-20250929-v1:0",
+             "Timestamps": [1760000040, 1760000100], "Values": [40000.0, 10000.0], "StatusCode": "Complete"},
+            {"Id": "tokens_out", "Label": "us.anthropic.claude-sonnet-4-5-20250929-v1:0",
+             "Timestamps": [1760000040, 1760000100], "Values": [10000.0, 90000.0], "StatusCode": "Complete"},
+            {"Id": "invocations", "Label": "us.anthropic.claude-sonnet-4-5-20250929-v1:0",
+             "Timestamps": [1760000040, 1760000100], "Values": [30.0, 10.0], "StatusCode": "Complete"},
+            {"Id": "tokens_in", "Label": "global.anthropic.claude-haiku-4-5-20251001-v1:0",
+             "Timestamps": [1760000040], "Values": [4000.0], "StatusCode": "Complete"},
+            {"Id": "invocations", "Label": "global.anthropic.claude-haiku-4-5-20251001-v1:0",
+             "Timestamps": [1760000040], "Values": [2.0], "StatusCode": "Complete"}
+        ],
+        "Messages": []
+    }"#;
+
+    fn fixture_quotas() -> Vec<ModelQuota> {
+        let quotas: ListQuotasResponse = serde_json::from_str(QUOTAS).unwrap();
+        assert!(quotas.next_token.is_none());
+        let metrics: MetricDataResponse = serde_json::from_str(METRICS).unwrap();
+        model_quotas(&quotas.quotas, &model_usage(&metrics.metric_data_results))
+    }
+
+    #[test]
+    fn sums_tokens_per_minute() {
+        let metrics: MetricDataResponse = serde_json::from_str(METRICS).unwrap();
+        let usage = model_usage(&metrics.metric_data_results);
+        let sonnet = &usage["us.anthropic.claude-sonnet-4-5-20250929-v1:0"];
+        assert_eq!(sonnet.peak(Unit::Tokens), 100000.0);
+        assert_eq!(sonnet.peak(Unit::Requests), 30.0);
+    }
+
+    #[test]
+    fn pairs_quotas_with_usage() {
+        let quotas = fixture_quotas();
+        // Sonnet's cross-region tokens and requests and Haiku's global
+        // tokens; the in-region Sonnet and unused Opus quotas are dropped.
+        assert_eq!(quotas.len(), 3);
+        assert_eq!(quotas[0].model, "claude-sonnet-4-5");
+        assert_eq!(quotas[0].unit, Unit::Tokens);
+        assert!((quotas[0].used_percent() - 50.0).abs() < 1e-9);
+        assert_eq!(quotas[1].unit, Unit::Requests);
+        assert!((quotas[1].used_percent() - 15.0).abs() < 1e-9);
+        assert_eq!(quotas[2].model, "claude-haiku-4-5");
+        assert!((quotas[2].used_percent() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn selects_token_and_request_windows() {
+        let now: DateTime<Utc> = "2026-10-18T12:00:30Z".parse().unwrap();
+        let [primary, secondary, tertiary] = select_windows(&fixture_quotas(), now);
+        let primary = primary.unwrap();
+        assert_eq!(primary.window_minutes, 1);
+        assert_eq!(
+            primary.reset_description.as_deref(),
+            Some("claude-sonnet-4-5: 100000 of 200000 tokens/min")
+        );
+        assert_eq!(
+            primary.resets_at,
+            Some("2026-10-18T12:01:00Z".parse().unwrap())
+        );
+        assert_eq!(
+            secondary.unwrap().reset_description.as_deref(),
+            Some("claude-sonnet-4-5: 30 of 200 requests/min")
+        );
+        assert!(tertiary
+            .unwrap()
+            .reset_description
+            .unwrap()
+            .starts_with("claude-haiku-4-5"));
+        assert!(select_windows(&[], now).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn configured_region_wins() {
+        let mut config = AppConfig::default();
+        config.providers.push(ProviderConfig {
+            id: "bedrock".into(),
+            enabled: true,
+            source: "auto".into(),
+            api_key: None,
+            timeout_secs: None,
+            cookie: None,
+            project: None,
+            region: Some("eu-central-1".into()),
+            credentials: None,
+            currency: None,
+        });
+        assert_eq!(resolve_region(&config, "default"), "eu-central-1");
+    }
+}
@@ -5,6 +5,8 @@ use serde::Deserialize;
 use crate::core::auth::read_claude_credentials;
 use crate::core::models::credits::CreditsSnapshot;
 use crate::core::models::usage::{ProviderIdentity, RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -73,32 +75,34 @@ fn parse_extra_usage(raw: &ClaudeExtraUsageRaw) -> Option<CreditsSnapshot> {
         used,
         limit,
         currency: raw.currency.clone(),
+        unit: None,
         period: Some("Monthly".to_string()),
+        converted: None,
     })
 }
 
 /// Fetch usage data from the Claude OAuth API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let creds = read_claude_credentials().context("Failed to read Claude credentials")?;
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = http
         .get(USAGE_URL)
         .header("Authorization", format!("Bearer {}", creds.access_token))
         .header("Accept", "application/json")
         .header("Content-Type", "application/json")
-        .header("anthropic-beta", "oauth-2025-04-20")
-        .send()
+        .header("anthropic-beta", "oauth-2025-04-20");
+    let response = http
+        .send(request)
         .await
         .context("Failed to send request to Claude API")?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized — run `claude` to re-authenticate");
+        return Err(ProviderError::unauthorized("Unauthorized — run `claude` to re-authenticate").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let data: ClaudeUsageResponse = response
@@ -129,9 +133,11 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary,
         tertiary,
         identity,
+        local: None,
+        endpoints: Vec::new(),
     };
 
-    Ok(FetchResult { usage, credits })
+    Ok(FetchResult { usage, credits, cost: None })
 }
 
 #[cfg(test)]
@@ -6,6 +6,8 @@ use std::path::PathBuf;
 use crate::core::auth::read_codex_credentials;
 use crate::core::models::credits::CreditsSnapshot;
 use crate::core::models::usage::{ProviderIdentity, RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -27,6 +29,11 @@ fn codex_config_path() -> PathBuf {
         .join("config.toml")
 }
 
+/// `chatgpt_base_url` from the Codex CLI config, when set.
+pub fn base_url_override() -> Option<String> {
+    read_codex_config().chatgpt_base_url
+}
+
 fn read_codex_config() -> CodexConfig {
     let path = codex_config_path();
     let content = match std::fs::read_to_string(&path) {
@@ -130,7 +137,7 @@ fn parse_window(raw: CodexWindowRaw) -> RateWindow {
 }
 
 /// Fetch usage data from the Codex API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let creds = read_codex_credentials().context("Failed to read Codex credentials")?;
 
     let config = read_codex_config();
@@ -141,8 +148,7 @@ pub async fn fetch() -> Result<FetchResult> {
         eprintln!("codex: using custom endpoint: {}", url);
     }
 
-    let client = reqwest::Client::new();
-    let mut request = client
+    let mut request = http
         .get(&url)
         .header("Authorization", format!("Bearer {}", creds.access_token))
         .header("Accept", "application/json");
@@ -151,18 +157,18 @@ pub async fn fetch() -> Result<FetchResult> {
         request = request.header("ChatGPT-Account-Id", account_id);
     }
 
-    let response = request
-        .send()
+    let response = http
+        .send(request)
         .await
         .context("Failed to send request to Codex API")?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized — run `codex` to re-authenticate");
+        return Err(ProviderError::unauthorized("Unauthorized — run `codex` to re-authenticate").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let data: CodexUsageResponse = response
@@ -191,7 +197,9 @@ pub async fn fetch() -> Result<FetchResult> {
         used: None,
         limit: None,
         currency: None,
+        unit: None,
         period: None,
+        converted: None,
     });
 
     let usage = UsageSnapshot {
@@ -201,9 +209,11 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary,
         tertiary: None,
         identity,
+        local: None,
+        endpoints: Vec::new(),
     };
 
-    Ok(FetchResult { usage, credits })
+    Ok(FetchResult { usage, credits, cost: None })
 }
 
 #[cfg(test)]
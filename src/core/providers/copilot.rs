@@ -4,6 +4,8 @@ use serde::Deserialize;
 
 use crate::core::models::credits::CreditsSnapshot;
 use crate::core::models::usage::{ProviderIdentity, RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -94,35 +96,36 @@ fn resolve_github_token() -> Result<String> {
         }
     }
 
-    anyhow::bail!(
-        "No GitHub token found. Set GITHUB_TOKEN env or authenticate with `gh auth login`"
+    Err(ProviderError::AuthMissing(
+        "No GitHub token found. Set GITHUB_TOKEN env or authenticate with `gh auth login`".into(),
     )
+    .into())
 }
 
 /// Fetch usage data from the GitHub Copilot API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let token = resolve_github_token().context("Failed to resolve GitHub token")?;
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = http
         .get(USER_URL)
         .header("Authorization", format!("token {}", token))
         .header("Editor-Version", "vscode/1.96.2")
         .header("Editor-Plugin-Version", "copilot-chat/0.26.7")
         .header("User-Agent", "GitHubCopilotChat/0.26.7")
         .header("X-Github-Api-Version", "2025-04-01")
-        .header("Accept", "application/json")
-        .send()
+        .header("Accept", "application/json");
+    let response = http
+        .send(request)
         .await
         .context("Failed to send request to Copilot API")?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized - check your GitHub token or run `gh auth login`");
+        return Err(ProviderError::unauthorized("Unauthorized - check your GitHub token or run `gh auth login`").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let data: CopilotUserResponse = response
@@ -140,7 +143,7 @@ pub async fn fetch() -> Result<FetchResult> {
         .quota_snapshots
         .as_ref()
         .and_then(|qs| qs.chat.as_ref())
-        .map(|c| parse_chat_window(c));
+        .map(parse_chat_window);
 
     let identity = data.copilot_plan.map(|plan| ProviderIdentity {
         email: None,
@@ -163,7 +166,9 @@ pub async fn fetch() -> Result<FetchResult> {
                     .as_ref()
                     .and_then(|e| e.parse::<f64>().ok()),
                 currency: None,
+                unit: None,
                 period: Some("Monthly".to_string()),
+                converted: None,
             })
         });
 
@@ -174,9 +179,11 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary,
         tertiary: None,
         identity,
+        local: None,
+        endpoints: Vec::new(),
     };
 
-    Ok(FetchResult { usage, credits })
+    Ok(FetchResult { usage, credits, cost: None })
 }
 
 #[cfg(test)]
@@ -1,8 +1,569 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Months, Utc};
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
+use crate::core::auth::decode_jwt_claims;
+use crate::core::cookies::{self, CookieSource};
+use crate::core::http::HttpClient;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::models::usage::{ProviderIdentity, RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
+use crate::core::providers::Provider;
 
-/// Cursor usage provider (stub).
-pub async fn fetch() -> Result<FetchResult> {
-    anyhow::bail!("Cursor usage requires browser cookies (not yet supported on Linux)")
+const USAGE_URL: &str = "https://cursor.com/api/usage";
+const SUMMARY_URL: &str = "https://cursor.com/api/usage-summary";
+
+/// Web session cookie on cursor.com: `<user id>%3A%3A<access token>`.
+const SESSION_COOKIE: &str = "WorkosCursorSessionToken";
+const COOKIE_DOMAIN: &str = "cursor.com";
+
+const ACCESS_TOKEN_KEY: &str = "cursorAuth/accessToken";
+const EMAIL_KEY: &str = "cursorAuth/cachedEmail";
+const MEMBERSHIP_KEY: &str = "cursorAuth/stripeMembershipType";
+
+/// Bucket counting premium (fast) requests.
+const PREMIUM_BUCKET: &str = "gpt-4";
+/// Bucket counting requests to the small in-house models.
+const SMALL_BUCKET: &str = "gpt-3.5-turbo";
+
+/// Cursor's VS Code state database, e.g.
+/// `~/.config/Cursor/User/globalStorage/state.vscdb`.
+pub fn state_db_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("~"))
+                .join(".config")
+        })
+        .join("Cursor")
+        .join("User")
+        .join("globalStorage")
+        .join("state.vscdb")
+}
+
+/// Session details stored by the Cursor IDE after sign-in.
+pub struct CursorAuth {
+    pub access_token: String,
+    pub email: Option<String>,
+    pub membership: Option<String>,
+}
+
+/// Read the session from the state database. The database is opened
+/// read-only so a running Cursor instance is never disturbed.
+pub fn read_auth(path: &Path) -> Result<CursorAuth> {
+    if !path.exists() {
+        return Err(ProviderError::AuthMissing(format!(
+            "Cursor state database not found at {} - sign in to Cursor first",
+            path.display()
+        ))
+        .into());
+    }
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("Failed to open {}", path.display()))?;
+    // Cursor writes to the database while running
+    conn.busy_timeout(std::time::Duration::from_secs(1))?;
+
+    let access_token = read_item(&conn, ACCESS_TOKEN_KEY)?
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| {
+            ProviderError::AuthMissing("No Cursor session token - sign in to Cursor".into())
+        })?;
+
+    Ok(CursorAuth {
+        access_token,
+        email: read_item(&conn, EMAIL_KEY)?,
+        membership: read_item(&conn, MEMBERSHIP_KEY)?,
+    })
+}
+
+/// A value from `ItemTable`, stored as either TEXT or a UTF-8 BLOB.
+fn read_item(conn: &Connection, key: &str) -> Result<Option<String>> {
+    let value: Option<Value> = conn
+        .query_row("SELECT value FROM ItemTable WHERE key = ?1", [key], |row| {
+            row.get(0)
+        })
+        .optional()
+        .with_context(|| format!("Failed to read {} from Cursor state database", key))?;
+    Ok(match value {
+        Some(Value::Text(s)) => Some(s),
+        Some(Value::Blob(b)) => String::from_utf8(b).ok(),
+        _ => None,
+    })
+}
+
+/// User id from the token's `sub` claim (`auth0|user_...`).
+fn user_id(token: &str) -> Result<String> {
+    let claims = decode_jwt_claims(token).context("Invalid Cursor session token")?;
+    let sub = claims["sub"]
+        .as_str()
+        .ok_or_else(|| ProviderError::Parse("Cursor session token has no sub claim".into()))?;
+    Ok(sub.rsplit('|').next().unwrap_or(sub).to_string())
+}
+
+/// Credentials for the dashboard API.
+struct Session {
+    user: String,
+    token: String,
+    email: Option<String>,
+    membership: Option<String>,
+}
+
+impl Session {
+    /// From the `cookie` configured for Cursor, otherwise the IDE's state
+    /// database, falling back to a browser signed in to cursor.com.
+    fn load() -> Result<Self> {
+        if let Some(source) = CookieSource::configured(Provider::Cursor) {
+            return Self::from_cookies(&source);
+        }
+        match read_auth(&state_db_path()) {
+            Ok(auth) => Ok(Self {
+                user: user_id(&auth.access_token)?,
+                token: auth.access_token,
+                email: auth.email,
+                membership: auth.membership,
+            }),
+            Err(err) => Self::from_cookies(&CookieSource::Auto).map_err(|_| err),
+        }
+    }
+
+    fn from_cookies(source: &CookieSource) -> Result<Self> {
+        let cookies = source.load(COOKIE_DOMAIN)?;
+        let value = cookies::find(&cookies, SESSION_COOKIE).ok_or_else(|| {
+            ProviderError::AuthMissing(format!("No {} cookie for cursor.com", SESSION_COOKIE))
+        })?;
+        Self::from_cookie_value(value)
+    }
+
+    fn from_cookie_value(value: &str) -> Result<Self> {
+        let (user, token) = value
+            .split_once("%3A%3A")
+            .or_else(|| value.split_once("::"))
+            .ok_or_else(|| ProviderError::Parse(format!("Malformed {} cookie", SESSION_COOKIE)))?;
+        Ok(Self {
+            user: user.to_string(),
+            token: token.to_string(),
+            email: None,
+            membership: None,
+        })
+    }
+
+    fn cookie(&self) -> String {
+        format!("{}={}%3A%3A{}", SESSION_COOKIE, self.user, self.token)
+    }
+}
+
+#[derive(Deserialize)]
+struct ModelBucket {
+    #[serde(rename = "numRequests")]
+    num_requests: Option<u64>,
+    #[serde(rename = "maxRequestUsage")]
+    max_request_usage: Option<u64>,
+}
+
+/// `/api/usage`: one bucket per model family plus the billing period start.
+#[derive(Deserialize)]
+struct UsageResponse {
+    #[serde(rename = "startOfMonth")]
+    start_of_month: Option<String>,
+    #[serde(flatten)]
+    buckets: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct OnDemand {
+    enabled: Option<bool>,
+    /// Cents
+    used: Option<f64>,
+    /// Cents; absent when on-demand spend is uncapped
+    limit: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct IndividualUsage {
+    #[serde(rename = "onDemand")]
+    on_demand: Option<OnDemand>,
+}
+
+/// `/api/usage-summary`: billing cycle and on-demand (usage-based) spend.
+#[derive(Deserialize)]
+struct SummaryResponse {
+    #[serde(rename = "billingCycleEnd")]
+    billing_cycle_end: Option<String>,
+    #[serde(rename = "membershipType")]
+    membership_type: Option<String>,
+    #[serde(rename = "individualUsage")]
+    individual_usage: Option<IndividualUsage>,
+}
+
+/// Billing period as (start, reset). Prefers the cycle end reported by the
+/// summary endpoint, otherwise assumes a monthly cycle.
+fn billing_period(
+    usage: &UsageResponse,
+    cycle_end: Option<DateTime<Utc>>,
+) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    let start = usage
+        .start_of_month
+        .as_deref()
+        .and_then(|s| s.parse::<DateTime<Utc>>().ok());
+    let end = cycle_end.or_else(|| start.and_then(|s| s.checked_add_months(Months::new(1))));
+    (start, end)
+}
+
+/// A window for a bucket with a request limit; unlimited buckets are skipped.
+fn bucket_window(
+    bucket: &ModelBucket,
+    start: Option<DateTime<Utc>>,
+    resets_at: Option<DateTime<Utc>>,
+) -> Option<RateWindow> {
+    let max = bucket.max_request_usage.filter(|&m| m > 0)?;
+    let used = bucket.num_requests.unwrap_or(0);
+    let window_minutes = match (start, resets_at) {
+        (Some(start), Some(end)) if end > start => (end - start).num_minutes() as u64,
+        _ => 0,
+    };
+    Some(RateWindow {
+        used_percent: (used as f64 / max as f64 * 100.0).min(100.0),
+        window_minutes,
+        resets_at,
+        reset_description: Some(format!("{} of {} requests", used, max)),
+    })
+}
+
+/// Premium requests as primary, small-model requests as secondary and the
+/// first other limited bucket as tertiary.
+fn parse_windows(
+    usage: &UsageResponse,
+    cycle_end: Option<DateTime<Utc>>,
+) -> (Option<RateWindow>, Option<RateWindow>, Option<RateWindow>) {
+    let (start, resets_at) = billing_period(usage, cycle_end);
+    let window = |name: &str| {
+        usage
+            .buckets
+            .get(name)
+            .and_then(|v| serde_json::from_value::<ModelBucket>(v.clone()).ok())
+            .and_then(|b| bucket_window(&b, start, resets_at))
+    };
+    let tertiary = usage
+        .buckets
+        .keys()
+        .filter(|k| *k != PREMIUM_BUCKET && *k != SMALL_BUCKET)
+        .find_map(|k| window(k));
+    (window(PREMIUM_BUCKET), window(SMALL_BUCKET), tertiary)
+}
+
+/// On-demand spend in dollars, when it is enabled for the account.
+fn parse_on_demand(on_demand: &OnDemand) -> Option<CreditsSnapshot> {
+    if on_demand.enabled == Some(false) {
+        return None;
+    }
+    let used = on_demand.used.unwrap_or(0.0) / 100.0;
+    let limit = on_demand.limit.map(|l| l / 100.0);
+    let remaining = limit.map(|l| (l - used).max(0.0)).unwrap_or(0.0);
+    Some(CreditsSnapshot {
+        remaining,
+        has_credits: limit.is_none() || remaining > 0.0,
+        unlimited: limit.is_none(),
+        used: Some(used),
+        limit,
+        currency: Some("usd".to_string()),
+        unit: None,
+        period: Some("Monthly".to_string()),
+        converted: None,
+    })
+}
+
+/// Fetch usage from Cursor's dashboard API with the IDE's session.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let session = Session::load()?;
+    // The dashboard authenticates with the web session cookie
+    let cookie = session.cookie();
+
+    let request = http
+        .get(USAGE_URL)
+        .query(&[("user", session.user.as_str())])
+        .header("Cookie", &cookie);
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to Cursor API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProviderError::unauthorized(
+            "Unauthorized - sign in to Cursor again to refresh the session",
+        )
+        .into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    let usage: UsageResponse = response
+        .json()
+        .await
+        .context("Failed to parse Cursor usage response")?;
+
+    // Older plans have no usage summary; request counts are still reported
+    let summary = fetch_summary(http, &cookie).await.ok();
+    let cycle_end = summary
+        .as_ref()
+        .and_then(|s| s.billing_cycle_end.as_deref())
+        .and_then(|s| s.parse::<DateTime<Utc>>().ok());
+    let (primary, secondary, tertiary) = parse_windows(&usage, cycle_end);
+
+    let credits = summary
+        .as_ref()
+        .and_then(|s| s.individual_usage.as_ref())
+        .and_then(|u| u.on_demand.as_ref())
+        .and_then(parse_on_demand);
+
+    let plan = session
+        .membership
+        .or_else(|| summary.and_then(|s| s.membership_type));
+    let identity = Some(ProviderIdentity {
+        email: session.email,
+        organization: None,
+        plan,
+    });
+
+    let usage = UsageSnapshot {
+        provider: Provider::Cursor,
+        source: "api".to_string(),
+        primary,
+        secondary,
+        tertiary,
+        identity,
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult { usage, credits, cost: None })
+}
+
+async fn fetch_summary(http: &HttpClient, cookie: &str) -> Result<SummaryResponse> {
+    let request = http.get(SUMMARY_URL).header("Cookie", cookie);
+    let response = http.send(request).await?.error_for_status()?;
+    Ok(response.json().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const USAGE_FIXTURE: &str = r#"{
+        "gpt-4": {
+            "numRequests": 120,
+            "numRequestsTotal": 150,
+            "numTokens": 2400000,
+            "maxRequestUsage": 500,
+            "maxTokenUsage": null
+        },
+        "gpt-3.5-turbo": {
+            "numRequests": 40,
+            "numRequestsTotal": 40,
+            "numTokens": 90000,
+            "maxRequestUsage": null,
+            "maxTokenUsage": null
+        },
+        "gpt-4-32k": {
+            "numRequests": 5,
+            "numRequestsTotal": 5,
+            "numTokens": 100000,
+            "maxRequestUsage": 50,
+            "maxTokenUsage": null
+        },
+        "startOfMonth": "2026-01-15T10:00:00.000Z"
+    }"#;
+
+    const SUMMARY_FIXTURE: &str = r#"{
+        "billingCycleStart": "2026-01-15T10:00:00.000Z",
+        "billingCycleEnd": "2026-02-15T10:00:00.000Z",
+        "membershipType": "pro",
+        "limitType": "user",
+        "individualUsage": {
+            "plan": { "enabled": true, "used": 2000, "limit": 2000, "remaining": 0 },
+            "onDemand": { "enabled": true, "used": 1250, "limit": 5000, "remaining": 3750 }
+        }
+    }"#;
+
+    fn fixture_db(name: &str, items: &[(&str, Value)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ait_test_cursor_{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.vscdb");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute(
+            "CREATE TABLE ItemTable (key TEXT UNIQUE ON CONFLICT REPLACE, value BLOB)",
+            [],
+        )
+        .unwrap();
+        for (key, value) in items {
+            conn.execute(
+                "INSERT INTO ItemTable (key, value) VALUES (?1, ?2)",
+                rusqlite::params![key, value],
+            )
+            .unwrap();
+        }
+        path
+    }
+
+    fn jwt(payload: &str) -> String {
+        use base64::Engine;
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        format!(
+            "{}.{}.sig",
+            engine.encode(r#"{"alg":"HS256"}"#),
+            engine.encode(payload)
+        )
+    }
+
+    #[test]
+    fn reads_auth_from_state_db() {
+        let token = jwt(r#"{"sub":"auth0|user_01ABC","exp":1900000000}"#);
+        let path = fixture_db(
+            "auth",
+            &[
+                (ACCESS_TOKEN_KEY, Value::Text(token.clone())),
+                (EMAIL_KEY, Value::Blob(b"dev@example.com".to_vec())),
+                (MEMBERSHIP_KEY, Value::Text("pro".into())),
+                ("cursorAuth/refreshToken", Value::Text("refresh".into())),
+            ],
+        );
+        let auth = read_auth(&path).unwrap();
+        assert_eq!(auth.access_token, token);
+        assert_eq!(auth.email.as_deref(), Some("dev@example.com"));
+        assert_eq!(auth.membership.as_deref(), Some("pro"));
+        assert_eq!(user_id(&auth.access_token).unwrap(), "user_01ABC");
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn missing_token_is_auth_missing() {
+        let path = fixture_db("no_token", &[(EMAIL_KEY, Value::Text("dev@example.com".into()))]);
+        let err = read_auth(&path).err().unwrap();
+        let typed = err.downcast_ref::<ProviderError>().unwrap();
+        assert!(matches!(typed, ProviderError::AuthMissing(_)));
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn missing_db_is_auth_missing() {
+        let err = read_auth(Path::new("/nonexistent/Cursor/state.vscdb")).err().unwrap();
+        assert!(matches!(
+            err.downcast_ref::<ProviderError>(),
+            Some(ProviderError::AuthMissing(_))
+        ));
+    }
+
+    #[test]
+    fn user_id_without_provider_prefix() {
+        let token = jwt(r#"{"sub":"user_02XYZ"}"#);
+        assert_eq!(user_id(&token).unwrap(), "user_02XYZ");
+        assert!(user_id(&jwt("{}")).is_err());
+    }
+
+    #[test]
+    fn session_from_browser_cookie() {
+        let session = Session::from_cookie_value("user_01ABC%3A%3AeyJhbGciOi.payload.sig").unwrap();
+        assert_eq!(session.user, "user_01ABC");
+        assert_eq!(session.token, "eyJhbGciOi.payload.sig");
+        assert_eq!(
+            session.cookie(),
+            "WorkosCursorSessionToken=user_01ABC%3A%3AeyJhbGciOi.payload.sig"
+        );
+        assert_eq!(Session::from_cookie_value("user_01ABC::tok").unwrap().token, "tok");
+        assert!(Session::from_cookie_value("garbage").is_err());
+    }
+
+    #[test]
+    fn session_from_cookie_header_source() {
+        let source = CookieSource::parse("WorkosCursorSessionToken=user_9%3A%3Atok; other=1").unwrap();
+        let session = Session::from_cookies(&source).unwrap();
+        assert_eq!(session.user, "user_9");
+        assert_eq!(session.token, "tok");
+    }
+
+    #[test]
+    fn parses_buckets_into_windows() {
+        let usage: UsageResponse = serde_json::from_str(USAGE_FIXTURE).unwrap();
+        let (primary, secondary, tertiary) = parse_windows(&usage, None);
+
+        let primary = primary.unwrap();
+        assert!((primary.used_percent - 24.0).abs() < 1e-10);
+        assert_eq!(
+            primary.resets_at,
+            Some(Utc.with_ymd_and_hms(2026, 2, 15, 10, 0, 0).unwrap())
+        );
+        assert_eq!(primary.window_minutes, 31 * 24 * 60);
+        assert_eq!(primary.reset_description.as_deref(), Some("120 of 500 requests"));
+
+        // Unlimited bucket has no percentage to report
+        assert!(secondary.is_none());
+
+        let tertiary = tertiary.unwrap();
+        assert!((tertiary.used_percent - 10.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn cycle_end_from_summary_wins() {
+        let usage: UsageResponse = serde_json::from_str(USAGE_FIXTURE).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 2, 14, 0, 0, 0).unwrap();
+        let (primary, _, _) = parse_windows(&usage, Some(end));
+        assert_eq!(primary.unwrap().resets_at, Some(end));
+    }
+
+    #[test]
+    fn usage_without_start_has_no_reset() {
+        let usage: UsageResponse =
+            serde_json::from_str(r#"{"gpt-4": {"numRequests": 600, "maxRequestUsage": 500}}"#)
+                .unwrap();
+        let (primary, _, _) = parse_windows(&usage, None);
+        let primary = primary.unwrap();
+        assert!((primary.used_percent - 100.0).abs() < 1e-10);
+        assert!(primary.resets_at.is_none());
+        assert_eq!(primary.window_minutes, 0);
+    }
+
+    #[test]
+    fn parses_on_demand_spend() {
+        let summary: SummaryResponse = serde_json::from_str(SUMMARY_FIXTURE).unwrap();
+        assert_eq!(summary.membership_type.as_deref(), Some("pro"));
+        let on_demand = summary.individual_usage.unwrap().on_demand.unwrap();
+        let credits = parse_on_demand(&on_demand).unwrap();
+        assert!((credits.used.unwrap() - 12.5).abs() < 1e-10);
+        assert!((credits.limit.unwrap() - 50.0).abs() < 1e-10);
+        assert!((credits.remaining - 37.5).abs() < 1e-10);
+        assert!(credits.has_credits);
+        assert!(!credits.unlimited);
+    }
+
+    #[test]
+    fn on_demand_disabled_or_uncapped() {
+        let disabled = OnDemand {
+            enabled: Some(false),
+            used: Some(0.0),
+            limit: Some(0.0),
+        };
+        assert!(parse_on_demand(&disabled).is_none());
+
+        let uncapped = OnDemand {
+            enabled: Some(true),
+            used: Some(999.0),
+            limit: None,
+        };
+        let credits = parse_on_demand(&uncapped).unwrap();
+        assert!(credits.unlimited);
+        assert!(credits.limit.is_none());
+        assert!((credits.used.unwrap() - 9.99).abs() < 1e-10);
+    }
 }
@@ -0,0 +1,127 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::core::http::HttpClient;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::models::usage::UsageSnapshot;
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{env_api_key, FetchResult};
+use crate::core::providers::Provider;
+
+const BALANCE_URL: &str = "https://api.deepseek.com/user/balance";
+
+#[derive(Deserialize)]
+struct BalanceResponse {
+    #[serde(default)]
+    is_available: bool,
+    #[serde(default)]
+    balance_infos: Vec<BalanceInfo>,
+}
+
+/// Balance in one currency. Amounts are decimal strings.
+#[derive(Deserialize)]
+struct BalanceInfo {
+    currency: String,
+    total_balance: String,
+}
+
+/// The balance in the account's first currency (USD or CNY).
+fn parse_credits(data: &BalanceResponse) -> Option<CreditsSnapshot> {
+    let info = data.balance_infos.first()?;
+    let remaining: f64 = info.total_balance.parse().ok()?;
+    Some(CreditsSnapshot {
+        remaining,
+        has_credits: data.is_available && remaining > 0.0,
+        unlimited: false,
+        used: None,
+        limit: None,
+        currency: Some(info.currency.to_lowercase()),
+        unit: None,
+        period: None,
+        converted: None,
+    })
+}
+
+/// Fetch the prepaid balance from the DeepSeek API.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let api_key = env_api_key("DEEPSEEK_API_KEY")?;
+
+    let request = http
+        .get(BALANCE_URL)
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Accept", "application/json");
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to DeepSeek API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(
+            ProviderError::unauthorized("Unauthorized - check your DEEPSEEK_API_KEY").into(),
+        );
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    let data: BalanceResponse = response
+        .json()
+        .await
+        .context("Failed to parse DeepSeek balance response")?;
+
+    let usage = UsageSnapshot {
+        provider: Provider::DeepSeek,
+        source: "api".to_string(),
+        primary: None,
+        secondary: None,
+        tertiary: None,
+        identity: None,
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage,
+        credits: parse_credits(&data),
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_balance() {
+        let data: BalanceResponse = serde_json::from_str(
+            r#"{
+                "is_available": true,
+                "balance_infos": [{
+                    "currency": "CNY",
+                    "total_balance": "110.00",
+                    "granted_balance": "10.00",
+                    "topped_up_balance": "100.00"
+                }]
+            }"#,
+        )
+        .unwrap();
+        let credits = parse_credits(&data).unwrap();
+        assert_eq!(credits.remaining, 110.0);
+        assert!(credits.has_credits);
+        assert_eq!(credits.currency.as_deref(), Some("cny"));
+    }
+
+    #[test]
+    fn unavailable_or_empty_balance() {
+        let data: BalanceResponse = serde_json::from_str(
+            r#"{"is_available": false, "balance_infos": [{"currency": "USD", "total_balance": "0.00"}]}"#,
+        )
+        .unwrap();
+        assert!(!parse_credits(&data).unwrap().has_credits);
+
+        let empty: BalanceResponse = serde_json::from_str(r#"{"is_available": false}"#).unwrap();
+        assert!(parse_credits(&empty).is_none());
+    }
+}
@@ -0,0 +1,209 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Stable machine-readable category of a failed provider fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    AuthMissing,
+    AuthExpired,
+    HttpStatus,
+    Network,
+    Parse,
+    Timeout,
+    Unsupported,
+}
+
+/// Typed failure raised by providers. Carried inside `anyhow::Error` so
+/// context added on the way up is kept; `ErrorReport::from_error` recovers it.
+#[derive(Error, Debug)]
+pub enum ProviderError {
+    /// No credentials configured (missing env var, file or login).
+    #[error("{0}")]
+    AuthMissing(String),
+    /// Credentials were found but rejected or have expired.
+    #[error("{message}")]
+    AuthExpired {
+        message: String,
+        status: Option<u16>,
+    },
+    /// The API answered with a non-success status.
+    #[error("HTTP {status}: {body}")]
+    HttpStatus { status: u16, body: String },
+    #[error("{0}")]
+    Network(String),
+    #[error("{0}")]
+    Parse(String),
+    #[error("{0}")]
+    Timeout(String),
+    /// The provider or configuration cannot be fetched by this build.
+    #[error("{0}")]
+    Unsupported(String),
+}
+
+impl ProviderError {
+    /// A 401 response, with a hint on how to re-authenticate.
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::AuthExpired {
+            message: message.into(),
+            status: Some(401),
+        }
+    }
+
+    pub fn http_status(status: reqwest::StatusCode, body: String) -> Self {
+        Self::HttpStatus {
+            status: status.as_u16(),
+            body,
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::AuthMissing(_) => ErrorKind::AuthMissing,
+            Self::AuthExpired { .. } => ErrorKind::AuthExpired,
+            Self::HttpStatus { .. } => ErrorKind::HttpStatus,
+            Self::Network(_) => ErrorKind::Network,
+            Self::Parse(_) => ErrorKind::Parse,
+            Self::Timeout(_) => ErrorKind::Timeout,
+            Self::Unsupported(_) => ErrorKind::Unsupported,
+        }
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::AuthExpired { status, .. } => *status,
+            Self::HttpStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+/// Serializable description of a fetch error for JSON output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorReport {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub status: Option<u16>,
+}
+
+impl ErrorReport {
+    /// Classify an error by the first recognised cause in its chain: a
+    /// `ProviderError`, then reqwest, JSON, I/O and env var errors. Anything
+    /// else (a failed subprocess, a local server not running) is `network`.
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        let (kind, status) = err
+            .chain()
+            .find_map(classify_cause)
+            .unwrap_or((ErrorKind::Network, None));
+        Self {
+            kind,
+            message: format!("{:#}", err),
+            status,
+        }
+    }
+}
+
+fn classify_cause(cause: &(dyn std::error::Error + 'static)) -> Option<(ErrorKind, Option<u16>)> {
+    if let Some(e) = cause.downcast_ref::<ProviderError>() {
+        return Some((e.kind(), e.status()));
+    }
+    if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+        let status = e.status().map(|s| s.as_u16());
+        let kind = if e.is_timeout() {
+            ErrorKind::Timeout
+        } else if e.is_decode() {
+            ErrorKind::Parse
+        } else if status.is_some() {
+            ErrorKind::HttpStatus
+        } else {
+            ErrorKind::Network
+        };
+        return Some((kind, status));
+    }
+    if cause.is::<serde_json::Error>() || cause.is::<base64::DecodeError>() {
+        return Some((ErrorKind::Parse, None));
+    }
+    if cause.is::<std::env::VarError>() {
+        return Some((ErrorKind::AuthMissing, None));
+    }
+    if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+        return match e.kind() {
+            std::io::ErrorKind::NotFound => Some((ErrorKind::AuthMissing, None)),
+            std::io::ErrorKind::TimedOut => Some((ErrorKind::Timeout, None)),
+            _ => None,
+        };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn typed_error_survives_context() {
+        let err = anyhow::Error::from(ProviderError::HttpStatus {
+            status: 503,
+            body: "down".into(),
+        })
+        .context("Failed to fetch usage");
+        let report = ErrorReport::from_error(&err);
+        assert_eq!(report.kind, ErrorKind::HttpStatus);
+        assert_eq!(report.status, Some(503));
+        assert_eq!(report.message, "Failed to fetch usage: HTTP 503: down");
+    }
+
+    #[test]
+    fn unauthorized_is_auth_expired_with_status() {
+        let err = anyhow::Error::from(ProviderError::unauthorized("Unauthorized"));
+        let report = ErrorReport::from_error(&err);
+        assert_eq!(report.kind, ErrorKind::AuthExpired);
+        assert_eq!(report.status, Some(401));
+    }
+
+    #[test]
+    fn missing_env_var_is_auth_missing() {
+        let err = std::env::var("AIT_TEST_SURELY_UNSET_VAR")
+            .context("AIT_TEST_SURELY_UNSET_VAR env var not set")
+            .unwrap_err();
+        assert_eq!(ErrorReport::from_error(&err).kind, ErrorKind::AuthMissing);
+    }
+
+    #[test]
+    fn missing_file_is_auth_missing() {
+        let err = std::fs::read_to_string("/nonexistent/ait/credentials.json")
+            .context("Failed to read credentials")
+            .unwrap_err();
+        assert_eq!(ErrorReport::from_error(&err).kind, ErrorKind::AuthMissing);
+    }
+
+    #[test]
+    fn json_error_is_parse() {
+        let err = serde_json::from_str::<serde_json::Value>("{")
+            .context("Failed to parse response")
+            .unwrap_err();
+        let report = ErrorReport::from_error(&err);
+        assert_eq!(report.kind, ErrorKind::Parse);
+        assert_eq!(report.status, None);
+    }
+
+    #[test]
+    fn untyped_error_falls_back_to_network() {
+        let err = anyhow::anyhow!("kiro-cli exited with 1");
+        assert_eq!(ErrorReport::from_error(&err).kind, ErrorKind::Network);
+    }
+
+    #[test]
+    fn report_serializes_snake_case_kind() {
+        let report = ErrorReport {
+            kind: ErrorKind::AuthMissing,
+            message: "WARP_TOKEN env var not set".into(),
+            status: None,
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["kind"], "auth_missing");
+        assert!(json["status"].is_null());
+    }
+}
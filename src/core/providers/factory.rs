@@ -1,8 +1,268 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
 
+use crate::core::auth::decode_jwt_claims;
+use crate::core::config::AppConfig;
+use crate::core::http::HttpClient;
+use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
+use crate::core::providers::Provider;
 
-/// Factory usage provider (stub).
-pub async fn fetch() -> Result<FetchResult> {
-    anyhow::bail!("Factory requires browser cookies (not yet supported)")
+const USAGE_URL: &str = "https://app.factory.ai/api/organization/subscription/usage";
+
+/// API key accepted by `droid exec` in place of a login.
+const API_KEY_ENV: &str = "FACTORY_API_KEY";
+
+/// Tokens written by `droid` after logging in.
+#[derive(Deserialize)]
+struct AuthFile {
+    access_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UsageResponse {
+    usage: Option<SubscriptionUsage>,
+}
+
+/// Token allowances of the organization's subscription for the current
+/// billing cycle. Dates are Unix milliseconds.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubscriptionUsage {
+    start_date: Option<i64>,
+    end_date: Option<i64>,
+    standard: Option<TokenAllowance>,
+    premium: Option<TokenAllowance>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenAllowance {
+    org_total_tokens_used: Option<f64>,
+    total_allowance: Option<f64>,
+}
+
+/// The `credentials` override for factory in config.toml, else
+/// `~/.factory/auth.json`.
+pub fn auth_path(config: &AppConfig) -> PathBuf {
+    config
+        .providers
+        .iter()
+        .find(|p| p.id == Provider::Factory.id())
+        .and_then(|p| p.credentials_path())
+        .unwrap_or_else(|| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("~"))
+                .join(".factory")
+                .join("auth.json")
+        })
+}
+
+/// `FACTORY_API_KEY`, else the access token from the auth file. An expired
+/// login is reported rather than sent, since only `droid` refreshes it.
+fn load_token(path: &Path, now: DateTime<Utc>) -> Result<String> {
+    if let Ok(key) = std::env::var(API_KEY_ENV) {
+        if !key.is_empty() {
+            return Ok(key);
+        }
+    }
+    let content = std::fs::read_to_string(path).map_err(|_| {
+        ProviderError::AuthMissing(format!(
+            "No Factory login at {} — run `droid` and sign in, or set {}",
+            path.display(),
+            API_KEY_ENV
+        ))
+    })?;
+    let auth: AuthFile = serde_json::from_str(&content)
+        .map_err(|e| ProviderError::Parse(format!("Invalid {}: {}", path.display(), e)))?;
+    let token = auth.access_token.filter(|t| !t.is_empty()).ok_or_else(|| {
+        ProviderError::AuthMissing(format!("No access_token in {}", path.display()))
+    })?;
+    let expires_at = decode_jwt_claims(&token)
+        .ok()
+        .and_then(|claims| claims["exp"].as_i64())
+        .and_then(|exp| Utc.timestamp_opt(exp, 0).single());
+    if expires_at.is_some_and(|at| at <= now) {
+        return Err(ProviderError::AuthExpired {
+            message: "Factory login expired — run `droid` to refresh it".into(),
+            status: None,
+        }
+        .into());
+    }
+    Ok(token)
+}
+
+fn millis(ms: Option<i64>) -> Option<DateTime<Utc>> {
+    ms.and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+}
+
+/// Tokens used out of the allowance, resetting at the end of the cycle.
+/// Tiers without an allowance are skipped.
+fn allowance_window(allowance: &TokenAllowance, usage: &SubscriptionUsage) -> Option<RateWindow> {
+    let limit = allowance.total_allowance.filter(|&l| l > 0.0)?;
+    let used = allowance.org_total_tokens_used.unwrap_or(0.0);
+    let (start, end) = (millis(usage.start_date), millis(usage.end_date));
+    let window_minutes = match (start, end) {
+        (Some(start), Some(end)) if end > start => (end - start).num_minutes() as u64,
+        _ => 0,
+    };
+    Some(RateWindow {
+        used_percent: (used / limit * 100.0).min(100.0),
+        window_minutes,
+        resets_at: end,
+        reset_description: Some(format!(
+            "{} of {} tokens",
+            used.round() as u64,
+            limit.round() as u64
+        )),
+    })
+}
+
+/// Fetch the subscription's token usage from Factory.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let config = AppConfig::load().unwrap_or_default();
+    let token = load_token(&auth_path(&config), Utc::now())?;
+
+    let request = http
+        .get(USAGE_URL)
+        .header("Authorization", format!("Bearer {}", token));
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to Factory API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProviderError::unauthorized(
+            "Unauthorized — run `droid` to sign in again or check FACTORY_API_KEY",
+        )
+        .into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    let data: UsageResponse = response
+        .json()
+        .await
+        .context("Failed to parse Factory usage response")?;
+    let usage = data
+        .usage
+        .ok_or_else(|| ProviderError::Parse("Missing 'usage' in Factory response".into()))?;
+
+    let window =
+        |tier: &Option<TokenAllowance>| tier.as_ref().and_then(|a| allowance_window(a, &usage));
+    let snapshot = UsageSnapshot {
+        provider: Provider::Factory,
+        source: "cli".to_string(),
+        primary: window(&usage.standard),
+        secondary: window(&usage.premium),
+        tertiary: None,
+        identity: None,
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    // The allowance is in tokens, not money, so it is only shown as windows
+    Ok(FetchResult {
+        usage: snapshot,
+        credits: None,
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+
+    const USAGE: &str = r#"{
+        "usage": {
+            "startDate": 1759276800000,
+            "endDate": 1761955200000,
+            "standard": {
+                "userTokens": 1200000,
+                "orgTotalTokensUsed": 5000000,
+                "orgOverageUsed": 0,
+                "totalAllowance": 20000000,
+                "usedRatio": 0.25
+            },
+            "premium": {
+                "userTokens": 0,
+                "orgTotalTokensUsed": 0,
+                "totalAllowance": 0,
+                "usedRatio": 0
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parses_subscription_usage() {
+        let data: UsageResponse = serde_json::from_str(USAGE).unwrap();
+        let usage = data.usage.unwrap();
+
+        let standard = allowance_window(usage.standard.as_ref().unwrap(), &usage).unwrap();
+        assert!((standard.used_percent - 25.0).abs() < 1e-9);
+        assert_eq!(standard.window_minutes, 31 * 24 * 60);
+        assert_eq!(
+            standard.resets_at,
+            Some("2025-11-01T00:00:00Z".parse().unwrap())
+        );
+        assert_eq!(
+            standard.reset_description.as_deref(),
+            Some("5000000 of 20000000 tokens")
+        );
+        // No premium allowance on this plan
+        assert!(allowance_window(usage.premium.as_ref().unwrap(), &usage).is_none());
+    }
+
+    #[test]
+    fn missing_usage_fields() {
+        let data: UsageResponse = serde_json::from_str(r#"{"usage": {}}"#).unwrap();
+        let usage = data.usage.unwrap();
+        assert!(usage.standard.is_none());
+        assert!(serde_json::from_str::<UsageResponse>("{}")
+            .unwrap()
+            .usage
+            .is_none());
+    }
+
+    fn jwt(exp: i64) -> String {
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(format!(r#"{{"sub":"user_01","exp":{}}}"#, exp));
+        format!("eyJhbGciOiJSUzI1NiJ9.{}.sig", payload)
+    }
+
+    #[test]
+    fn reads_auth_file_and_rejects_expired_login() {
+        let dir = std::env::temp_dir().join("ait_test_factory_auth");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("auth.json");
+        let now: DateTime<Utc> = "2026-01-15T12:00:00Z".parse().unwrap();
+
+        let valid = jwt(now.timestamp() + 3600);
+        std::fs::write(
+            &path,
+            format!(r#"{{"access_token": "{}", "refresh_token": "r"}}"#, valid),
+        )
+        .unwrap();
+        assert_eq!(load_token(&path, now).unwrap(), valid);
+
+        let expired = jwt(now.timestamp() - 60);
+        std::fs::write(&path, format!(r#"{{"access_token": "{}"}}"#, expired)).unwrap();
+        let err = load_token(&path, now).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ProviderError>(),
+            Some(ProviderError::AuthExpired { .. })
+        ));
+
+        std::fs::write(&path, "{}").unwrap();
+        assert!(load_token(&path, now).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
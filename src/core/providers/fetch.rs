@@ -1,10 +1,114 @@
+use chrono::{DateTime, Duration, DurationRound, Utc};
+
+use crate::core::models::cost::CostSummary;
 use crate::core::models::credits::CreditsSnapshot;
-use crate::core::models::usage::UsageSnapshot;
+use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
 
 /// Result of a provider fetch operation.
 pub struct FetchResult {
     pub usage: UsageSnapshot,
     pub credits: Option<CreditsSnapshot>,
+    /// Spend reported by the provider's API, for providers whose cost does
+    /// not come from local session logs.
+    pub cost: Option<CostSummary>,
+}
+
+/// Window for a per-minute quota of a cloud provider: `used` of `limit`
+/// units (requests or tokens) for `label`, resetting with the next minute.
+pub fn per_minute_window(
+    label: &str,
+    used: f64,
+    limit: f64,
+    unit: &str,
+    now: DateTime<Utc>,
+) -> RateWindow {
+    RateWindow {
+        used_percent: (used / limit * 100.0).clamp(0.0, 100.0),
+        window_minutes: 1,
+        resets_at: now
+            .duration_trunc(Duration::minutes(1))
+            .ok()
+            .map(|m| m + Duration::minutes(1)),
+        reset_description: Some(format!(
+            "{}: {} of {} {}/min",
+            label,
+            used.round() as u64,
+            limit.round() as u64,
+            unit
+        )),
+    }
+}
+
+/// Delay until a rate limit resets, as sent in response headers: seconds
+/// (`"12"`, `"0.5"`) or a Go-style duration (`"1m30.5s"`, `"250ms"`).
+pub fn parse_reset_delay(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<f64>() {
+        return (secs >= 0.0).then(|| Duration::milliseconds((secs * 1000.0) as i64));
+    }
+    let mut total_ms = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter(|&i| i > 0)?;
+        let amount: f64 = rest[..split].parse().ok()?;
+        rest = &rest[split..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let ms = match &rest[..unit_len] {
+            "h" => 3_600_000.0,
+            "m" => 60_000.0,
+            "s" => 1_000.0,
+            "ms" => 1.0,
+            _ => return None,
+        };
+        total_ms += amount * ms;
+        rest = &rest[unit_len..];
+    }
+    (!value.is_empty()).then(|| Duration::milliseconds(total_ms as i64))
+}
+
+/// Window from `x-ratelimit-*` style response headers: the `limit` and
+/// `remaining` counts of `unit`, resetting after the delay in `reset`.
+/// `None` when the headers are absent.
+///
+/// Vendors without a balance API (Mistral, Groq, Together) are read this way
+/// from the headers of a cheap authenticated request such as listing models.
+pub fn header_window(
+    headers: &reqwest::header::HeaderMap,
+    limit: &str,
+    remaining: &str,
+    reset: Option<&str>,
+    unit: &str,
+    window_minutes: u64,
+    now: DateTime<Utc>,
+) -> Option<RateWindow> {
+    let number = |name: &str| -> Option<f64> { headers.get(name)?.to_str().ok()?.trim().parse().ok() };
+    let limit = number(limit).filter(|&l| l > 0.0)?;
+    let used = (limit - number(remaining)?).max(0.0);
+    Some(RateWindow {
+        used_percent: (used / limit * 100.0).clamp(0.0, 100.0),
+        window_minutes,
+        resets_at: reset
+            .and_then(|name| headers.get(name)?.to_str().ok())
+            .and_then(parse_reset_delay)
+            .map(|delay| now + delay),
+        reset_description: Some(format!(
+            "{} of {} {}",
+            used.round() as u64,
+            limit.round() as u64,
+            unit
+        )),
+    })
+}
+
+/// API key from the environment variable `var`.
+pub fn env_api_key(var: &str) -> anyhow::Result<String> {
+    std::env::var(var)
+        .ok()
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| ProviderError::AuthMissing(format!("{} env var not set", var)).into())
 }
 
 /// Validate that a resolved endpoint URL uses HTTPS.
@@ -13,15 +117,46 @@ pub struct FetchResult {
 /// credentials, to prevent exfiltration over plain HTTP or other schemes.
 pub fn validate_endpoint(url: &str, provider_name: &str) -> anyhow::Result<()> {
     if !url.starts_with("https://") {
-        anyhow::bail!(
+        return Err(ProviderError::Unsupported(format!(
             "{}: endpoint must use HTTPS, got: {}",
-            provider_name,
-            url
-        );
+            provider_name, url
+        ))
+        .into());
     }
     Ok(())
 }
 
+/// Like `validate_endpoint`, but also accepts plain HTTP to a loopback
+/// address, for servers running on this machine.
+pub fn validate_local_endpoint(url: &str, provider_name: &str) -> anyhow::Result<()> {
+    if url.starts_with("http://") && is_loopback(url) {
+        return Ok(());
+    }
+    validate_endpoint(url, provider_name).map_err(|_| {
+        ProviderError::Unsupported(format!(
+            "{}: endpoint must use HTTPS unless it is on localhost, got: {}",
+            provider_name, url
+        ))
+        .into()
+    })
+}
+
+/// Whether `url` points at `localhost` or a loopback IP address.
+pub fn is_loopback(url: &str) -> bool {
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return false;
+    };
+    match parsed.host_str() {
+        Some("localhost") => true,
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback()),
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,4 +186,66 @@ mod tests {
     fn validate_endpoint_rejects_no_scheme() {
         assert!(validate_endpoint("api.example.com/v1", "Test").is_err());
     }
+
+    #[test]
+    fn validate_local_endpoint_allows_loopback_http() {
+        assert!(validate_local_endpoint("http://127.0.0.1:11434", "Test").is_ok());
+        assert!(validate_local_endpoint("http://localhost:11434", "Test").is_ok());
+        assert!(validate_local_endpoint("http://[::1]:11434", "Test").is_ok());
+        assert!(validate_local_endpoint("https://gpu-box.lan:11434", "Test").is_ok());
+    }
+
+    #[test]
+    fn validate_local_endpoint_rejects_remote_http() {
+        let err = validate_local_endpoint("http://gpu-box.lan:11434", "Test").unwrap_err();
+        assert!(err.to_string().contains("unless it is on localhost"));
+        assert!(validate_local_endpoint("http://127.0.0.1.evil.com", "Test").is_err());
+        assert!(validate_local_endpoint("http://localhost.evil.com", "Test").is_err());
+    }
+
+    #[test]
+    fn parses_reset_delays() {
+        assert_eq!(parse_reset_delay("12"), Some(Duration::seconds(12)));
+        assert_eq!(parse_reset_delay("0.5"), Some(Duration::milliseconds(500)));
+        assert_eq!(parse_reset_delay("7.66s"), Some(Duration::milliseconds(7660)));
+        assert_eq!(
+            parse_reset_delay("2m59.56s"),
+            Some(Duration::milliseconds(179_560))
+        );
+        assert_eq!(parse_reset_delay("1h2m"), Some(Duration::minutes(62)));
+        assert_eq!(parse_reset_delay("250ms"), Some(Duration::milliseconds(250)));
+        assert_eq!(parse_reset_delay(""), None);
+        assert_eq!(parse_reset_delay("soon"), None);
+        assert_eq!(parse_reset_delay("5d"), None);
+    }
+
+    #[test]
+    fn header_window_from_rate_limit_headers() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit-tokens", HeaderValue::from_static("6000"));
+        headers.insert("x-ratelimit-remaining-tokens", HeaderValue::from_static("4500"));
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("7.5s"));
+        let now: DateTime<Utc> = "2026-10-18T12:00:00Z".parse().unwrap();
+
+        let window = header_window(
+            &headers,
+            "x-ratelimit-limit-tokens",
+            "x-ratelimit-remaining-tokens",
+            Some("x-ratelimit-reset-tokens"),
+            "tokens/min",
+            1,
+            now,
+        )
+        .unwrap();
+        assert!((window.used_percent - 25.0).abs() < 1e-9);
+        assert_eq!(window.resets_at, Some(now + Duration::milliseconds(7500)));
+        assert_eq!(
+            window.reset_description.as_deref(),
+            Some("1500 of 6000 tokens/min")
+        );
+
+        let missing = header_window(&headers, "x-limit", "x-remaining", None, "requests", 1, now);
+        assert!(missing.is_none());
+    }
 }
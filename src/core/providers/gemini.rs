@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -41,7 +43,7 @@ struct TokenRefreshResponse {
     token_type: Option<String>,
 }
 
-fn gemini_oauth_path() -> PathBuf {
+pub fn gemini_oauth_path() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("~"))
         .join(".gemini")
@@ -59,29 +61,37 @@ fn is_expired(expiry_date: Option<u64>) -> bool {
     }
 }
 
-async fn refresh_access_token(creds: &mut GeminiOAuthCreds) -> Result<()> {
+async fn refresh_access_token(http: &HttpClient, creds: &mut GeminiOAuthCreds) -> Result<()> {
     let refresh_token = creds
         .refresh_token
         .as_deref()
-        .context("No refresh_token in Gemini OAuth credentials — re-authenticate with Gemini CLI")?;
+        .ok_or_else(|| ProviderError::AuthExpired {
+            message: "No refresh_token in Gemini OAuth credentials — re-authenticate with Gemini CLI"
+                .into(),
+            status: None,
+        })?;
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = http
         .post(GOOGLE_TOKEN_URL)
         .form(&[
             ("client_id", GEMINI_CLIENT_ID),
             ("client_secret", GEMINI_CLIENT_SECRET),
             ("refresh_token", refresh_token),
             ("grant_type", "refresh_token"),
-        ])
-        .send()
+        ]);
+    let response = http
+        .send(request)
         .await
         .context("Failed to send token refresh request to Google")?;
 
     let status = response.status();
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("Token refresh failed (HTTP {}): {}", status.as_u16(), body);
+        return Err(ProviderError::AuthExpired {
+            message: format!("Token refresh failed (HTTP {}): {}", status.as_u16(), body),
+            status: Some(status.as_u16()),
+        }
+        .into());
     }
 
     let token_resp: TokenRefreshResponse = response
@@ -102,7 +112,7 @@ async fn refresh_access_token(creds: &mut GeminiOAuthCreds) -> Result<()> {
     Ok(())
 }
 
-async fn resolve_gemini_access_token() -> Result<String> {
+async fn resolve_gemini_access_token(http: &HttpClient) -> Result<String> {
     let path = gemini_oauth_path();
     let content = std::fs::read_to_string(&path)
         .with_context(|| format!("Failed to read {}", path.display()))?;
@@ -110,11 +120,14 @@ async fn resolve_gemini_access_token() -> Result<String> {
         serde_json::from_str(&content).context("Failed to parse Gemini OAuth credentials")?;
 
     if creds.access_token.is_empty() && creds.refresh_token.is_none() {
-        anyhow::bail!("Empty access_token and no refresh_token in Gemini OAuth credentials");
+        return Err(ProviderError::AuthMissing(
+            "Empty access_token and no refresh_token in Gemini OAuth credentials".into(),
+        )
+        .into());
     }
 
     if creds.access_token.is_empty() || is_expired(creds.expiry_date) {
-        refresh_access_token(&mut creds).await?;
+        refresh_access_token(http, &mut creds).await?;
     }
 
     Ok(creds.access_token)
@@ -164,28 +177,28 @@ fn bucket_to_window(bucket: &QuotaBucket) -> RateWindow {
 }
 
 /// Fetch usage data from the Gemini quota API.
-pub async fn fetch() -> Result<FetchResult> {
-    let token = resolve_gemini_access_token()
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let token = resolve_gemini_access_token(http)
         .await
         .context("Gemini credentials not found — authenticate with Gemini CLI first")?;
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = http
         .post(QUOTA_URL)
         .header("Authorization", format!("Bearer {}", token))
         .header("Content-Type", "application/json")
-        .body("{}")
-        .send()
+        .body("{}");
+    let response = http
+        .send_repeatable(request)
         .await
         .context("Failed to send request to Gemini quota API")?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized — re-authenticate with Gemini CLI");
+        return Err(ProviderError::unauthorized("Unauthorized — re-authenticate with Gemini CLI").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let data: QuotaResponse = response
@@ -220,11 +233,14 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
     Ok(FetchResult {
         usage,
         credits: None,
+        cost: None,
     })
 }
 
@@ -0,0 +1,763 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::config::{expand_home, AppConfig};
+use crate::core::http::HttpClient;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::models::usage::{EndpointUsage, ProviderIdentity, RateWindow, UsageSnapshot};
+use crate::core::providers::error::{ErrorReport, ProviderError};
+use crate::core::providers::fetch::{parse_reset_delay, validate_endpoint, FetchResult};
+use crate::core::providers::Provider;
+
+/// A usage endpoint described entirely in config.toml (`[[generic_http]]`),
+/// for gateways and quota services without a built-in provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenericHttpConfig {
+    /// Label for this endpoint in output and errors.
+    pub name: String,
+    /// Endpoint URL; must use HTTPS. May hold secret references.
+    pub url: String,
+    /// "GET" or "POST".
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// Request headers. Values may hold secret references: `${env:NAME}`
+    /// or `${file:path}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Request body for POST, sent as-is. May hold secret references.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary: Option<WindowMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<WindowMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tertiary: Option<WindowMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<CreditsMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<IdentityMapping>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// Paths into the response for one rate window. Usage comes from
+/// `used_percent`, else `used` (or `limit - remaining`) of `limit`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowMapping {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used_percent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+    /// Reset time as an RFC 3339 string or Unix seconds/milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resets_at: Option<String>,
+    /// Delay until the reset, in seconds or as a duration like "1m30s".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resets_in: Option<String>,
+    /// Window length in minutes (literal).
+    #[serde(default)]
+    pub window_minutes: u64,
+    /// Unit shown after "used of limit", e.g. "requests" (literal).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+}
+
+/// Paths into the response for a credit balance. `remaining` falls back
+/// to `limit - used`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreditsMapping {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+    /// Currency code (literal), e.g. "usd".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Billing period (literal), e.g. "Monthly".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<String>,
+}
+
+/// Paths into the response for the account identity.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IdentityMapping {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<String>,
+}
+
+impl GenericHttpConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name must not be empty".to_string());
+        }
+        if !self.url.starts_with("https://") {
+            return Err("url must use HTTPS".to_string());
+        }
+        if !["GET", "POST"].contains(&self.method.to_uppercase().as_str()) {
+            return Err(format!(
+                "invalid method '{}' (must be GET or POST)",
+                self.method
+            ));
+        }
+        let windows = [&self.primary, &self.secondary, &self.tertiary];
+        let paths = windows
+            .into_iter()
+            .flatten()
+            .flat_map(|w| {
+                [
+                    &w.used_percent,
+                    &w.used,
+                    &w.remaining,
+                    &w.limit,
+                    &w.resets_at,
+                    &w.resets_in,
+                ]
+            })
+            .chain(
+                self.credits
+                    .iter()
+                    .flat_map(|c| [&c.remaining, &c.used, &c.limit]),
+            )
+            .chain(
+                self.identity
+                    .iter()
+                    .flat_map(|i| [&i.email, &i.organization, &i.plan]),
+            )
+            .flatten();
+        for path in paths {
+            parse_path(path).map_err(|e| format!("invalid path '{}': {}", path, e))?;
+        }
+        Ok(())
+    }
+
+    /// Secret references in the URL, headers and body, without the
+    /// braces (`env:NAME`, `file:path`).
+    pub fn secret_refs(&self) -> Vec<&str> {
+        let mut refs: Vec<&str> = Vec::new();
+        for value in std::iter::once(&self.url)
+            .chain(self.headers.values())
+            .chain(self.body.iter())
+        {
+            for reference in secret_refs(value) {
+                if !refs.contains(&reference) {
+                    refs.push(reference);
+                }
+            }
+        }
+        refs
+    }
+}
+
+/// The `${...}` references in `value`, without the braces.
+fn secret_refs(value: &str) -> Vec<&str> {
+    let mut refs = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        refs.push(&rest[start + 2..start + len]);
+        rest = &rest[start + len + 1..];
+    }
+    refs
+}
+
+/// Replace `${env:NAME}` and `${file:path}` references in `value` with the
+/// variable or the trimmed file contents.
+fn resolve_secrets(value: &str) -> Result<String> {
+    let mut out = value.to_string();
+    for reference in secret_refs(value) {
+        let secret = if let Some(var) = reference.strip_prefix("env:") {
+            std::env::var(var)
+                .ok()
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| ProviderError::AuthMissing(format!("{} env var not set", var)))?
+        } else if let Some(path) = reference.strip_prefix("file:") {
+            let path = expand_home(path);
+            std::fs::read_to_string(&path)
+                .map(|s| s.trim().to_string())
+                .map_err(|e| {
+                    ProviderError::AuthMissing(format!("cannot read {}: {}", path.display(), e))
+                })?
+        } else {
+            return Err(ProviderError::Unsupported(format!(
+                "unknown secret reference '${{{}}}' (use env: or file:)",
+                reference
+            ))
+            .into());
+        };
+        out = out.replace(&format!("${{{}}}", reference), &secret);
+    }
+    Ok(out)
+}
+
+/// One step of a response path.
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Key(String),
+    /// Array index; negative counts from the end.
+    Index(i64),
+}
+
+/// Parse a JSONPath-like expression: `$.data.limits[0].used`,
+/// `data["x-key"]`, `items[-1]`. The leading `$` is optional.
+fn parse_path(path: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let rest = path.trim();
+    let mut rest = rest.strip_prefix('$').unwrap_or(rest);
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or("unclosed '['")?;
+            let inner = after[..end].trim();
+            let quoted = inner
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .or_else(|| inner.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')));
+            segments.push(match quoted {
+                Some(key) => Segment::Key(key.to_string()),
+                None => Segment::Index(
+                    inner
+                        .parse()
+                        .map_err(|_| format!("invalid index '{}'", inner))?,
+                ),
+            });
+            rest = &after[end + 1..];
+        } else {
+            let after = rest.strip_prefix('.').unwrap_or(rest);
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return Err("empty key".to_string());
+            }
+            segments.push(Segment::Key(after[..end].to_string()));
+            rest = &after[end..];
+        }
+    }
+    Ok(segments)
+}
+
+/// The value at `path` in `root`; `None` when absent, null or the path is
+/// invalid.
+fn select<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut value = root;
+    for segment in parse_path(path).ok()? {
+        value = match segment {
+            Segment::Key(key) => value.get(key)?,
+            Segment::Index(i) => {
+                let items = value.as_array()?;
+                let i = if i < 0 { items.len() as i64 + i } else { i };
+                items.get(usize::try_from(i).ok()?)?
+            }
+        };
+    }
+    (!value.is_null()).then_some(value)
+}
+
+/// A number at `path`, also accepting numeric strings.
+fn number(root: &Value, path: Option<&String>) -> Option<f64> {
+    let value = select(root, path?)?;
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+        .filter(|n: &f64| n.is_finite())
+}
+
+/// A string at `path`; numbers are formatted.
+fn text(root: &Value, path: Option<&String>) -> Option<String> {
+    match select(root, path?)? {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// A timestamp: RFC 3339, or Unix seconds (milliseconds above 1e11).
+fn timestamp(root: &Value, path: Option<&String>) -> Option<DateTime<Utc>> {
+    let value = select(root, path?)?;
+    if let Some(s) = value.as_str() {
+        if let Ok(at) = DateTime::parse_from_rfc3339(s.trim()) {
+            return Some(at.with_timezone(&Utc));
+        }
+    }
+    let n = number(root, path)?;
+    let ms = if n.abs() > 1e11 { n } else { n * 1000.0 };
+    Utc.timestamp_millis_opt(ms as i64).single()
+}
+
+fn map_window(root: &Value, mapping: &WindowMapping, now: DateTime<Utc>) -> Option<RateWindow> {
+    let limit = number(root, mapping.limit.as_ref()).filter(|&l| l > 0.0);
+    let used = number(root, mapping.used.as_ref()).or_else(|| {
+        let remaining = number(root, mapping.remaining.as_ref())?;
+        Some((limit? - remaining).max(0.0))
+    });
+    let used_percent = match number(root, mapping.used_percent.as_ref()) {
+        Some(percent) => percent,
+        None => used? / limit? * 100.0,
+    };
+    let resets_at = timestamp(root, mapping.resets_at.as_ref()).or_else(|| {
+        let delay = match select(root, mapping.resets_in.as_ref()?)? {
+            Value::String(s) => parse_reset_delay(s)?,
+            other => parse_reset_delay(&other.as_f64()?.to_string())?,
+        };
+        Some(now + delay)
+    });
+    let reset_description = used.zip(limit).map(|(used, limit)| {
+        let mut description = format!("{} of {}", used.round() as u64, limit.round() as u64);
+        if let Some(unit) = &mapping.unit {
+            description.push(' ');
+            description.push_str(unit);
+        }
+        description
+    });
+    Some(RateWindow {
+        used_percent: used_percent.clamp(0.0, 100.0),
+        window_minutes: mapping.window_minutes,
+        resets_at,
+        reset_description,
+    })
+}
+
+fn map_credits(root: &Value, mapping: &CreditsMapping) -> Option<CreditsSnapshot> {
+    let used = number(root, mapping.used.as_ref());
+    let limit = number(root, mapping.limit.as_ref());
+    let remaining =
+        number(root, mapping.remaining.as_ref()).or_else(|| Some((limit? - used?).max(0.0)))?;
+    Some(CreditsSnapshot {
+        remaining,
+        has_credits: remaining > 0.0,
+        unlimited: false,
+        used,
+        limit,
+        currency: mapping.currency.as_ref().map(|c| c.to_lowercase()),
+        unit: None,
+        period: mapping.period.clone(),
+        converted: None,
+    })
+}
+
+fn map_identity(root: &Value, mapping: &IdentityMapping) -> Option<ProviderIdentity> {
+    let identity = ProviderIdentity {
+        email: text(root, mapping.email.as_ref()),
+        organization: text(root, mapping.organization.as_ref()),
+        plan: text(root, mapping.plan.as_ref()),
+    };
+    (identity.email.is_some() || identity.organization.is_some() || identity.plan.is_some())
+        .then_some(identity)
+}
+
+/// Map a response body onto a snapshot as described by `config`.
+fn map_response(
+    root: &Value,
+    config: &GenericHttpConfig,
+    now: DateTime<Utc>,
+) -> (UsageSnapshot, Option<CreditsSnapshot>) {
+    let window =
+        |mapping: &Option<WindowMapping>| mapping.as_ref().and_then(|m| map_window(root, m, now));
+    let usage = UsageSnapshot {
+        provider: Provider::GenericHttp,
+        source: "api".to_string(),
+        primary: window(&config.primary),
+        secondary: window(&config.secondary),
+        tertiary: window(&config.tertiary),
+        identity: config.identity.as_ref().and_then(|m| map_identity(root, m)),
+        local: None,
+        endpoints: Vec::new(),
+    };
+    let credits = config.credits.as_ref().and_then(|m| map_credits(root, m));
+    (usage, credits)
+}
+
+/// The `[[generic_http]]` endpoints of config.toml.
+pub fn configured(config: &AppConfig) -> &[GenericHttpConfig] {
+    &config.generic_http
+}
+
+/// The host of `url`, the only part of it safe to show: the path or query
+/// may carry a secret.
+pub fn url_host(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Fetch the endpoints configured in `[[generic_http]]` and map their JSON
+/// responses. A single endpoint fills the snapshot itself; several are
+/// fetched concurrently and listed under `endpoints`.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let config = AppConfig::load().unwrap_or_default();
+    match configured(&config) {
+        [] => Err(ProviderError::AuthMissing(
+            "no [[generic_http]] endpoint in config.toml".to_string(),
+        )
+        .into()),
+        [endpoint] => fetch_endpoint(http, endpoint).await,
+        endpoints => {
+            let handles: Vec<_> = endpoints
+                .iter()
+                .map(|endpoint| {
+                    let http = http.clone();
+                    let endpoint = endpoint.clone();
+                    tokio::spawn(async move { fetch_endpoint(&http, &endpoint).await })
+                })
+                .collect();
+            let mut results = Vec::new();
+            for handle in handles {
+                results.push(handle.await?);
+            }
+            combine(endpoints, results)
+        }
+    }
+}
+
+/// Merge per-endpoint results into one snapshot. Fails only when every
+/// endpoint failed, with the first endpoint's error.
+fn combine(
+    endpoints: &[GenericHttpConfig],
+    results: Vec<Result<FetchResult>>,
+) -> Result<FetchResult> {
+    if results.iter().all(|r| r.is_err()) {
+        let (endpoint, err) = endpoints
+            .iter()
+            .zip(results)
+            .find_map(|(endpoint, r)| Some((endpoint, r.err()?)))
+            .expect("at least one endpoint");
+        return Err(err.context(format!(
+            "All [[generic_http]] endpoints failed (first: '{}')",
+            endpoint.name
+        )));
+    }
+    let endpoints = endpoints
+        .iter()
+        .zip(results)
+        .map(|(endpoint, result)| match result {
+            Ok(fetched) => EndpointUsage {
+                name: endpoint.name.clone(),
+                primary: fetched.usage.primary,
+                secondary: fetched.usage.secondary,
+                tertiary: fetched.usage.tertiary,
+                identity: fetched.usage.identity,
+                credits: fetched.credits,
+                error: None,
+            },
+            Err(err) => EndpointUsage {
+                name: endpoint.name.clone(),
+                primary: None,
+                secondary: None,
+                tertiary: None,
+                identity: None,
+                credits: None,
+                error: Some(ErrorReport::from_error(&err).message),
+            },
+        })
+        .collect();
+    Ok(FetchResult {
+        usage: UsageSnapshot {
+            provider: Provider::GenericHttp,
+            source: "api".to_string(),
+            primary: None,
+            secondary: None,
+            tertiary: None,
+            identity: None,
+            local: None,
+            endpoints,
+        },
+        credits: None,
+        cost: None,
+    })
+}
+
+async fn fetch_endpoint(http: &HttpClient, endpoint: &GenericHttpConfig) -> Result<FetchResult> {
+    endpoint.validate().map_err(|msg| {
+        ProviderError::Unsupported(format!("[[generic_http]] '{}': {}", endpoint.name, msg))
+    })?;
+
+    let url = resolve_secrets(&endpoint.url)?;
+    validate_endpoint(&url, "Generic HTTP")?;
+    // reqwest errors quote the full URL, secrets included; name the host only
+    let host = url_host(&url);
+    let mut request = if endpoint.method.eq_ignore_ascii_case("POST") {
+        http.post(&url)
+    } else {
+        http.get(&url)
+    };
+    request = request.header("Accept", "application/json");
+    for (name, value) in &endpoint.headers {
+        request = request.header(name.as_str(), resolve_secrets(value)?);
+    }
+    if let Some(body) = &endpoint.body {
+        request = request.body(resolve_secrets(body)?);
+    }
+    let response = http
+        .send(request)
+        .await
+        .map_err(reqwest::Error::without_url)
+        .with_context(|| format!("Failed to send request to {}", host))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProviderError::unauthorized(format!(
+            "Unauthorized - check the headers of [[generic_http]] '{}'",
+            endpoint.name
+        ))
+        .into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    let data: Value = response
+        .json()
+        .await
+        .map_err(reqwest::Error::without_url)
+        .with_context(|| format!("Failed to parse response from {} as JSON", host))?;
+    let (usage, credits) = map_response(&data, endpoint, Utc::now());
+
+    Ok(FetchResult {
+        usage,
+        credits,
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const RESPONSE: &str = r#"{
+        "user": {"email": "dev@example.com", "team": {"name": "platform"}},
+        "plan": "internal",
+        "limits": [
+            {"name": "tpm", "used": 12000, "limit": 48000, "reset_in": "42s"},
+            {"name": "daily", "usage_pct": "65.5", "resets": "2026-10-19T00:00:00Z"}
+        ],
+        "budget": {"spend": 37.5, "max": "100"}
+    }"#;
+
+    fn config(toml: &str) -> GenericHttpConfig {
+        toml::from_str(&format!("name = \"gateway\"\n{}", toml)).unwrap()
+    }
+
+    #[test]
+    fn parses_paths() {
+        assert_eq!(
+            parse_path("$.a[0]['b.c'][-1].d").unwrap(),
+            vec![
+                Segment::Key("a".into()),
+                Segment::Index(0),
+                Segment::Key("b.c".into()),
+                Segment::Index(-1),
+                Segment::Key("d".into()),
+            ]
+        );
+        assert_eq!(parse_path("a.b").unwrap().len(), 2);
+        assert!(parse_path("$.a[").is_err());
+        assert!(parse_path("$.a..b").is_err());
+        assert!(parse_path("$[x]").is_err());
+    }
+
+    #[test]
+    fn maps_response() {
+        let endpoint = config(
+            r#"
+            url = "https://gateway.example.com/usage"
+            [primary]
+            used = "$.limits[0].used"
+            limit = "$.limits[0].limit"
+            resets_in = "$.limits[0].reset_in"
+            window_minutes = 1
+            unit = "tokens/min"
+            [secondary]
+            used_percent = "$.limits[-1].usage_pct"
+            resets_at = "$.limits[-1].resets"
+            [tertiary]
+            used = "$.missing"
+            [credits]
+            used = "$.budget.spend"
+            limit = "$.budget.max"
+            currency = "USD"
+            [identity]
+            email = "$.user.email"
+            organization = "$.user.team.name"
+            plan = "$.plan"
+            "#,
+        );
+        assert!(endpoint.validate().is_ok());
+        let now: DateTime<Utc> = "2026-10-18T12:00:00Z".parse().unwrap();
+        let root: Value = serde_json::from_str(RESPONSE).unwrap();
+        let (usage, credits) = map_response(&root, &endpoint, now);
+
+        let primary = usage.primary.unwrap();
+        assert!((primary.used_percent - 25.0).abs() < 1e-9);
+        assert_eq!(primary.window_minutes, 1);
+        assert_eq!(primary.resets_at, Some(now + Duration::seconds(42)));
+        assert_eq!(
+            primary.reset_description.as_deref(),
+            Some("12000 of 48000 tokens/min")
+        );
+        let secondary = usage.secondary.unwrap();
+        assert!((secondary.used_percent - 65.5).abs() < 1e-9);
+        assert_eq!(
+            secondary.resets_at,
+            Some("2026-10-19T00:00:00Z".parse().unwrap())
+        );
+        assert!(usage.tertiary.is_none());
+
+        let credits = credits.unwrap();
+        assert_eq!(credits.remaining, 62.5);
+        assert_eq!(credits.limit, Some(100.0));
+        assert_eq!(credits.currency.as_deref(), Some("usd"));
+
+        let identity = usage.identity.unwrap();
+        assert_eq!(identity.email.as_deref(), Some("dev@example.com"));
+        assert_eq!(identity.organization.as_deref(), Some("platform"));
+        assert_eq!(identity.plan.as_deref(), Some("internal"));
+    }
+
+    #[test]
+    fn reads_unix_timestamps() {
+        let root: Value =
+            serde_json::from_str(r#"{"s": 1792195200, "ms": 1792195200000}"#).unwrap();
+        let expected = Utc.timestamp_opt(1792195200, 0).single();
+        assert_eq!(timestamp(&root, Some(&"s".to_string())), expected);
+        assert_eq!(timestamp(&root, Some(&"ms".to_string())), expected);
+    }
+
+    #[test]
+    fn resolves_secret_references() {
+        let var = "AIT_GENERIC_HTTP_TEST_TOKEN";
+        std::env::set_var(var, "s3cret");
+        let file = std::env::temp_dir().join("ait-generic-http-test-secret");
+        std::fs::write(&file, "from-file\n").unwrap();
+
+        let header = format!("Bearer ${{env:{}}} ${{file:{}}}", var, file.display());
+        assert_eq!(resolve_secrets(&header).unwrap(), "Bearer s3cret from-file");
+        std::env::remove_var(var);
+        std::fs::remove_file(&file).unwrap();
+
+        let err = resolve_secrets(&header).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ProviderError>(),
+            Some(ProviderError::AuthMissing(_))
+        ));
+        assert!(resolve_secrets("${vault:x}").is_err());
+        assert_eq!(resolve_secrets("plain").unwrap(), "plain");
+    }
+
+    #[tokio::test]
+    async fn send_errors_never_include_secrets() {
+        let var = "AIT_GENERIC_HTTP_TEST_QUERY_KEY";
+        std::env::set_var(var, "query-s3cret");
+        // Nothing listens on port 9 (discard), so the send fails
+        let endpoint = config(&format!(
+            r#"url = "https://127.0.0.1:9/usage?key=${{env:{}}}""#,
+            var
+        ));
+        let http = HttpClient::new(&Default::default()).unwrap();
+        let err = fetch_endpoint(&http, &endpoint).await.err().unwrap();
+        std::env::remove_var(var);
+
+        let report = crate::core::providers::error::ErrorReport::from_error(&err);
+        assert!(!format!("{:#}", err).contains("query-s3cret"), "{:#}", err);
+        assert!(
+            !report.message.contains("query-s3cret"),
+            "{}",
+            report.message
+        );
+        assert!(report.message.contains("127.0.0.1"), "{}", report.message);
+    }
+
+    #[test]
+    fn combines_endpoints() {
+        let endpoints = vec![
+            config(r#"url = "https://gateway.example.com/usage""#),
+            GenericHttpConfig {
+                name: "quota".into(),
+                ..config(r#"url = "https://quota.example.com/v1""#)
+            },
+        ];
+        let root: Value = serde_json::from_str(r#"{"pct": 40}"#).unwrap();
+        let mut mapped = endpoints[0].clone();
+        mapped.primary = Some(WindowMapping {
+            used_percent: Some("$.pct".into()),
+            ..Default::default()
+        });
+        let (usage, credits) = map_response(&root, &mapped, Utc::now());
+        let ok = || {
+            Ok(FetchResult {
+                usage: usage.clone(),
+                credits: credits.clone(),
+                cost: None,
+            })
+        };
+        let failed = || {
+            Err(ProviderError::http_status(reqwest::StatusCode::BAD_GATEWAY, "down".into()).into())
+        };
+
+        let fetched = combine(&endpoints, vec![ok(), failed()]).unwrap();
+        assert!(fetched.usage.primary.is_none());
+        assert!(fetched.credits.is_none());
+        let [gateway, quota] = fetched.usage.endpoints.as_slice() else {
+            panic!("expected two endpoints");
+        };
+        assert_eq!(gateway.name, "gateway");
+        assert!((gateway.primary.as_ref().unwrap().used_percent - 40.0).abs() < 1e-9);
+        assert!(gateway.error.is_none());
+        assert_eq!(quota.name, "quota");
+        assert!(quota.error.as_ref().unwrap().contains("502"));
+
+        let err = combine(&endpoints, vec![failed(), failed()]).err().unwrap();
+        assert!(format!("{:#}", err)
+            .starts_with("All [[generic_http]] endpoints failed (first: 'gateway'): "));
+        assert!(err.downcast_ref::<ProviderError>().is_some());
+    }
+
+    #[test]
+    fn validates_config() {
+        let mut endpoint = config(
+            r#"
+            url = "https://gateway.example.com/usage?key=${env:GATEWAY_KEY}"
+            headers = { Authorization = "Bearer ${env:GATEWAY_TOKEN}" }
+            "#,
+        );
+        assert_eq!(endpoint.method, "GET");
+        assert!(endpoint.validate().is_ok());
+        assert_eq!(
+            endpoint.secret_refs(),
+            vec!["env:GATEWAY_KEY", "env:GATEWAY_TOKEN"]
+        );
+
+        endpoint.name = " ".into();
+        assert!(endpoint.validate().unwrap_err().contains("name"));
+        endpoint.name = "gateway".into();
+        endpoint.method = "PUT".into();
+        assert!(endpoint.validate().unwrap_err().contains("method"));
+        endpoint.method = "post".into();
+        endpoint.url = "http://gateway.example.com".into();
+        assert!(endpoint.validate().unwrap_err().contains("HTTPS"));
+        endpoint.url = "https://gateway.example.com".into();
+        endpoint.credits = Some(CreditsMapping {
+            remaining: Some("$.a[".into()),
+            ..Default::default()
+        });
+        assert!(endpoint.validate().unwrap_err().contains("$.a["));
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+
+use crate::core::http::HttpClient;
+use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{env_api_key, header_window, FetchResult};
+use crate::core::providers::Provider;
+
+const MODELS_URL: &str = "https://api.groq.com/openai/v1/models";
+
+/// Tokens per minute and requests per day, from the `x-ratelimit-*`
+/// headers.
+fn rate_windows(
+    headers: &HeaderMap,
+    now: DateTime<Utc>,
+) -> (Option<RateWindow>, Option<RateWindow>) {
+    let tokens = header_window(
+        headers,
+        "x-ratelimit-limit-tokens",
+        "x-ratelimit-remaining-tokens",
+        Some("x-ratelimit-reset-tokens"),
+        "tokens/min",
+        1,
+        now,
+    );
+    let requests = header_window(
+        headers,
+        "x-ratelimit-limit-requests",
+        "x-ratelimit-remaining-requests",
+        Some("x-ratelimit-reset-requests"),
+        "requests/day",
+        24 * 60,
+        now,
+    );
+    (tokens, requests)
+}
+
+/// Fetch the API key's rate limits from Groq.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let api_key = env_api_key("GROQ_API_KEY")?;
+
+    let request = http
+        .get(MODELS_URL)
+        .header("Authorization", format!("Bearer {}", api_key));
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to Groq API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProviderError::unauthorized("Unauthorized - check your GROQ_API_KEY").into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    let (primary, secondary) = rate_windows(response.headers(), Utc::now());
+    let usage = UsageSnapshot {
+        provider: Provider::Groq,
+        source: "api".to_string(),
+        primary,
+        secondary,
+        tertiary: None,
+        identity: None,
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage,
+        credits: None,
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn reads_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("x-ratelimit-limit-requests", "14400"),
+            ("x-ratelimit-limit-tokens", "18000"),
+            ("x-ratelimit-remaining-requests", "14370"),
+            ("x-ratelimit-remaining-tokens", "17997"),
+            ("x-ratelimit-reset-requests", "2m59.56s"),
+            ("x-ratelimit-reset-tokens", "7.66s"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+        let now: DateTime<Utc> = "2026-10-18T12:00:00Z".parse().unwrap();
+
+        let (tokens, requests) = rate_windows(&headers, now);
+        let tokens = tokens.unwrap();
+        assert_eq!(tokens.window_minutes, 1);
+        assert_eq!(tokens.resets_at, Some(now + Duration::milliseconds(7660)));
+        let requests = requests.unwrap();
+        assert!((requests.used_percent - 30.0 / 144.0).abs() < 1e-9);
+        assert_eq!(
+            requests.reset_description.as_deref(),
+            Some("30 of 14400 requests/day")
+        );
+    }
+
+    #[test]
+    fn no_headers_no_windows() {
+        let (tokens, requests) = rate_windows(&HeaderMap::new(), Utc::now());
+        assert!(tokens.is_none() && requests.is_none());
+    }
+}
@@ -4,28 +4,33 @@ use serde::Deserialize;
 use std::path::PathBuf;
 
 use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
 // --- XML / config discovery ---
 
-/// Search directories that may contain JetBrains AI quota config files.
-fn candidate_dirs() -> Vec<PathBuf> {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("~"));
-    let mut dirs = Vec::new();
-
-    // ~/.config/JetBrains/*/options/
-    let config_jb = home.join(".config").join("JetBrains");
-    collect_option_dirs(&config_jb, &mut dirs);
-
-    // ~/.local/share/JetBrains/*/options/
-    let share_jb = home.join(".local").join("share").join("JetBrains");
-    collect_option_dirs(&share_jb, &mut dirs);
+pub const QUOTA_FILE: &str = "AIAssistantQuotaManager2.xml";
 
-    // ~/.config/Google/*/options/  (Android Studio)
-    let config_google = home.join(".config").join("Google");
-    collect_option_dirs(&config_google, &mut dirs);
+/// Vendor directories holding one subdirectory per IDE installation.
+pub fn ide_roots() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("~"));
+    vec![
+        // ~/.config/JetBrains/*/options/
+        home.join(".config").join("JetBrains"),
+        // ~/.local/share/JetBrains/*/options/
+        home.join(".local").join("share").join("JetBrains"),
+        // ~/.config/Google/*/options/  (Android Studio)
+        home.join(".config").join("Google"),
+    ]
+}
 
+/// Search directories that may contain JetBrains AI quota config files.
+pub fn candidate_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for root in ide_roots() {
+        collect_option_dirs(&root, &mut dirs);
+    }
     dirs
 }
 
@@ -42,7 +47,7 @@ fn collect_option_dirs(parent: &PathBuf, dirs: &mut Vec<PathBuf>) {
 
 fn find_quota_file() -> Option<PathBuf> {
     for dir in candidate_dirs() {
-        let path = dir.join("AIAssistantQuotaManager2.xml");
+        let path = dir.join(QUOTA_FILE);
         if path.exists() {
             return Some(path);
         }
@@ -100,7 +105,7 @@ fn parse_quota_xml(content: &str) -> Result<(Option<QuotaInfo>, Option<NextRefil
 }
 
 /// Fetch usage data from JetBrains AI Assistant quota config files.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(_http: &HttpClient) -> Result<FetchResult> {
     let path = find_quota_file().context("No JetBrains AI config found")?;
 
     let content = std::fs::read_to_string(&path)
@@ -134,11 +139,14 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary: None,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
     Ok(FetchResult {
         usage,
         credits: None,
+        cost: None,
     })
 }
 
@@ -5,11 +5,15 @@ use serde::Deserialize;
 use crate::core::auth::decode_jwt_claims;
 use crate::core::models::credits::CreditsSnapshot;
 use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
 const USAGE_URL: &str =
     "https://www.kimi.com/apiv2/kimi.gateway.billing.v1.BillingService/GetUsages";
+/// Kimi bills its accounts in renminbi.
+const CURRENCY: &str = "CNY";
 
 #[derive(Deserialize)]
 struct UsageDetail {
@@ -85,13 +89,15 @@ fn parse_credits(detail: &UsageDetail) -> CreditsSnapshot {
         unlimited: false,
         used,
         limit,
-        currency: None,
+        currency: Some(CURRENCY.to_string()),
+        unit: None,
         period: None,
+        converted: None,
     }
 }
 
 /// Fetch usage data from the Kimi billing API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let token = std::env::var("KIMI_TOKEN").context("KIMI_TOKEN env var not set")?;
 
     let claims = decode_jwt_claims(&token).context("Failed to decode KIMI_TOKEN JWT")?;
@@ -103,8 +109,7 @@ pub async fn fetch() -> Result<FetchResult> {
     let ssid = claims["ssid"].as_str().unwrap_or_default().to_string();
     let sub = claims["sub"].as_str().unwrap_or_default().to_string();
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = http
         .post(USAGE_URL)
         .header("Authorization", format!("Bearer {}", token))
         .header("Cookie", format!("refresh_token={}", token))
@@ -112,18 +117,19 @@ pub async fn fetch() -> Result<FetchResult> {
         .header("x-ssid", &ssid)
         .header("x-sub", &sub)
         .header("Content-Type", "application/json")
-        .body(r#"{"scope":["FEATURE_CODING"]}"#)
-        .send()
+        .body(r#"{"scope":["FEATURE_CODING"]}"#);
+    let response = http
+        .send_repeatable(request)
         .await
         .context("Failed to send request to Kimi API")?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized — check KIMI_TOKEN");
+        return Err(ProviderError::unauthorized("Unauthorized — check KIMI_TOKEN").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let data: KimiUsageResponse = response
@@ -147,9 +153,11 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary: None,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
-    Ok(FetchResult { usage, credits })
+    Ok(FetchResult { usage, credits, cost: None })
 }
 
 #[cfg(test)]
@@ -243,6 +251,38 @@ mod tests {
         assert!(!credits.unlimited);
         assert!((credits.used.unwrap() - 250.0).abs() < 1e-10);
         assert!((credits.limit.unwrap() - 1000.0).abs() < 1e-10);
+        assert_eq!(credits.currency.as_deref(), Some("CNY"));
+    }
+
+    #[test]
+    fn credits_render_in_yuan() {
+        let detail = UsageDetail {
+            limit: None,
+            used: None,
+            remaining: Some(750.0),
+            reset_time: None,
+        };
+        let credits = parse_credits(&detail);
+        let snapshot = UsageSnapshot {
+            provider: Provider::Kimi,
+            source: "api".to_string(),
+            primary: None,
+            secondary: None,
+            tertiary: None,
+            identity: None,
+            local: None,
+            endpoints: Vec::new(),
+        };
+        let output = crate::cli::renderer::render_provider(
+            &snapshot,
+            Some(&credits),
+            None,
+            None,
+            None,
+            false,
+            false,
+        );
+        assert!(output.contains("¥750.00 remaining"), "{}", output);
     }
 
     #[test]
@@ -3,6 +3,8 @@ use serde::Deserialize;
 
 use crate::core::models::credits::CreditsSnapshot;
 use crate::core::models::usage::UsageSnapshot;
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -45,30 +47,32 @@ fn parse_credits(data: &KimiK2CreditsResponse) -> CreditsSnapshot {
         used,
         limit: None,
         currency: None,
+        unit: None,
         period: None,
+        converted: None,
     }
 }
 
 /// Fetch credit balance from the Kimi K2 API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let api_key = std::env::var("KIMI_K2_API_KEY").context("KIMI_K2_API_KEY env var not set")?;
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = http
         .get(CREDITS_URL)
         .header("Authorization", format!("Bearer {}", api_key))
-        .header("Accept", "application/json")
-        .send()
+        .header("Accept", "application/json");
+    let response = http
+        .send(request)
         .await
         .context("Failed to send request to Kimi K2 API")?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized — check KIMI_K2_API_KEY");
+        return Err(ProviderError::unauthorized("Unauthorized — check KIMI_K2_API_KEY").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let data: KimiK2CreditsResponse = response
@@ -85,11 +89,14 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary: None,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
     Ok(FetchResult {
         usage,
         credits: Some(credits),
+        cost: None,
     })
 }
 
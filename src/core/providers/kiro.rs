@@ -3,6 +3,8 @@ use serde::Deserialize;
 use std::time::Duration;
 
 use crate::core::models::usage::{ProviderIdentity, RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -120,18 +122,22 @@ fn extract_last_number(s: &str) -> Option<f64> {
 }
 
 /// Fetch usage data by running the `kiro-cli` command.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     if crate::core::process::which("kiro-cli").is_none() {
-        anyhow::bail!("kiro-cli not found in PATH");
+        return Err(ProviderError::AuthMissing("kiro-cli not found in PATH".into()).into());
     }
 
     let output = tokio::process::Command::new("kiro-cli")
         .args(["chat", "--no-interactive", "/usage"])
         .output();
 
-    let output = tokio::time::timeout(KIRO_TIMEOUT, output)
+    // kiro-cli is slow to start; a configured timeout can only extend the floor
+    let timeout = http.timeout().max(KIRO_TIMEOUT);
+    let output = tokio::time::timeout(timeout, output)
         .await
-        .context("kiro-cli timed out after 20 seconds")?
+        .map_err(|_| {
+            ProviderError::Timeout(format!("kiro-cli timed out after {} seconds", timeout.as_secs()))
+        })?
         .context("Failed to execute kiro-cli")?;
 
     if !output.status.success() {
@@ -162,11 +168,14 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary: None,
         tertiary: None,
         identity,
+        local: None,
+        endpoints: Vec::new(),
     };
 
     Ok(FetchResult {
         usage,
         credits: None,
+        cost: None,
     })
 }
 
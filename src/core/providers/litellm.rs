@@ -0,0 +1,645 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::core::cost::org::{self, SpendReport};
+use crate::core::http::HttpClient;
+use crate::core::models::cost::CostSummary;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::models::usage::{ProviderIdentity, RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{
+    env_api_key, header_window, is_loopback, validate_local_endpoint, FetchResult,
+};
+use crate::core::providers::Provider;
+
+/// Base URL of the proxy, as read by the LiteLLM CLI.
+pub const PROXY_URL_ENV: &str = "LITELLM_PROXY_URL";
+pub const API_KEY_ENV: &str = "LITELLM_PROXY_API_KEY";
+
+/// Days covered by the proxy's global spend views.
+const GLOBAL_DAYS: u32 = 30;
+
+/// The configured proxy's base URL without a trailing slash.
+pub fn proxy_url() -> Option<String> {
+    std::env::var(PROXY_URL_ENV)
+        .ok()
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// A virtual key's row, as returned by `/key/info` and in `/user/info`.
+#[derive(Deserialize, Default)]
+struct KeyInfo {
+    key_alias: Option<String>,
+    /// Redacted key, e.g. "sk-...a1b2".
+    key_name: Option<String>,
+    spend: Option<f64>,
+    max_budget: Option<f64>,
+    /// Budget period, e.g. "30d" or "1h".
+    budget_duration: Option<String>,
+    budget_reset_at: Option<String>,
+    tpm_limit: Option<u64>,
+    rpm_limit: Option<u64>,
+    user_id: Option<String>,
+    team_id: Option<String>,
+    model_spend: Option<HashMap<String, f64>>,
+    created_at: Option<String>,
+}
+
+impl KeyInfo {
+    fn label(&self) -> String {
+        self.key_alias
+            .clone()
+            .or_else(|| self.key_name.clone())
+            .unwrap_or_else(|| "unnamed".to_string())
+    }
+}
+
+#[derive(Deserialize)]
+struct KeyInfoResponse {
+    info: KeyInfo,
+}
+
+#[derive(Deserialize, Default)]
+struct UserInfoResponse {
+    user_info: Option<UserRow>,
+    #[serde(default)]
+    keys: Vec<KeyInfo>,
+    #[serde(default)]
+    teams: Vec<Team>,
+}
+
+#[derive(Deserialize)]
+struct UserRow {
+    user_email: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Team {
+    team_id: String,
+    team_alias: Option<String>,
+    spend: Option<f64>,
+}
+
+impl Team {
+    fn label(&self) -> String {
+        self.team_alias
+            .clone()
+            .unwrap_or_else(|| self.team_id.clone())
+    }
+}
+
+/// Proxy-wide spend, from `/global/spend` (admin only).
+#[derive(Deserialize)]
+struct GlobalSpend {
+    spend: Option<f64>,
+    max_budget: Option<f64>,
+}
+
+/// A row of `/global/spend/models`: the last 30 days per model.
+#[derive(Deserialize)]
+struct ModelSpend {
+    model: Option<String>,
+    total_spend: Option<f64>,
+}
+
+/// A row of `/global/spend/logs`: the last 30 days per day.
+#[derive(Deserialize)]
+struct DaySpend {
+    date: String,
+    spend: Option<f64>,
+}
+
+/// A budget period such as "30s", "10m", "24h", "30d", "1w" or "1mo"
+/// (taken as 30 days). `None` when out of range.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let amount: i64 = value[..split].parse().ok()?;
+    match &value[split..] {
+        "s" => Duration::try_seconds(amount),
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        "mo" => Duration::try_days(amount.checked_mul(30)?),
+        _ => None,
+    }
+}
+
+/// A timestamp as stored by the proxy: RFC 3339, or naive UTC.
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                .ok()
+                .map(|t| t.and_utc())
+        })
+}
+
+/// Spend against `max_budget` as a window resetting with the budget.
+fn budget_window(
+    spend: f64,
+    max_budget: Option<f64>,
+    duration: Option<&str>,
+    reset_at: Option<&str>,
+) -> Option<RateWindow> {
+    let max_budget = max_budget.filter(|&b| b > 0.0)?;
+    Some(RateWindow {
+        used_percent: (spend / max_budget * 100.0).clamp(0.0, 100.0),
+        window_minutes: duration
+            .and_then(parse_duration)
+            .map(|d| d.num_minutes().max(0) as u64)
+            .unwrap_or(0),
+        resets_at: reset_at.and_then(parse_time),
+        reset_description: Some(format!("${:.2} of ${:.2}", spend, max_budget)),
+    })
+}
+
+fn budget_credits(
+    spend: f64,
+    max_budget: Option<f64>,
+    period: Option<&str>,
+) -> Option<CreditsSnapshot> {
+    let max_budget = max_budget.filter(|&b| b > 0.0)?;
+    let remaining = (max_budget - spend).max(0.0);
+    Some(CreditsSnapshot {
+        remaining,
+        has_credits: remaining > 0.0,
+        unlimited: false,
+        used: Some(spend),
+        limit: Some(max_budget),
+        currency: Some("usd".to_string()),
+        unit: None,
+        period: period.map(str::to_string),
+        converted: None,
+    })
+}
+
+/// A TPM or RPM limit of the key. Current use comes from the proxy's
+/// `x-ratelimit-*` headers when it sends them; otherwise only the limit is
+/// shown.
+fn limit_window(
+    headers: &HeaderMap,
+    limit: Option<u64>,
+    kind: &str,
+    unit: &str,
+    now: DateTime<Utc>,
+) -> Option<RateWindow> {
+    let limit = limit.filter(|&l| l > 0)?;
+    header_window(
+        headers,
+        &format!("x-ratelimit-limit-{}", kind),
+        &format!("x-ratelimit-remaining-{}", kind),
+        None,
+        unit,
+        1,
+        now,
+    )
+    .or_else(|| {
+        Some(RateWindow {
+            used_percent: 0.0,
+            window_minutes: 1,
+            resets_at: None,
+            reset_description: Some(format!("limit {} {}", limit, unit)),
+        })
+    })
+}
+
+/// Days since the key's spend started accumulating: the start of the
+/// current budget period, else the key's creation.
+fn spend_days(info: &KeyInfo, now: DateTime<Utc>) -> u32 {
+    let period_start = info
+        .budget_reset_at
+        .as_deref()
+        .and_then(parse_time)
+        .zip(info.budget_duration.as_deref().and_then(parse_duration))
+        .and_then(|(reset_at, duration)| reset_at.checked_sub_signed(duration));
+    period_start
+        .or_else(|| info.created_at.as_deref().and_then(parse_time))
+        .map(|start| ((now.date_naive() - start.date_naive()).num_days() + 1).max(1) as u32)
+        .unwrap_or(0)
+}
+
+/// The key's spend per model, with its owner's keys and teams.
+fn key_summary(info: &KeyInfo, user: Option<&UserInfoResponse>, now: DateTime<Utc>) -> CostSummary {
+    let report = SpendReport {
+        total: info.spend.unwrap_or(0.0),
+        days: spend_days(info, now),
+        models: info
+            .model_spend
+            .iter()
+            .flatten()
+            .map(|(model, spend)| (model.clone(), *spend))
+            .collect(),
+        workspaces: user
+            .iter()
+            .flat_map(|u| &u.teams)
+            .map(|t| (t.label(), t.spend.unwrap_or(0.0)))
+            .collect(),
+        api_keys: user
+            .iter()
+            .flat_map(|u| &u.keys)
+            .map(|k| (k.label(), k.spend.unwrap_or(0.0)))
+            .collect(),
+        daily: Vec::new(),
+    };
+    org::spend_summary(report, now.date_naive())
+}
+
+/// The last 30 days of proxy-wide spend per model and day.
+fn global_summary(models: &[ModelSpend], daily: &[DaySpend], today: NaiveDate) -> CostSummary {
+    let daily: Vec<(NaiveDate, f64)> = daily
+        .iter()
+        .filter_map(|d| {
+            let date = NaiveDate::parse_from_str(d.date.get(..10)?, "%Y-%m-%d").ok()?;
+            Some((date, d.spend.unwrap_or(0.0)))
+        })
+        .collect();
+    let models: Vec<(String, f64)> = models
+        .iter()
+        .filter_map(|m| Some((m.model.clone()?, m.total_spend.unwrap_or(0.0))))
+        .collect();
+    let total = if daily.is_empty() {
+        models.iter().map(|(_, spend)| spend).sum()
+    } else {
+        daily.iter().map(|(_, spend)| spend).sum()
+    };
+    let report = SpendReport {
+        total,
+        days: GLOBAL_DAYS,
+        models,
+        daily,
+        ..Default::default()
+    };
+    org::spend_summary(report, today)
+}
+
+/// Authenticated requests to one proxy.
+struct Proxy<'a> {
+    http: &'a HttpClient,
+    /// Set for a proxy on this machine, which is never reached through the
+    /// configured HTTP proxy.
+    local: Option<reqwest::Client>,
+    base: String,
+    key: String,
+}
+
+impl Proxy<'_> {
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<(T, HeaderMap)> {
+        let url = format!("{}{}", self.base, path);
+        let request = match &self.local {
+            Some(client) => client.get(&url),
+            None => self.http.get(&url),
+        }
+        .header("Authorization", format!("Bearer {}", self.key))
+        .query(query);
+        let response = self
+            .http
+            .send(request)
+            .await
+            .with_context(|| format!("Failed to connect to LiteLLM proxy at {}", self.base))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(ProviderError::unauthorized(format!(
+                "Unauthorized - check your {}",
+                API_KEY_ENV
+            ))
+            .into());
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ProviderError::http_status(status, body).into());
+        }
+        let headers = response.headers().clone();
+        let data = response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse LiteLLM {} response", path))?;
+        Ok((data, headers))
+    }
+}
+
+fn is_not_found(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<ProviderError>()
+        .and_then(ProviderError::status)
+        == Some(404)
+}
+
+/// The key's budget, limits and spend, with its owner's keys and teams.
+async fn key_report(proxy: &Proxy<'_>, info: KeyInfo, headers: &HeaderMap) -> FetchResult {
+    let now = Utc::now();
+    // The owner's keys and teams need a key allowed to read its user
+    let user = match &info.user_id {
+        Some(user_id) => proxy
+            .get::<UserInfoResponse>("/user/info", &[("user_id", user_id)])
+            .await
+            .ok()
+            .map(|(user, _)| user),
+        None => None,
+    };
+
+    let spend = info.spend.unwrap_or(0.0);
+    let organization = info.team_id.as_ref().map(|team_id| {
+        user.iter()
+            .flat_map(|u| &u.teams)
+            .find(|t| &t.team_id == team_id)
+            .map(Team::label)
+            .unwrap_or_else(|| team_id.clone())
+    });
+    let usage = UsageSnapshot {
+        provider: Provider::LiteLlm,
+        source: "api".to_string(),
+        primary: budget_window(
+            spend,
+            info.max_budget,
+            info.budget_duration.as_deref(),
+            info.budget_reset_at.as_deref(),
+        ),
+        secondary: limit_window(headers, info.tpm_limit, "tokens", "tokens/min", now),
+        tertiary: limit_window(headers, info.rpm_limit, "requests", "requests/min", now),
+        identity: Some(ProviderIdentity {
+            email: user
+                .as_ref()
+                .and_then(|u| u.user_info.as_ref())
+                .and_then(|u| u.user_email.clone()),
+            organization,
+            plan: Some(info.label()),
+        }),
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    FetchResult {
+        usage,
+        credits: budget_credits(spend, info.max_budget, info.budget_duration.as_deref()),
+        cost: Some(key_summary(&info, user.as_ref(), now)),
+    }
+}
+
+/// Proxy-wide spend for the master key, which has no `/key/info` row.
+async fn global_report(proxy: &Proxy<'_>) -> Result<FetchResult> {
+    let (global, _) = proxy.get::<GlobalSpend>("/global/spend", &[]).await?;
+    let limit = GLOBAL_DAYS.to_string();
+    let models_query = [("limit", limit.as_str())];
+    let (models, daily) = tokio::join!(
+        proxy.get::<Vec<ModelSpend>>("/global/spend/models", &models_query),
+        proxy.get::<Vec<DaySpend>>("/global/spend/logs", &[]),
+    );
+    let models = models.map(|(m, _)| m).unwrap_or_default();
+    let daily = daily.map(|(d, _)| d).unwrap_or_default();
+
+    let spend = global.spend.unwrap_or(0.0);
+    let usage = UsageSnapshot {
+        provider: Provider::LiteLlm,
+        source: "api".to_string(),
+        primary: budget_window(spend, global.max_budget, None, None),
+        secondary: None,
+        tertiary: None,
+        identity: Some(ProviderIdentity {
+            email: None,
+            organization: None,
+            plan: Some("master key".to_string()),
+        }),
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage,
+        credits: budget_credits(spend, global.max_budget, None),
+        cost: Some(global_summary(&models, &daily, Utc::now().date_naive())),
+    })
+}
+
+/// Fetch the calling key's spend, budget and limits from a LiteLLM proxy,
+/// or the proxy-wide spend for its master key.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let base = proxy_url()
+        .ok_or_else(|| ProviderError::AuthMissing(format!("{} env var not set", PROXY_URL_ENV)))?;
+    validate_local_endpoint(&base, "LiteLLM")?;
+    let key = env_api_key(API_KEY_ENV)?;
+    let local = if is_loopback(&base) {
+        Some(
+            http.local_builder()
+                .build()
+                .context("Failed to build HTTP client")?,
+        )
+    } else {
+        None
+    };
+    let proxy = Proxy {
+        http,
+        local,
+        base,
+        key,
+    };
+
+    match proxy.get::<KeyInfoResponse>("/key/info", &[]).await {
+        Ok((response, headers)) => Ok(key_report(&proxy, response.info, &headers).await),
+        // The master key is not stored as a virtual key
+        Err(err) if is_not_found(&err) => global_report(&proxy).await.map_err(|_| err),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    const KEY_INFO: &str = r#"{
+        "key": "sk-1234",
+        "info": {
+            "key_name": "sk-...ab12",
+            "key_alias": "ci-pipeline",
+            "spend": 37.5,
+            "max_budget": 150.0,
+            "budget_duration": "30d",
+            "budget_reset_at": "2026-11-01T00:00:00",
+            "tpm_limit": 100000,
+            "rpm_limit": null,
+            "user_id": "u-42",
+            "team_id": "team-7",
+            "models": ["gpt-4o", "claude-sonnet-4"],
+            "model_spend": {"gpt-4o": 12.5, "claude-sonnet-4": 25.0},
+            "metadata": {},
+            "created_at": "2026-06-01T09:30:00.123Z"
+        }
+    }"#;
+
+    const USER_INFO: &str = r#"{
+        "user_id": "u-42",
+        "user_info": {"user_id": "u-42", "user_email": "dev@example.com", "spend": 41.0},
+        "keys": [
+            {"key_alias": "ci-pipeline", "key_name": "sk-...ab12", "spend": 37.5},
+            {"key_alias": null, "key_name": "sk-...cd34", "spend": 3.5}
+        ],
+        "teams": [{"team_id": "team-7", "team_alias": "platform", "spend": 210.0}]
+    }"#;
+
+    fn now() -> DateTime<Utc> {
+        "2026-10-18T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_durations_and_times() {
+        assert_eq!(parse_duration("30d"), Some(Duration::days(30)));
+        assert_eq!(parse_duration("1mo"), Some(Duration::days(30)));
+        assert_eq!(parse_duration("24h"), Some(Duration::hours(24)));
+        assert_eq!(parse_duration("d"), None);
+        assert_eq!(parse_duration("5y"), None);
+        assert_eq!(parse_duration("999999999999999d"), None);
+        assert_eq!(parse_duration("999999999999999999mo"), None);
+        assert_eq!(
+            parse_time("2026-11-01T00:00:00"),
+            parse_time("2026-11-01T00:00:00+00:00")
+        );
+        assert!(parse_time("2026-06-01T09:30:00.123Z").is_some());
+    }
+
+    #[test]
+    fn maps_key_budget_and_limits() {
+        let response: KeyInfoResponse = serde_json::from_str(KEY_INFO).unwrap();
+        let info = response.info;
+        let spend = info.spend.unwrap();
+
+        let budget = budget_window(
+            spend,
+            info.max_budget,
+            info.budget_duration.as_deref(),
+            info.budget_reset_at.as_deref(),
+        )
+        .unwrap();
+        assert!((budget.used_percent - 25.0).abs() < 1e-9);
+        assert_eq!(budget.window_minutes, 30 * 24 * 60);
+        assert_eq!(
+            budget.resets_at,
+            Some("2026-11-01T00:00:00Z".parse().unwrap())
+        );
+        assert_eq!(
+            budget.reset_description.as_deref(),
+            Some("$37.50 of $150.00")
+        );
+
+        let credits = budget_credits(spend, info.max_budget, Some("30d")).unwrap();
+        assert_eq!(credits.remaining, 112.5);
+        assert_eq!(credits.period.as_deref(), Some("30d"));
+        assert!(budget_credits(spend, None, None).is_none());
+
+        let tpm = limit_window(
+            &HeaderMap::new(),
+            info.tpm_limit,
+            "tokens",
+            "tokens/min",
+            now(),
+        )
+        .unwrap();
+        assert_eq!(tpm.used_percent, 0.0);
+        assert_eq!(
+            tpm.reset_description.as_deref(),
+            Some("limit 100000 tokens/min")
+        );
+        assert!(limit_window(
+            &HeaderMap::new(),
+            info.rpm_limit,
+            "requests",
+            "requests/min",
+            now()
+        )
+        .is_none());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-limit-tokens",
+            HeaderValue::from_static("100000"),
+        );
+        headers.insert(
+            "x-ratelimit-remaining-tokens",
+            HeaderValue::from_static("60000"),
+        );
+        let tpm = limit_window(&headers, info.tpm_limit, "tokens", "tokens/min", now()).unwrap();
+        assert!((tpm.used_percent - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn summarizes_key_spend() {
+        let info = serde_json::from_str::<KeyInfoResponse>(KEY_INFO)
+            .unwrap()
+            .info;
+        let user: UserInfoResponse = serde_json::from_str(USER_INFO).unwrap();
+
+        let summary = key_summary(&info, Some(&user), now());
+        assert_eq!(summary.total_cost, 37.5);
+        // Budget period started 2026-10-02
+        assert_eq!(summary.days, 17);
+        assert_eq!(summary.by_model[0].model, "claude-sonnet-4");
+        assert_eq!(summary.by_model[0].total_cost, 25.0);
+        assert_eq!(summary.by_workspace[0].id, "platform");
+        let keys: Vec<&str> = summary.by_api_key.iter().map(|k| k.id.as_str()).collect();
+        assert_eq!(keys, vec!["ci-pipeline", "sk-...cd34"]);
+
+        // Without a budget period, spend counts from the key's creation
+        let info = KeyInfo {
+            created_at: Some("2026-10-09T08:00:00".into()),
+            ..Default::default()
+        };
+        assert_eq!(key_summary(&info, None, now()).days, 10);
+
+        // A period too long to subtract from the reset time is ignored
+        let info = KeyInfo {
+            created_at: Some("2026-10-09T08:00:00".into()),
+            budget_reset_at: Some("2026-11-01T00:00:00".into()),
+            budget_duration: Some("99999999d".into()),
+            ..Default::default()
+        };
+        assert_eq!(key_summary(&info, None, now()).days, 10);
+        let info = KeyInfo {
+            budget_duration: Some("999999999999999d".into()),
+            ..info
+        };
+        assert_eq!(key_summary(&info, None, now()).days, 10);
+        assert!(budget_window(1.0, Some(10.0), Some("999999999999999d"), None).is_some());
+    }
+
+    #[test]
+    fn summarizes_global_spend() {
+        let models: Vec<ModelSpend> = serde_json::from_str(
+            r#"[{"model": "gpt-4o", "total_spend": 80.0}, {"model": null, "total_spend": 1.0}]"#,
+        )
+        .unwrap();
+        let daily: Vec<DaySpend> = serde_json::from_str(
+            r#"[{"date": "2026-10-17", "spend": 50.0}, {"date": "2026-10-18T00:00:00", "spend": 30.0}]"#,
+        )
+        .unwrap();
+
+        let summary = global_summary(&models, &daily, now().date_naive());
+        assert_eq!(summary.total_cost, 80.0);
+        assert_eq!(summary.today_cost, 30.0);
+        assert_eq!(summary.days, GLOBAL_DAYS);
+        assert_eq!(summary.by_model.len(), 1);
+        assert_eq!(summary.daily.len(), 2);
+    }
+
+    #[test]
+    fn master_key_is_detected_by_not_found() {
+        let err: anyhow::Error =
+            ProviderError::http_status(reqwest::StatusCode::NOT_FOUND, String::new()).into();
+        assert!(is_not_found(&err));
+        let err: anyhow::Error = ProviderError::unauthorized("no").into();
+        assert!(!is_not_found(&err));
+    }
+}
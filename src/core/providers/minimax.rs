@@ -3,6 +3,8 @@ use chrono::{TimeZone, Utc};
 use serde::Deserialize;
 
 use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -72,30 +74,30 @@ fn parse_model_remain(remain: &ModelRemain) -> RateWindow {
     }
 }
 
-async fn try_fetch(url: &str, token: &str) -> Result<reqwest::Response> {
-    let client = reqwest::Client::new();
-    let response = client
+async fn try_fetch(http: &HttpClient, url: &str, token: &str) -> Result<reqwest::Response> {
+    let request = http
         .get(url)
         .header("Authorization", format!("Bearer {}", token))
-        .header("Accept", "application/json")
-        .send()
+        .header("Accept", "application/json");
+    let response = http
+        .send(request)
         .await
         .with_context(|| format!("Failed to send request to {}", url))?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized — check MINIMAX_API_TOKEN");
+        return Err(ProviderError::unauthorized("Unauthorized — check MINIMAX_API_TOKEN").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     Ok(response)
 }
 
 /// Fetch usage data from the MiniMax coding plan API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let token =
         std::env::var("MINIMAX_API_TOKEN").context("MINIMAX_API_TOKEN env var not set")?;
 
@@ -105,12 +107,12 @@ pub async fn fetch() -> Result<FetchResult> {
         eprintln!("minimax: using custom host via MINIMAX_API_HOST");
     }
 
-    let response = match try_fetch(&url, &token).await {
+    let response = match try_fetch(http, &url, &token).await {
         Ok(resp) => resp,
         Err(_) => {
             let fallback = fallback_url();
             crate::core::providers::fetch::validate_endpoint(&fallback, "MiniMax")?;
-            try_fetch(&fallback, &token).await?
+            try_fetch(http, &fallback, &token).await?
         }
     };
 
@@ -133,11 +135,14 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary: None,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
     Ok(FetchResult {
         usage,
         credits: None,
+        cost: None,
     })
 }
 
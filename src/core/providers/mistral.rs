@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+
+use crate::core::http::HttpClient;
+use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{env_api_key, header_window, FetchResult};
+use crate::core::providers::Provider;
+
+const MODELS_URL: &str = "https://api.mistral.ai/v1/models";
+
+/// Tokens per minute and per month, from the `x-ratelimitbysize-*` headers.
+fn rate_windows(
+    headers: &HeaderMap,
+    now: DateTime<Utc>,
+) -> (Option<RateWindow>, Option<RateWindow>) {
+    let minute = header_window(
+        headers,
+        "x-ratelimitbysize-limit-minute",
+        "x-ratelimitbysize-remaining-minute",
+        None,
+        "tokens/min",
+        1,
+        now,
+    );
+    let month = header_window(
+        headers,
+        "x-ratelimitbysize-limit-month",
+        "x-ratelimitbysize-remaining-month",
+        None,
+        "tokens/month",
+        0,
+        now,
+    );
+    (minute, month)
+}
+
+/// Fetch the workspace's token limits from Mistral.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let api_key = env_api_key("MISTRAL_API_KEY")?;
+
+    let request = http
+        .get(MODELS_URL)
+        .header("Authorization", format!("Bearer {}", api_key));
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to Mistral API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(
+            ProviderError::unauthorized("Unauthorized - check your MISTRAL_API_KEY").into(),
+        );
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    let (primary, secondary) = rate_windows(response.headers(), Utc::now());
+    let usage = UsageSnapshot {
+        provider: Provider::Mistral,
+        source: "api".to_string(),
+        primary,
+        secondary,
+        tertiary: None,
+        identity: None,
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage,
+        credits: None,
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn reads_token_limit_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("x-ratelimitbysize-limit-minute", "500000"),
+            ("x-ratelimitbysize-remaining-minute", "450000"),
+            ("x-ratelimitbysize-limit-month", "1000000000"),
+            ("x-ratelimitbysize-remaining-month", "750000000"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+
+        let (minute, month) = rate_windows(&headers, Utc::now());
+        assert!((minute.unwrap().used_percent - 10.0).abs() < 1e-9);
+        let month = month.unwrap();
+        assert!((month.used_percent - 25.0).abs() < 1e-9);
+        assert!(month.resets_at.is_none());
+        assert_eq!(
+            month.reset_description.as_deref(),
+            Some("250000000 of 1000000000 tokens/month")
+        );
+    }
+}
@@ -1,29 +1,41 @@
 pub mod amp;
+pub mod anthropic_admin;
 pub mod antigravity;
 pub mod augment;
+pub mod bedrock;
 pub mod claude;
 pub mod codex;
 pub mod copilot;
 pub mod cursor;
+pub mod deepseek;
+pub mod error;
 pub mod factory;
 pub mod fetch;
 pub mod gemini;
+pub mod generic_http;
+pub mod groq;
 pub mod jetbrains;
 pub mod kimi;
 pub mod kimi_k2;
 pub mod kiro;
+pub mod litellm;
 pub mod minimax;
+pub mod mistral;
 pub mod ollama;
 pub mod opencode;
+pub mod openai_admin;
 pub mod openrouter;
 pub mod synthetic;
+pub mod together;
 pub mod vertex_ai;
 pub mod warp;
+pub mod xai;
 pub mod zai;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, JsonSchema, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Provider {
     Claude,
@@ -47,6 +59,16 @@ pub enum Provider {
     Antigravity,
     Synthetic,
     VertexAi,
+    Bedrock,
+    AnthropicAdmin,
+    OpenAiAdmin,
+    DeepSeek,
+    Mistral,
+    Groq,
+    Xai,
+    Together,
+    GenericHttp,
+    LiteLlm,
 }
 
 impl Provider {
@@ -73,6 +95,16 @@ impl Provider {
             "antigravity" => Some(Self::Antigravity),
             "synthetic" => Some(Self::Synthetic),
             "vertex_ai" | "vertex-ai" | "vertexai" => Some(Self::VertexAi),
+            "bedrock" | "aws_bedrock" | "aws-bedrock" => Some(Self::Bedrock),
+            "anthropic_admin" | "anthropic-admin" => Some(Self::AnthropicAdmin),
+            "openai_admin" | "openai-admin" => Some(Self::OpenAiAdmin),
+            "deepseek" => Some(Self::DeepSeek),
+            "mistral" => Some(Self::Mistral),
+            "groq" => Some(Self::Groq),
+            "xai" | "x_ai" | "x-ai" => Some(Self::Xai),
+            "together" | "together_ai" | "togetherai" => Some(Self::Together),
+            "generic_http" | "generic-http" => Some(Self::GenericHttp),
+            "litellm" | "lite_llm" => Some(Self::LiteLlm),
             _ => None,
         }
     }
@@ -100,6 +132,16 @@ impl Provider {
             Self::Antigravity => "antigravity",
             Self::Synthetic => "synthetic",
             Self::VertexAi => "vertex_ai",
+            Self::Bedrock => "bedrock",
+            Self::AnthropicAdmin => "anthropic_admin",
+            Self::OpenAiAdmin => "openai_admin",
+            Self::DeepSeek => "deepseek",
+            Self::Mistral => "mistral",
+            Self::Groq => "groq",
+            Self::Xai => "xai",
+            Self::Together => "together",
+            Self::GenericHttp => "generic_http",
+            Self::LiteLlm => "litellm",
         }
     }
 
@@ -126,12 +168,29 @@ impl Provider {
             Self::Antigravity => "Antigravity",
             Self::Synthetic => "Synthetic",
             Self::VertexAi => "Vertex AI",
+            Self::Bedrock => "Bedrock",
+            Self::AnthropicAdmin => "Anthropic API",
+            Self::OpenAiAdmin => "OpenAI API",
+            Self::DeepSeek => "DeepSeek",
+            Self::Mistral => "Mistral",
+            Self::Groq => "Groq",
+            Self::Xai => "xAI",
+            Self::Together => "Together",
+            Self::GenericHttp => "Generic HTTP",
+            Self::LiteLlm => "LiteLLM",
         }
     }
 
     pub fn session_label(&self) -> &'static str {
         match self {
             Self::Gemini => "Pro",
+            Self::Cursor => "Premium",
+            Self::VertexAi => "Claude",
+            Self::Bedrock | Self::OpenAiAdmin | Self::Groq | Self::Together => "Tokens",
+            Self::Mistral => "Minute",
+            Self::LiteLlm => "Budget",
+            Self::Augment => "Credits",
+            Self::Factory => "Standard",
             _ => "Session",
         }
     }
@@ -139,6 +198,12 @@ impl Provider {
     pub fn weekly_label(&self) -> &'static str {
         match self {
             Self::Gemini => "Flash",
+            Self::Cursor => "Small",
+            Self::VertexAi => "Gemini",
+            Self::Bedrock | Self::OpenAiAdmin | Self::Groq | Self::Together => "Requests",
+            Self::Mistral => "Month",
+            Self::LiteLlm => "Tokens",
+            Self::Factory => "Premium",
             _ => "Weekly",
         }
     }
@@ -146,14 +211,15 @@ impl Provider {
     pub fn tertiary_label(&self) -> &'static str {
         match self {
             Self::Claude => "Sonnet",
+            Self::LiteLlm => "Requests",
             _ => "Model",
         }
     }
 
     pub fn status_page_url(&self) -> Option<&'static str> {
         match self {
-            Self::Claude => Some("https://status.anthropic.com"),
-            Self::Codex => Some("https://status.openai.com"),
+            Self::Claude | Self::AnthropicAdmin => Some("https://status.anthropic.com"),
+            Self::Codex | Self::OpenAiAdmin => Some("https://status.openai.com"),
             Self::Copilot => Some("https://www.githubstatus.com"),
             _ => None,
         }
@@ -163,6 +229,11 @@ impl Provider {
         true
     }
 
+    /// Whether token costs for this provider come from local session logs.
+    pub fn has_cost_logs(&self) -> bool {
+        matches!(self, Self::Claude | Self::Codex | Self::VertexAi | Self::Bedrock)
+    }
+
     /// All provider variants in display order (supported first, stubs last).
     pub fn all() -> &'static [Provider] {
         &[
@@ -181,28 +252,29 @@ impl Provider {
             Provider::JetBrains,
             Provider::Antigravity,
             Provider::Synthetic,
-            // Stubs
             Provider::Cursor,
             Provider::Ollama,
+            Provider::VertexAi,
+            Provider::Bedrock,
             Provider::Augment,
-            Provider::OpenCode,
             Provider::Factory,
+            Provider::AnthropicAdmin,
+            Provider::OpenAiAdmin,
+            Provider::DeepSeek,
+            Provider::Mistral,
+            Provider::Groq,
+            Provider::Xai,
+            Provider::Together,
+            Provider::GenericHttp,
+            Provider::LiteLlm,
+            // Stubs
+            Provider::OpenCode,
             Provider::Amp,
-            Provider::VertexAi,
         ]
     }
 
     pub fn is_stub(&self) -> bool {
-        matches!(
-            self,
-            Self::Cursor
-                | Self::Ollama
-                | Self::Augment
-                | Self::OpenCode
-                | Self::Factory
-                | Self::Amp
-                | Self::VertexAi
-        )
+        matches!(self, Self::OpenCode | Self::Amp)
     }
 
     pub fn auth_hint(&self) -> &'static str {
@@ -221,8 +293,22 @@ impl Provider {
             Self::JetBrains => "IDE config files",
             Self::Antigravity => "language server process",
             Self::Synthetic => "SYNTHETIC_API_KEY",
-            Self::Cursor | Self::Ollama | Self::Augment | Self::OpenCode | Self::Factory
-            | Self::Amp | Self::VertexAi => "planned",
+            Self::Cursor => "auto-detected (Cursor IDE)",
+            Self::Ollama => "local server or OLLAMA_API_KEY",
+            Self::VertexAi => "gcloud ADC or GOOGLE_APPLICATION_CREDENTIALS",
+            Self::Bedrock => "Claude logs; quotas with AWS credentials",
+            Self::Augment => "auto-detected (~/.augment/) or AUGMENT_SESSION_AUTH",
+            Self::Factory => "auto-detected (~/.factory/) or FACTORY_API_KEY",
+            Self::AnthropicAdmin => "ANTHROPIC_ADMIN_KEY",
+            Self::OpenAiAdmin => "OPENAI_ADMIN_KEY",
+            Self::DeepSeek => "DEEPSEEK_API_KEY",
+            Self::Mistral => "MISTRAL_API_KEY",
+            Self::Groq => "GROQ_API_KEY",
+            Self::Xai => "XAI_API_KEY (balance: XAI_MANAGEMENT_KEY)",
+            Self::Together => "TOGETHER_API_KEY",
+            Self::GenericHttp => "[[generic_http]] in config.toml",
+            Self::LiteLlm => "LITELLM_PROXY_URL + LITELLM_PROXY_API_KEY",
+            Self::OpenCode | Self::Amp => "planned",
         }
     }
 }
@@ -1,8 +1,370 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use serde::Deserialize;
 
-use crate::core::providers::fetch::FetchResult;
+use crate::core::http::HttpClient;
+use crate::core::models::usage::{
+    LoadedModel, LocalModels, ProviderIdentity, RateWindow, UsageSnapshot,
+};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{is_loopback, validate_local_endpoint, FetchResult};
+use crate::core::providers::Provider;
 
-/// Ollama cloud usage provider (stub).
-pub async fn fetch() -> Result<FetchResult> {
-    anyhow::bail!("Ollama cloud usage requires browser cookies (not yet supported)")
+/// Ollama's own default when `OLLAMA_HOST` is unset.
+const DEFAULT_HOST: &str = "http://127.0.0.1:11434";
+const DEFAULT_PORT: u16 = 11434;
+
+const CLOUD_USAGE_URL: &str = "https://ollama.com/api/usage";
+
+/// Server to query, from `OLLAMA_HOST` as understood by the Ollama CLI.
+pub fn host() -> String {
+    resolve_host(std::env::var("OLLAMA_HOST").ok().as_deref())
+}
+
+/// Normalize an `OLLAMA_HOST` value (`0.0.0.0`, `localhost:11434`,
+/// `https://gpu-box.lan`) to a base URL without a trailing slash.
+fn resolve_host(raw: Option<&str>) -> String {
+    let Some(raw) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+        return DEFAULT_HOST.to_string();
+    };
+    let (scheme, rest) = raw.split_once("://").unwrap_or(("http", raw));
+    let rest = rest.trim_end_matches('/');
+    // A server bound to all interfaces is reached over loopback
+    let rest = match rest.strip_prefix("0.0.0.0") {
+        Some(port) => format!("127.0.0.1{}", port),
+        None => rest.to_string(),
+    };
+    let has_port = rest
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()));
+    if has_port || scheme == "https" {
+        format!("{}://{}", scheme, rest)
+    } else {
+        format!("{}://{}:{}", scheme, rest, DEFAULT_PORT)
+    }
+}
+
+#[derive(Deserialize)]
+struct RunningModel {
+    name: String,
+    size: u64,
+    #[serde(default)]
+    size_vram: u64,
+    expires_at: Option<String>,
+}
+
+/// `/api/ps`: models currently loaded in memory.
+#[derive(Deserialize)]
+struct PsResponse {
+    #[serde(default)]
+    models: Vec<RunningModel>,
+}
+
+#[derive(Deserialize)]
+struct InstalledModel {
+    #[serde(default)]
+    size: u64,
+}
+
+/// `/api/tags`: models available on disk.
+#[derive(Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<InstalledModel>,
+}
+
+#[derive(Deserialize)]
+struct CloudWindow {
+    used_percent: f64,
+    window_minutes: Option<u64>,
+    resets_at: Option<DateTime<Utc>>,
+}
+
+/// ollama.com usage limits for cloud models.
+#[derive(Deserialize)]
+struct CloudUsage {
+    plan: Option<String>,
+    session: Option<CloudWindow>,
+    weekly: Option<CloudWindow>,
+}
+
+fn parse_cloud_window(window: &CloudWindow) -> RateWindow {
+    RateWindow {
+        used_percent: window.used_percent.clamp(0.0, 100.0),
+        window_minutes: window.window_minutes.unwrap_or(0),
+        resets_at: window.resets_at,
+        reset_description: None,
+    }
+}
+
+/// Unload time of a running model. Models loaded with a negative
+/// `keep_alive` report an expiry centuries ahead and never unload.
+fn parse_expiry(expires_at: Option<&str>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let at = expires_at?
+        .parse::<DateTime<FixedOffset>>()
+        .ok()?
+        .with_timezone(&Utc);
+    (at - now < Duration::days(365)).then_some(at)
+}
+
+fn parse_local(host: &str, ps: PsResponse, tags: TagsResponse, now: DateTime<Utc>) -> LocalModels {
+    let loaded = ps
+        .models
+        .into_iter()
+        .map(|m| LoadedModel {
+            expires_at: parse_expiry(m.expires_at.as_deref(), now),
+            vram_bytes: m.size_vram.min(m.size),
+            ram_bytes: m.size.saturating_sub(m.size_vram),
+            name: m.name,
+        })
+        .collect();
+    LocalModels {
+        host: host.to_string(),
+        loaded,
+        installed: tags.models.len() as u32,
+        installed_bytes: tags.models.iter().map(|m| m.size).sum(),
+    }
+}
+
+async fn fetch_local(http: &HttpClient, host: &str) -> Result<LocalModels> {
+    // A local server is never reached through the configured proxy
+    let local_client = if is_loopback(host) {
+        Some(
+            http.local_builder()
+                .build()
+                .context("Failed to build HTTP client")?,
+        )
+    } else {
+        None
+    };
+    let get = |path: &str| {
+        let url = format!("{}{}", host, path);
+        match &local_client {
+            Some(client) => client.get(url),
+            None => http.get(&url),
+        }
+    };
+
+    let (ps, tags) = tokio::join!(http.send(get("/api/ps")), http.send(get("/api/tags")));
+    let connect_error = || format!("Failed to connect to Ollama at {} - is it running?", host);
+    let (ps, tags) = (ps.with_context(connect_error)?, tags.with_context(connect_error)?);
+    for response in [&ps, &tags] {
+        if !response.status().is_success() {
+            return Err(ProviderError::http_status(response.status(), String::new()).into());
+        }
+    }
+    let ps: PsResponse = ps
+        .json()
+        .await
+        .context("Failed to parse Ollama /api/ps response")?;
+    let tags: TagsResponse = tags
+        .json()
+        .await
+        .context("Failed to parse Ollama /api/tags response")?;
+    Ok(parse_local(host, ps, tags, Utc::now()))
+}
+
+async fn fetch_cloud(http: &HttpClient, api_key: &str) -> Result<CloudUsage> {
+    let request = http
+        .get(CLOUD_USAGE_URL)
+        .header("Authorization", format!("Bearer {}", api_key));
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to ollama.com")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProviderError::unauthorized("Unauthorized - check your OLLAMA_API_KEY").into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    response
+        .json()
+        .await
+        .context("Failed to parse ollama.com usage response")
+}
+
+/// Fetch loaded models from the local server and, when `OLLAMA_API_KEY` is
+/// set, cloud model usage limits from ollama.com. Either part may be
+/// missing as long as the other succeeds.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let host = host();
+    validate_local_endpoint(&host, "Ollama")?;
+    let api_key = std::env::var("OLLAMA_API_KEY")
+        .ok()
+        .filter(|k| !k.is_empty());
+
+    let (local, cloud) = tokio::join!(fetch_local(http, &host), async {
+        match &api_key {
+            Some(key) => Some(fetch_cloud(http, key).await),
+            None => None,
+        }
+    });
+    let (local, cloud) = match (local, cloud) {
+        (Ok(local), None) => (Some(local), None),
+        (Ok(local), Some(Ok(cloud))) => (Some(local), Some(cloud)),
+        (Ok(local), Some(Err(e))) => {
+            eprintln!("ollama: cloud usage unavailable: {:#}", e);
+            (Some(local), None)
+        }
+        (Err(_), Some(Ok(cloud))) => (None, Some(cloud)),
+        (Err(e), None) => return Err(e),
+        // With a key configured the cloud error is the more useful one
+        (Err(_), Some(Err(e))) => return Err(e),
+    };
+
+    let identity = cloud
+        .as_ref()
+        .and_then(|c| c.plan.clone())
+        .map(|plan| ProviderIdentity {
+            email: None,
+            organization: None,
+            plan: Some(plan),
+        });
+
+    let usage = UsageSnapshot {
+        provider: Provider::Ollama,
+        source: "api".to_string(),
+        primary: cloud
+            .as_ref()
+            .and_then(|c| c.session.as_ref())
+            .map(parse_cloud_window),
+        secondary: cloud
+            .as_ref()
+            .and_then(|c| c.weekly.as_ref())
+            .map(parse_cloud_window),
+        tertiary: None,
+        identity,
+        local,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage,
+        credits: None,
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const PS_FIXTURE: &str = r#"{
+        "models": [
+            {
+                "name": "llama3.2:3b",
+                "model": "llama3.2:3b",
+                "size": 3400000000,
+                "digest": "a80c4f17acd5",
+                "details": { "family": "llama", "parameter_size": "3.2B", "quantization_level": "Q4_K_M" },
+                "expires_at": "2026-01-15T13:04:00.123456789+01:00",
+                "size_vram": 3400000000
+            },
+            {
+                "name": "qwen3:32b",
+                "model": "qwen3:32b",
+                "size": 22000000000,
+                "digest": "e1c9f0d9a9b0",
+                "expires_at": "2318-08-21T20:00:00Z",
+                "size_vram": 16000000000
+            }
+        ]
+    }"#;
+
+    const TAGS_FIXTURE: &str = r#"{
+        "models": [
+            { "name": "llama3.2:3b", "modified_at": "2025-11-01T10:00:00Z", "size": 2000000000 },
+            { "name": "qwen3:32b", "modified_at": "2025-11-02T10:00:00Z", "size": 20000000000 },
+            { "name": "nomic-embed-text:latest", "modified_at": "2025-11-03T10:00:00Z", "size": 274000000 }
+        ]
+    }"#;
+
+    const CLOUD_FIXTURE: &str = r#"{
+        "plan": "pro",
+        "session": { "used_percent": 12.5, "window_minutes": 300, "resets_at": "2026-01-15T14:00:00Z" },
+        "weekly": { "used_percent": 140.0, "resets_at": "2026-01-19T00:00:00Z" }
+    }"#;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn resolves_ollama_host_values() {
+        assert_eq!(resolve_host(None), "http://127.0.0.1:11434");
+        assert_eq!(resolve_host(Some("")), "http://127.0.0.1:11434");
+        assert_eq!(resolve_host(Some("0.0.0.0")), "http://127.0.0.1:11434");
+        assert_eq!(resolve_host(Some("0.0.0.0:8080")), "http://127.0.0.1:8080");
+        assert_eq!(resolve_host(Some("localhost")), "http://localhost:11434");
+        assert_eq!(resolve_host(Some("http://[::1]:11434/")), "http://[::1]:11434");
+        assert_eq!(resolve_host(Some("https://gpu-box.lan")), "https://gpu-box.lan");
+        assert_eq!(resolve_host(Some("gpu-box.lan:9000")), "http://gpu-box.lan:9000");
+    }
+
+    #[test]
+    fn remote_plain_http_host_is_rejected() {
+        let host = resolve_host(Some("gpu-box.lan"));
+        assert!(validate_local_endpoint(&host, "Ollama").is_err());
+        assert!(validate_local_endpoint(&resolve_host(Some("0.0.0.0")), "Ollama").is_ok());
+    }
+
+    #[test]
+    fn parses_loaded_and_installed_models() {
+        let ps: PsResponse = serde_json::from_str(PS_FIXTURE).unwrap();
+        let tags: TagsResponse = serde_json::from_str(TAGS_FIXTURE).unwrap();
+        let local = parse_local(DEFAULT_HOST, ps, tags, now());
+
+        assert_eq!(local.host, DEFAULT_HOST);
+        assert_eq!(local.installed, 3);
+        assert_eq!(local.installed_bytes, 22_274_000_000);
+        assert_eq!(local.loaded.len(), 2);
+
+        let llama = &local.loaded[0];
+        assert_eq!(llama.name, "llama3.2:3b");
+        assert_eq!(llama.vram_bytes, 3_400_000_000);
+        assert_eq!(llama.ram_bytes, 0);
+        assert_eq!(
+            llama.expires_at.unwrap().timestamp(),
+            Utc.with_ymd_and_hms(2026, 1, 15, 12, 4, 0).unwrap().timestamp()
+        );
+
+        // Partially offloaded and kept loaded indefinitely
+        let qwen = &local.loaded[1];
+        assert_eq!(qwen.vram_bytes, 16_000_000_000);
+        assert_eq!(qwen.ram_bytes, 6_000_000_000);
+        assert!(qwen.expires_at.is_none());
+    }
+
+    #[test]
+    fn empty_server_responses() {
+        let ps: PsResponse = serde_json::from_str(r#"{"models": []}"#).unwrap();
+        let tags: TagsResponse = serde_json::from_str("{}").unwrap();
+        let local = parse_local(DEFAULT_HOST, ps, tags, now());
+        assert!(local.loaded.is_empty());
+        assert_eq!(local.installed, 0);
+    }
+
+    #[test]
+    fn parses_cloud_limits() {
+        let cloud: CloudUsage = serde_json::from_str(CLOUD_FIXTURE).unwrap();
+        assert_eq!(cloud.plan.as_deref(), Some("pro"));
+
+        let session = parse_cloud_window(cloud.session.as_ref().unwrap());
+        assert!((session.used_percent - 12.5).abs() < 1e-10);
+        assert_eq!(session.window_minutes, 300);
+        assert_eq!(
+            session.resets_at,
+            Some(Utc.with_ymd_and_hms(2026, 1, 15, 14, 0, 0).unwrap())
+        );
+
+        let weekly = parse_cloud_window(cloud.weekly.as_ref().unwrap());
+        assert!((weekly.used_percent - 100.0).abs() < 1e-10);
+        assert_eq!(weekly.window_minutes, 0);
+    }
 }
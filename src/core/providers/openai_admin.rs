@@ -0,0 +1,513 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::core::config::AppConfig;
+use crate::core::cost::org::{self, CostLine, CostPart, UsageLine};
+use crate::core::http::HttpClient;
+use crate::core::models::cost::CostSummary;
+use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{per_minute_window, FetchResult};
+use crate::core::providers::Provider;
+
+const API_URL: &str = "https://api.openai.com/v1/organization";
+
+/// Admin key (`sk-admin-...`) from the organization's settings.
+const ADMIN_KEY_ENV: &str = "OPENAI_ADMIN_KEY";
+
+/// Days of reports to summarize, matching the session-log scan.
+const DAYS: u32 = 30;
+
+/// One page of a usage or cost report.
+#[derive(Deserialize)]
+struct ReportPage<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<Bucket<T>>,
+    #[serde(default)]
+    has_more: bool,
+    next_page: Option<String>,
+}
+
+/// A bucket of a report, starting at a Unix time.
+#[derive(Deserialize)]
+struct Bucket<T> {
+    start_time: i64,
+    #[serde(default = "Vec::new")]
+    results: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct Amount {
+    value: f64,
+}
+
+/// A charge of one project and line item, such as `gpt-5, input`.
+#[derive(Deserialize)]
+struct CostResult {
+    amount: Option<Amount>,
+    line_item: Option<String>,
+    project_id: Option<String>,
+}
+
+/// Completion tokens of one model, API key and project. `input_tokens`
+/// includes the cached ones.
+#[derive(Deserialize)]
+struct UsageResult {
+    model: Option<String>,
+    api_key_id: Option<String>,
+    project_id: Option<String>,
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    #[serde(default)]
+    input_cached_tokens: u64,
+    #[serde(default)]
+    num_model_requests: u64,
+}
+
+#[derive(Deserialize)]
+struct RateLimitList {
+    #[serde(default)]
+    data: Vec<RateLimit>,
+}
+
+/// Per-minute limits of a model for the project's usage tier.
+#[derive(Deserialize)]
+struct RateLimit {
+    model: String,
+    max_requests_per_1_minute: Option<f64>,
+    max_tokens_per_1_minute: Option<f64>,
+}
+
+fn load_key() -> Result<String> {
+    std::env::var(ADMIN_KEY_ENV)
+        .ok()
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| {
+            ProviderError::AuthMissing(format!(
+                "{} not set — create an admin key in the OpenAI organization settings",
+                ADMIN_KEY_ENV
+            ))
+            .into()
+        })
+}
+
+/// The `project` for openai_admin in config.toml, whose rate limits are shown.
+pub fn configured_project(config: &AppConfig) -> Option<String> {
+    config
+        .providers
+        .iter()
+        .find(|p| p.id == Provider::OpenAiAdmin.id())
+        .and_then(|p| p.project.clone())
+        .filter(|p| !p.is_empty())
+}
+
+async fn get<T: DeserializeOwned>(
+    http: &HttpClient,
+    key: &str,
+    path: &str,
+    query: &[(&str, String)],
+) -> Result<T> {
+    let request = http
+        .get(&format!("{}/{}", API_URL, path))
+        .header("Authorization", format!("Bearer {}", key))
+        .query(query);
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to OpenAI Admin API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProviderError::unauthorized(
+            "Unauthorized — check OPENAI_ADMIN_KEY (an admin key, not a project key)",
+        )
+        .into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+    response
+        .json()
+        .await
+        .context("Failed to parse OpenAI Admin API response")
+}
+
+/// All buckets of a report, following `next_page`.
+async fn get_report<T: DeserializeOwned>(
+    http: &HttpClient,
+    key: &str,
+    path: &str,
+    query: Vec<(&str, String)>,
+) -> Result<Vec<Bucket<T>>> {
+    let mut buckets = Vec::new();
+    let mut page: Option<String> = None;
+    loop {
+        let mut query = query.clone();
+        query.extend(page.take().map(|p| ("page", p)));
+        let data: ReportPage<T> = get(http, key, path, &query).await?;
+        buckets.extend(data.data);
+        match data.next_page {
+            Some(next) if data.has_more => page = Some(next),
+            _ => return Ok(buckets),
+        }
+    }
+}
+
+fn bucket_date(start_time: i64) -> NaiveDate {
+    Utc.timestamp_opt(start_time, 0)
+        .single()
+        .unwrap_or_default()
+        .date_naive()
+}
+
+/// Split a line item into the model and the billed token category.
+fn parse_line_item(item: &str) -> (String, CostPart) {
+    let Some((model, kind)) = item.rsplit_once(", ") else {
+        return (item.to_string(), CostPart::Other);
+    };
+    let part = match kind {
+        "input" => CostPart::Input,
+        "output" => CostPart::Output,
+        "cached input" => CostPart::CacheRead,
+        _ => CostPart::Other,
+    };
+    (model.to_string(), part)
+}
+
+/// Project usage outside any project is billed to the default project.
+fn project(id: &Option<String>) -> String {
+    id.clone().unwrap_or_else(|| "default".to_string())
+}
+
+fn cost_lines(buckets: &[Bucket<CostResult>]) -> Vec<CostLine> {
+    buckets
+        .iter()
+        .flat_map(|bucket| {
+            bucket.results.iter().filter_map(move |r| {
+                let (model, part) = parse_line_item(r.line_item.as_deref().unwrap_or("other"));
+                Some(CostLine {
+                    date: bucket_date(bucket.start_time),
+                    model,
+                    part,
+                    amount: r.amount.as_ref()?.value,
+                    workspace: project(&r.project_id),
+                })
+            })
+        })
+        .collect()
+}
+
+fn usage_lines(buckets: &[Bucket<UsageResult>]) -> Vec<UsageLine> {
+    buckets
+        .iter()
+        .flat_map(|bucket| {
+            bucket.results.iter().map(move |r| UsageLine {
+                date: bucket_date(bucket.start_time),
+                model: r.model.clone().unwrap_or_else(|| "unknown".to_string()),
+                api_key: r.api_key_id.clone().unwrap_or_else(|| "unknown".to_string()),
+                workspace: project(&r.project_id),
+                input_tokens: r.input_tokens.saturating_sub(r.input_cached_tokens),
+                output_tokens: r.output_tokens,
+                cache_read_tokens: r.input_cached_tokens,
+                cache_creation_tokens: 0,
+            })
+        })
+        .collect()
+}
+
+/// The limit entry of `model`: an exact match, else the longest limit name
+/// the dated model id starts with (`gpt-5-2025-08-07` → `gpt-5`).
+fn limit_for<'a>(limits: &'a [RateLimit], model: &str) -> Option<&'a RateLimit> {
+    limits
+        .iter()
+        .filter(|l| model == l.model || model.starts_with(&format!("{}-", l.model)))
+        .max_by_key(|l| l.model.len())
+}
+
+/// Token and request windows of the busiest model in the latest minute,
+/// against the project's per-minute limits. Without recent traffic the
+/// top-spending model of the period is shown at 0.
+fn rate_windows(
+    limits: &[RateLimit],
+    minute: &[Bucket<UsageResult>],
+    summary: &CostSummary,
+    now: DateTime<Utc>,
+) -> (Option<RateWindow>, Option<RateWindow>) {
+    let mut used: HashMap<&str, (f64, f64)> = HashMap::new();
+    if let Some(latest) = minute.iter().max_by_key(|b| b.start_time) {
+        for r in &latest.results {
+            let entry = used.entry(r.model.as_deref().unwrap_or("unknown")).or_default();
+            entry.0 += (r.input_tokens + r.output_tokens) as f64;
+            entry.1 += r.num_model_requests as f64;
+        }
+    }
+    if used.is_empty() {
+        if let Some(top) = summary.by_model.first() {
+            used.insert(&top.model, (0.0, 0.0));
+        }
+    }
+
+    let mut tokens: Option<RateWindow> = None;
+    let mut requests: Option<RateWindow> = None;
+    for (model, (used_tokens, used_requests)) in used {
+        let Some(limit) = limit_for(limits, model) else {
+            continue;
+        };
+        let keep_max = |best: &mut Option<RateWindow>, window: RateWindow| {
+            if best
+                .as_ref()
+                .is_none_or(|b| window.used_percent > b.used_percent)
+            {
+                *best = Some(window);
+            }
+        };
+        if let Some(max) = limit.max_tokens_per_1_minute.filter(|&m| m > 0.0) {
+            keep_max(
+                &mut tokens,
+                per_minute_window(&limit.model, used_tokens, max, "tokens", now),
+            );
+        }
+        if let Some(max) = limit.max_requests_per_1_minute.filter(|&m| m > 0.0) {
+            keep_max(
+                &mut requests,
+                per_minute_window(&limit.model, used_requests, max, "requests", now),
+            );
+        }
+    }
+    (tokens, requests)
+}
+
+/// Fetch the organization's completion usage and costs for the last 30
+/// days, and the per-minute rate limits of the configured project.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let config = AppConfig::load().unwrap_or_default();
+    let key = load_key()?;
+    let now = Utc::now();
+    let today = now.date_naive();
+    let start = (today - Duration::days(DAYS as i64 - 1))
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+        .timestamp();
+
+    let usage: Vec<Bucket<UsageResult>> = get_report(
+        http,
+        &key,
+        "usage/completions",
+        vec![
+            ("start_time", start.to_string()),
+            ("bucket_width", "1d".to_string()),
+            ("limit", DAYS.to_string()),
+            ("group_by", "model".to_string()),
+            ("group_by", "api_key_id".to_string()),
+            ("group_by", "project_id".to_string()),
+        ],
+    )
+    .await?;
+    let costs: Vec<Bucket<CostResult>> = get_report(
+        http,
+        &key,
+        "costs",
+        vec![
+            ("start_time", start.to_string()),
+            ("bucket_width", "1d".to_string()),
+            ("limit", DAYS.to_string()),
+            ("group_by", "line_item".to_string()),
+            ("group_by", "project_id".to_string()),
+        ],
+    )
+    .await?;
+    let summary = org::build_summary(&cost_lines(&costs), &usage_lines(&usage), DAYS, today);
+
+    // Rate limits are set per project; without one only spend is shown
+    let (primary, secondary) = match configured_project(&config) {
+        Some(project) => {
+            let limits: RateLimitList = get(
+                http,
+                &key,
+                &format!("projects/{}/rate_limits", project),
+                &[("limit", "100".to_string())],
+            )
+            .await?;
+            let minute: Vec<Bucket<UsageResult>> = get_report(
+                http,
+                &key,
+                "usage/completions",
+                vec![
+                    ("start_time", (now - Duration::minutes(2)).timestamp().to_string()),
+                    ("bucket_width", "1m".to_string()),
+                    ("project_ids", project),
+                    ("group_by", "model".to_string()),
+                ],
+            )
+            .await
+            .unwrap_or_default();
+            rate_windows(&limits.data, &minute, &summary, now)
+        }
+        None => (None, None),
+    };
+
+    let snapshot = UsageSnapshot {
+        provider: Provider::OpenAiAdmin,
+        source: "api".to_string(),
+        primary,
+        secondary,
+        tertiary: None,
+        identity: None,
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage: snapshot,
+        credits: None,
+        cost: Some(summary),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ProviderConfig;
+
+    const COSTS: &str = r#"{
+        "object": "page",
+        "data": [{
+            "object": "bucket",
+            "start_time": 1792195200,
+            "end_time": 1792281600,
+            "results": [
+                {"object": "organization.costs.result", "amount": {"value": 1.25, "currency": "usd"}, "line_item": "gpt-5-2025-08-07, input", "project_id": "proj_abc"},
+                {"object": "organization.costs.result", "amount": {"value": 2.5, "currency": "usd"}, "line_item": "gpt-5-2025-08-07, output", "project_id": "proj_abc"},
+                {"object": "organization.costs.result", "amount": {"value": 0.05, "currency": "usd"}, "line_item": "gpt-5-2025-08-07, cached input", "project_id": null},
+                {"object": "organization.costs.result", "amount": {"value": 0.2, "currency": "usd"}, "line_item": "Web search tool calls", "project_id": "proj_abc"}
+            ]
+        }],
+        "has_more": false,
+        "next_page": null
+    }"#;
+
+    const USAGE: &str = r#"{
+        "object": "page",
+        "data": [{
+            "object": "bucket",
+            "start_time": 1792195200,
+            "end_time": 1792281600,
+            "results": [
+                {"object": "organization.usage.completions.result", "input_tokens": 1000000, "output_tokens": 250000, "input_cached_tokens": 400000, "num_model_requests": 120, "project_id": "proj_abc", "api_key_id": "key_ci", "model": "gpt-5-2025-08-07", "batch": null},
+                {"object": "organization.usage.completions.result", "input_tokens": 200000, "output_tokens": 0, "input_cached_tokens": 0, "num_model_requests": 8, "project_id": null, "api_key_id": null, "model": "gpt-5-2025-08-07"}
+            ]
+        }],
+        "has_more": false,
+        "next_page": null
+    }"#;
+
+    const RATE_LIMITS: &str = r#"{
+        "object": "list",
+        "data": [
+            {"object": "project.rate_limit", "id": "rl-gpt-5", "model": "gpt-5", "max_requests_per_1_minute": 500, "max_tokens_per_1_minute": 500000},
+            {"object": "project.rate_limit", "id": "rl-gpt-5-mini", "model": "gpt-5-mini", "max_requests_per_1_minute": 5000, "max_tokens_per_1_minute": 2000000}
+        ],
+        "has_more": false
+    }"#;
+
+    #[test]
+    fn parses_line_items() {
+        assert!(matches!(parse_line_item("gpt-4o, input"), (m, CostPart::Input) if m == "gpt-4o"));
+        assert!(matches!(
+            parse_line_item("gpt-4o-mini, cached input"),
+            (m, CostPart::CacheRead) if m == "gpt-4o-mini"
+        ));
+        assert!(matches!(
+            parse_line_item("Web search tool calls"),
+            (m, CostPart::Other) if m == "Web search tool calls"
+        ));
+    }
+
+    #[test]
+    fn reports_map_onto_cost_summary() {
+        let costs: ReportPage<CostResult> = serde_json::from_str(COSTS).unwrap();
+        let usage: ReportPage<UsageResult> = serde_json::from_str(USAGE).unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let lines = usage_lines(&usage.data);
+        assert_eq!(lines[0].date, today);
+        assert_eq!(lines[0].input_tokens, 600_000);
+        assert_eq!(lines[0].cache_read_tokens, 400_000);
+        assert_eq!(lines[1].workspace, "default");
+
+        let summary = org::build_summary(&cost_lines(&costs.data), &lines, DAYS, today);
+        assert!((summary.total_cost - 4.0).abs() < 1e-9);
+        let gpt5 = &summary.by_model[0];
+        assert_eq!(gpt5.model, "gpt-5-2025-08-07");
+        assert!((gpt5.cache_read_cost - 0.05).abs() < 1e-9);
+        assert_eq!(gpt5.output_tokens, 250_000);
+        assert_eq!(summary.by_workspace[0].id, "proj_abc");
+        assert_eq!(summary.by_api_key[0].id, "key_ci");
+    }
+
+    #[test]
+    fn rate_windows_use_busiest_model() {
+        let limits: RateLimitList = serde_json::from_str(RATE_LIMITS).unwrap();
+        let minute: ReportPage<UsageResult> = serde_json::from_str(
+            r#"{"data": [
+                {"start_time": 1760745480, "results": [{"model": "gpt-5-2025-08-07", "input_tokens": 100000, "output_tokens": 25000, "num_model_requests": 50}]},
+                {"start_time": 1760745540, "results": [
+                    {"model": "gpt-5-2025-08-07", "input_tokens": 200000, "output_tokens": 50000, "num_model_requests": 100},
+                    {"model": "gpt-5-mini-2025-08-07", "input_tokens": 10000, "output_tokens": 0, "num_model_requests": 4000}
+                ]}
+            ]}"#,
+        )
+        .unwrap();
+        let summary = org::build_summary(&[], &[], DAYS, Utc::now().date_naive());
+        let now: DateTime<Utc> = "2026-10-18T00:00:30Z".parse().unwrap();
+
+        let (tokens, requests) = rate_windows(&limits.data, &minute.data, &summary, now);
+        let tokens = tokens.unwrap();
+        assert!((tokens.used_percent - 50.0).abs() < 1e-9);
+        assert_eq!(
+            tokens.reset_description.as_deref(),
+            Some("gpt-5: 250000 of 500000 tokens/min")
+        );
+        let requests = requests.unwrap();
+        assert!((requests.used_percent - 80.0).abs() < 1e-9);
+        assert!(requests.reset_description.unwrap().starts_with("gpt-5-mini:"));
+    }
+
+    #[test]
+    fn idle_project_shows_top_model_limits() {
+        let limits: RateLimitList = serde_json::from_str(RATE_LIMITS).unwrap();
+        let costs: ReportPage<CostResult> = serde_json::from_str(COSTS).unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let summary = org::build_summary(&cost_lines(&costs.data), &[], DAYS, today);
+
+        let (tokens, requests) = rate_windows(&limits.data, &[], &summary, Utc::now());
+        assert_eq!(tokens.unwrap().used_percent, 0.0);
+        assert!(requests.is_some());
+        assert_eq!(rate_windows(&[], &[], &summary, Utc::now()).0.map(|w| w.used_percent), None);
+    }
+
+    #[test]
+    fn project_comes_from_config() {
+        let mut config = AppConfig::default();
+        assert!(configured_project(&config).is_none());
+        config.providers.push(ProviderConfig {
+            id: "openai_admin".into(),
+            enabled: true,
+            source: "auto".into(),
+            api_key: None,
+            timeout_secs: None,
+            cookie: None,
+            project: Some("proj_abc".into()),
+            region: None,
+            credentials: None,
+            currency: None,
+        });
+        assert_eq!(configured_project(&config).as_deref(), Some("proj_abc"));
+    }
+}
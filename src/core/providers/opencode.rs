@@ -1,8 +1,13 @@
 use anyhow::Result;
 
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 
 /// OpenCode usage provider (stub).
-pub async fn fetch() -> Result<FetchResult> {
-    anyhow::bail!("OpenCode requires browser cookies (not yet supported)")
+pub async fn fetch(_http: &HttpClient) -> Result<FetchResult> {
+    Err(ProviderError::Unsupported(
+        "OpenCode requires browser cookies (not yet supported)".into(),
+    )
+    .into())
 }
@@ -3,6 +3,8 @@ use serde::Deserialize;
 
 use crate::core::models::credits::CreditsSnapshot;
 use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -50,32 +52,31 @@ fn parse_key_window(key_data: &KeyData) -> Option<RateWindow> {
 }
 
 /// Fetch usage data from the OpenRouter API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let api_key =
         std::env::var("OPENROUTER_API_KEY").context("OPENROUTER_API_KEY env var not set")?;
 
     if api_key.is_empty() {
-        anyhow::bail!("OPENROUTER_API_KEY is empty");
+        return Err(ProviderError::AuthMissing("OPENROUTER_API_KEY is empty".into()).into());
     }
 
-    let client = reqwest::Client::new();
-
     // Fetch credits
-    let credits_response = client
+    let request = http
         .get(CREDITS_URL)
         .header("Authorization", format!("Bearer {}", api_key))
-        .header("Accept", "application/json")
-        .send()
+        .header("Accept", "application/json");
+    let credits_response = http
+        .send(request)
         .await
         .context("Failed to send request to OpenRouter credits API")?;
 
     let status = credits_response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized - check your OPENROUTER_API_KEY");
+        return Err(ProviderError::unauthorized("Unauthorized - check your OPENROUTER_API_KEY").into());
     }
     if !status.is_success() {
         let body = credits_response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {} from credits endpoint: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let credits_data: CreditsResponse = credits_response
@@ -94,17 +95,17 @@ pub async fn fetch() -> Result<FetchResult> {
         used: Some(total_usage),
         limit: Some(total_credits),
         currency: Some("usd".to_string()),
+        unit: None,
         period: None,
+        converted: None,
     });
 
     // Optionally fetch key info for rate window
-    let primary = match client
+    let request = http
         .get(KEY_URL)
         .header("Authorization", format!("Bearer {}", api_key))
-        .header("Accept", "application/json")
-        .send()
-        .await
-    {
+        .header("Accept", "application/json");
+    let primary = match http.send(request).await {
         Ok(resp) if resp.status().is_success() => {
             match resp.json::<KeyResponse>().await {
                 Ok(key_data) => parse_key_window(&key_data.data),
@@ -121,9 +122,11 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary: None,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
-    Ok(FetchResult { usage, credits })
+    Ok(FetchResult { usage, credits, cost: None })
 }
 
 #[cfg(test)]
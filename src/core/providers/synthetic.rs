@@ -3,6 +3,8 @@ use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -46,26 +48,26 @@ fn parse_quota(entry: &QuotaEntry) -> RateWindow {
 }
 
 /// Fetch quota data from the Synthetic API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let api_key =
         std::env::var("SYNTHETIC_API_KEY").context("SYNTHETIC_API_KEY env var not set")?;
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = http
         .get(QUOTAS_URL)
         .header("Authorization", format!("Bearer {}", api_key))
-        .header("Accept", "application/json")
-        .send()
+        .header("Accept", "application/json");
+    let response = http
+        .send(request)
         .await
         .context("Failed to send request to Synthetic API")?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized — check SYNTHETIC_API_KEY");
+        return Err(ProviderError::unauthorized("Unauthorized — check SYNTHETIC_API_KEY").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let data: SyntheticResponse = response
@@ -84,11 +86,14 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
     Ok(FetchResult {
         usage,
         credits: None,
+        cost: None,
     })
 }
 
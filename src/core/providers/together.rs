@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+
+use crate::core::http::HttpClient;
+use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{env_api_key, header_window, FetchResult};
+use crate::core::providers::Provider;
+
+const MODELS_URL: &str = "https://api.together.xyz/v1/models";
+
+/// Token and request limits, from the `x-tokenlimit-*` and `x-ratelimit-*`
+/// headers. Together meters both per second.
+fn rate_windows(
+    headers: &HeaderMap,
+    now: DateTime<Utc>,
+) -> (Option<RateWindow>, Option<RateWindow>) {
+    let tokens = header_window(
+        headers,
+        "x-tokenlimit-limit",
+        "x-tokenlimit-remaining",
+        None,
+        "tokens/s",
+        0,
+        now,
+    );
+    let requests = header_window(
+        headers,
+        "x-ratelimit-limit",
+        "x-ratelimit-remaining",
+        Some("x-ratelimit-reset"),
+        "requests/s",
+        0,
+        now,
+    );
+    (tokens, requests)
+}
+
+/// Fetch the account's rate limits from Together.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let api_key = env_api_key("TOGETHER_API_KEY")?;
+
+    let request = http
+        .get(MODELS_URL)
+        .header("Authorization", format!("Bearer {}", api_key));
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to Together API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(
+            ProviderError::unauthorized("Unauthorized - check your TOGETHER_API_KEY").into(),
+        );
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    let (primary, secondary) = rate_windows(response.headers(), Utc::now());
+    let usage = UsageSnapshot {
+        provider: Provider::Together,
+        source: "api".to_string(),
+        primary,
+        secondary,
+        tertiary: None,
+        identity: None,
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage,
+        credits: None,
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn reads_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("x-ratelimit-limit", "100"),
+            ("x-ratelimit-remaining", "97"),
+            ("x-ratelimit-reset", "1"),
+            ("x-tokenlimit-limit", "200000"),
+            ("x-tokenlimit-remaining", "150000"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+        let now: DateTime<Utc> = "2026-10-18T12:00:00Z".parse().unwrap();
+
+        let (tokens, requests) = rate_windows(&headers, now);
+        assert!((tokens.unwrap().used_percent - 25.0).abs() < 1e-9);
+        let requests = requests.unwrap();
+        assert!((requests.used_percent - 3.0).abs() < 1e-9);
+        assert_eq!(requests.resets_at, Some(now + Duration::seconds(1)));
+    }
+}
@@ -4,6 +4,8 @@ use serde::Deserialize;
 
 use crate::core::models::credits::CreditsSnapshot;
 use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -76,11 +78,11 @@ fn sum_bonus_grants(grants: &[BonusGrant]) -> f64 {
 }
 
 /// Fetch usage data from the Warp GraphQL API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let token = std::env::var("WARP_TOKEN").context("WARP_TOKEN env var not set")?;
 
     if token.is_empty() {
-        anyhow::bail!("WARP_TOKEN is empty");
+        return Err(ProviderError::AuthMissing("WARP_TOKEN is empty".into()).into());
     }
 
     let body = GraphQLRequest {
@@ -89,24 +91,24 @@ pub async fn fetch() -> Result<FetchResult> {
         variables: serde_json::json!({}),
     };
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = http
         .post(GRAPHQL_URL)
         .header("Authorization", format!("Bearer {}", token))
         .header("User-Agent", "Warp/1.0")
         .header("Content-Type", "application/json")
-        .json(&body)
-        .send()
+        .json(&body);
+    let response = http
+        .send_repeatable(request)
         .await
         .context("Failed to send request to Warp API")?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized - check your WARP_TOKEN");
+        return Err(ProviderError::unauthorized("Unauthorized - check your WARP_TOKEN").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     let data: WarpGraphQLResponse = response
@@ -114,7 +116,9 @@ pub async fn fetch() -> Result<FetchResult> {
         .await
         .context("Failed to parse Warp GraphQL response")?;
 
-    let warp_data = data.data.context("Missing 'data' field in Warp response")?;
+    let warp_data = data
+        .data
+        .ok_or_else(|| ProviderError::Parse("Missing 'data' field in Warp response".into()))?;
 
     let primary = warp_data
         .request_limit_info
@@ -134,7 +138,9 @@ pub async fn fetch() -> Result<FetchResult> {
         used: None,
         limit: None,
         currency: None,
+        unit: None,
         period: None,
+        converted: None,
     });
 
     let usage = UsageSnapshot {
@@ -144,9 +150,11 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary: None,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
-    Ok(FetchResult { usage, credits })
+    Ok(FetchResult { usage, credits, cost: None })
 }
 
 #[cfg(test)]
@@ -0,0 +1,206 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::core::http::HttpClient;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::models::usage::{ProviderIdentity, UsageSnapshot};
+use crate::core::providers::error::ProviderError;
+use crate::core::providers::fetch::{env_api_key, FetchResult};
+use crate::core::providers::Provider;
+
+const API_KEY_URL: &str = "https://api.x.ai/v1/api-key";
+const MANAGEMENT_URL: &str = "https://management-api.x.ai/v1/billing/teams";
+
+/// Management key for the team's billing; the prepaid balance is only
+/// shown when it is set.
+const MANAGEMENT_KEY_ENV: &str = "XAI_MANAGEMENT_KEY";
+
+/// The API key's own metadata.
+#[derive(Deserialize)]
+struct ApiKeyInfo {
+    name: Option<String>,
+    team_id: Option<String>,
+    #[serde(default)]
+    team_blocked: bool,
+    #[serde(default)]
+    api_key_blocked: bool,
+    #[serde(default)]
+    api_key_disabled: bool,
+}
+
+/// Prepaid credit ledger of a team. `total.val` is in cents and negative
+/// while credit remains.
+#[derive(Deserialize)]
+struct PrepaidBalance {
+    total: Option<serde_json::Value>,
+}
+
+fn parse_balance(data: &PrepaidBalance) -> Option<CreditsSnapshot> {
+    let val = &data.total.as_ref()?["val"];
+    let cents = val
+        .as_f64()
+        .or_else(|| val.as_str().and_then(|s| s.parse().ok()))?;
+    let remaining = (-cents / 100.0).max(0.0);
+    Some(CreditsSnapshot {
+        remaining,
+        has_credits: remaining > 0.0,
+        unlimited: false,
+        used: None,
+        limit: None,
+        currency: Some("usd".to_string()),
+        unit: None,
+        period: None,
+        converted: None,
+    })
+}
+
+fn check_key(info: &ApiKeyInfo) -> Result<()> {
+    let reason = if info.team_blocked {
+        "the team is blocked"
+    } else if info.api_key_blocked {
+        "the API key is blocked"
+    } else if info.api_key_disabled {
+        "the API key is disabled"
+    } else {
+        return Ok(());
+    };
+    Err(ProviderError::AuthExpired {
+        message: format!("xAI: {}", reason),
+        status: None,
+    }
+    .into())
+}
+
+async fn fetch_balance(http: &HttpClient, key: &str, team_id: &str) -> Result<PrepaidBalance> {
+    let request = http
+        .get(&format!("{}/{}/prepaid/balance", MANAGEMENT_URL, team_id))
+        .header("Authorization", format!("Bearer {}", key));
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to xAI Management API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(
+            ProviderError::unauthorized("Unauthorized - check your XAI_MANAGEMENT_KEY").into(),
+        );
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+    response
+        .json()
+        .await
+        .context("Failed to parse xAI balance response")
+}
+
+/// Fetch the API key's status from xAI, and the team's prepaid balance
+/// with a management key.
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
+    let api_key = env_api_key("XAI_API_KEY")?;
+
+    let request = http
+        .get(API_KEY_URL)
+        .header("Authorization", format!("Bearer {}", api_key));
+    let response = http
+        .send(request)
+        .await
+        .context("Failed to send request to xAI API")?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProviderError::unauthorized("Unauthorized - check your XAI_API_KEY").into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ProviderError::http_status(status, body).into());
+    }
+
+    let info: ApiKeyInfo = response
+        .json()
+        .await
+        .context("Failed to parse xAI API key response")?;
+    check_key(&info)?;
+
+    let credits = match (env_api_key(MANAGEMENT_KEY_ENV), &info.team_id) {
+        (Ok(key), Some(team_id)) => parse_balance(&fetch_balance(http, &key, team_id).await?),
+        _ => None,
+    };
+
+    let usage = UsageSnapshot {
+        provider: Provider::Xai,
+        source: "api".to_string(),
+        primary: None,
+        secondary: None,
+        tertiary: None,
+        identity: Some(ProviderIdentity {
+            email: None,
+            organization: info.team_id,
+            plan: info.name,
+        }),
+        local: None,
+        endpoints: Vec::new(),
+    };
+
+    Ok(FetchResult {
+        usage,
+        credits,
+        cost: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const API_KEY: &str = r#"{
+        "redacted_api_key": "xai-...b2c3",
+        "user_id": "59fbe5f2-040b-46d5-8325-868bb8f23eb2",
+        "name": "ci",
+        "create_time": "2026-01-01T12:55:18.139305Z",
+        "modify_time": "2026-02-28T17:20:13.343173Z",
+        "team_id": "5ea6f6bd-7815-4b8a-9135-28b2d7ba6722",
+        "acls": ["api-key:model:*", "api-key:endpoint:*"],
+        "api_key_id": "ae1e1841-4326-4b25-8d2c-b5e8da6b7e1a",
+        "team_blocked": false,
+        "api_key_blocked": false,
+        "api_key_disabled": false
+    }"#;
+
+    #[test]
+    fn parses_api_key_info() {
+        let info: ApiKeyInfo = serde_json::from_str(API_KEY).unwrap();
+        assert!(check_key(&info).is_ok());
+        assert_eq!(info.name.as_deref(), Some("ci"));
+        assert_eq!(
+            info.team_id.as_deref(),
+            Some("5ea6f6bd-7815-4b8a-9135-28b2d7ba6722")
+        );
+    }
+
+    #[test]
+    fn blocked_key_is_an_auth_error() {
+        let info: ApiKeyInfo = serde_json::from_str(r#"{"api_key_disabled": true}"#).unwrap();
+        let err = check_key(&info).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ProviderError>(),
+            Some(ProviderError::AuthExpired { .. })
+        ));
+        assert!(err.to_string().contains("disabled"));
+    }
+
+    #[test]
+    fn parses_prepaid_balance_in_cents() {
+        let data: PrepaidBalance =
+            serde_json::from_str(r#"{"changes": [], "total": {"val": "-2550"}}"#).unwrap();
+        let credits = parse_balance(&data).unwrap();
+        assert_eq!(credits.remaining, 25.5);
+        assert!(credits.has_credits);
+
+        let spent: PrepaidBalance = serde_json::from_str(r#"{"total": {"val": 120}}"#).unwrap();
+        assert!(!parse_balance(&spent).unwrap().has_credits);
+        assert!(parse_balance(&PrepaidBalance { total: None }).is_none());
+    }
+}
@@ -3,6 +3,8 @@ use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 use crate::core::models::usage::{RateWindow, UsageSnapshot};
+use crate::core::http::HttpClient;
+use crate::core::providers::error::ProviderError;
 use crate::core::providers::fetch::FetchResult;
 use crate::core::providers::Provider;
 
@@ -75,30 +77,30 @@ fn parse_limit(entry: &LimitEntry) -> RateWindow {
     }
 }
 
-async fn try_fetch(url: &str, api_key: &str) -> Result<reqwest::Response> {
-    let client = reqwest::Client::new();
-    let response = client
+async fn try_fetch(http: &HttpClient, url: &str, api_key: &str) -> Result<reqwest::Response> {
+    let request = http
         .get(url)
         .header("Authorization", format!("Bearer {}", api_key))
-        .header("Accept", "application/json")
-        .send()
+        .header("Accept", "application/json");
+    let response = http
+        .send(request)
         .await
         .with_context(|| format!("Failed to send request to {}", url))?;
 
     let status = response.status();
     if status == reqwest::StatusCode::UNAUTHORIZED {
-        anyhow::bail!("Unauthorized — check Z_AI_API_KEY");
+        return Err(ProviderError::unauthorized("Unauthorized — check Z_AI_API_KEY").into());
     }
     if !status.is_success() {
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!("HTTP {}: {}", status.as_u16(), body);
+        return Err(ProviderError::http_status(status, body).into());
     }
 
     Ok(response)
 }
 
 /// Fetch usage quota data from the Zai API.
-pub async fn fetch() -> Result<FetchResult> {
+pub async fn fetch(http: &HttpClient) -> Result<FetchResult> {
     let api_key = std::env::var("Z_AI_API_KEY").context("Z_AI_API_KEY env var not set")?;
 
     let url = resolve_url();
@@ -107,12 +109,12 @@ pub async fn fetch() -> Result<FetchResult> {
         eprintln!("zai: using custom host via Z_AI_API_HOST");
     }
 
-    let response = match try_fetch(&url, &api_key).await {
+    let response = match try_fetch(http, &url, &api_key).await {
         Ok(resp) => resp,
         Err(_) => {
             let fallback = fallback_url();
             crate::core::providers::fetch::validate_endpoint(&fallback, "Zai")?;
-            try_fetch(&fallback, &api_key).await?
+            try_fetch(http, &fallback, &api_key).await?
         }
     };
 
@@ -147,11 +149,14 @@ pub async fn fetch() -> Result<FetchResult> {
         secondary,
         tertiary: None,
         identity: None,
+        local: None,
+        endpoints: Vec::new(),
     };
 
     Ok(FetchResult {
         usage,
         credits: None,
+        cost: None,
     })
 }
 
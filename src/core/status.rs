@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
 
+use crate::core::http::HttpClient;
 use crate::core::models::status::{StatusIndicator, StatusInfo};
 use crate::core::providers::Provider;
 
@@ -27,22 +28,16 @@ fn parse_indicator(indicator: &str) -> StatusIndicator {
 }
 
 /// Fetch status from a provider's statuspage.io endpoint.
-pub async fn fetch_status(provider: &Provider) -> Result<StatusInfo> {
+pub async fn fetch_status(http: &HttpClient, provider: &Provider) -> Result<StatusInfo> {
     let base_url = provider
         .status_page_url()
         .context("Provider has no status page URL")?;
 
     let url = format!("{}/api/v2/status.json", base_url);
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .context("Failed to build HTTP client")?;
-
-    let response = client
-        .get(&url)
-        .header("Accept", "application/json")
-        .send()
+    let request = http.get(&url).header("Accept", "application/json");
+    let response = http
+        .send(request)
         .await
         .context("Failed to fetch status page")?;
 
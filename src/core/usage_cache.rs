@@ -0,0 +1,211 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::core::config::AppConfig;
+use crate::core::models::cost::CostSummary;
+use crate::core::models::credits::CreditsSnapshot;
+use crate::core::models::usage::UsageSnapshot;
+use crate::core::providers::fetch::FetchResult;
+use crate::core::providers::Provider;
+
+/// Default `settings.cache_ttl_secs`.
+pub const DEFAULT_TTL_SECS: u64 = 60;
+
+/// Bumped when the entry format changes; older entries are ignored.
+const ENTRY_VERSION: u64 = 1;
+
+/// How `ait usage` uses the response cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Serve fresh entries, store new results.
+    Normal,
+    /// Skip cached entries but store the new results (`--refresh`).
+    Refresh,
+    /// Neither read nor write the cache (`--no-cache`).
+    Disabled,
+}
+
+impl CacheMode {
+    pub fn reads(&self) -> bool {
+        *self == Self::Normal
+    }
+
+    pub fn writes(&self) -> bool {
+        *self != Self::Disabled
+    }
+}
+
+/// A provider's last successful fetch. Errors are never cached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFetch {
+    #[serde(default)]
+    pub version: u64,
+    pub fetched_at: DateTime<Utc>,
+    pub usage: UsageSnapshot,
+    pub credits: Option<CreditsSnapshot>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostSummary>,
+}
+
+impl CachedFetch {
+    pub fn into_result(self) -> FetchResult {
+        FetchResult {
+            usage: self.usage,
+            credits: self.credits,
+            cost: self.cost,
+        }
+    }
+}
+
+/// `$XDG_CACHE_HOME/ait/usage`, one JSON file per provider.
+pub fn cache_dir() -> PathBuf {
+    AppConfig::cache_dir().join("usage")
+}
+
+fn entry_path(dir: &Path, provider: Provider) -> PathBuf {
+    dir.join(format!("{}.json", provider.id()))
+}
+
+/// Return the cached fetch for `provider` if it is younger than `ttl_secs`.
+pub fn load(
+    dir: &Path,
+    provider: Provider,
+    ttl_secs: u64,
+    now: DateTime<Utc>,
+) -> Option<CachedFetch> {
+    if ttl_secs == 0 {
+        return None;
+    }
+    let content = std::fs::read_to_string(entry_path(dir, provider)).ok()?;
+    let entry: CachedFetch = serde_json::from_str(&content).ok()?;
+    if entry.version != ENTRY_VERSION || entry.usage.provider != provider {
+        return None;
+    }
+    let age = (now - entry.fetched_at).num_seconds();
+    // An entry from the future (clock change) is treated as stale
+    if age < 0 || age as u64 >= ttl_secs {
+        return None;
+    }
+    Some(entry)
+}
+
+/// Store a successful fetch. The entry is written to a temporary file and
+/// renamed so that concurrent invocations never read a partial file.
+pub fn store(
+    dir: &Path,
+    provider: Provider,
+    result: &FetchResult,
+    fetched_at: DateTime<Utc>,
+) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create cache directory: {}", dir.display()))?;
+    let entry = CachedFetch {
+        version: ENTRY_VERSION,
+        fetched_at,
+        usage: result.usage.clone(),
+        credits: result.credits.clone(),
+        cost: result.cost.clone(),
+    };
+    let json = serde_json::to_string(&entry).context("Failed to serialize usage cache entry")?;
+    let path = entry_path(dir, provider);
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    std::fs::write(&tmp, json)
+        .with_context(|| format!("Failed to write cache to {}", tmp.display()))?;
+    std::fs::rename(&tmp, &path)
+        .with_context(|| format!("Failed to write cache to {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn result(provider: Provider) -> FetchResult {
+        FetchResult {
+            usage: UsageSnapshot {
+                provider,
+                source: "api".into(),
+                primary: None,
+                secondary: None,
+                tertiary: None,
+                identity: None,
+                local: None,
+                endpoints: Vec::new(),
+            },
+            credits: Some(CreditsSnapshot {
+                remaining: 4.0,
+                has_credits: true,
+                unlimited: false,
+                used: None,
+                limit: None,
+                currency: None,
+                unit: None,
+                period: None,
+                converted: None,
+            }),
+            cost: None,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ait_test_usage_cache_{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fresh_entry_is_served() {
+        let dir = temp_dir("fresh");
+        let now = Utc::now();
+        store(&dir, Provider::Warp, &result(Provider::Warp), now - Duration::seconds(40)).unwrap();
+
+        let entry = load(&dir, Provider::Warp, 60, now).unwrap();
+        assert_eq!(entry.fetched_at, now - Duration::seconds(40));
+        assert_eq!(entry.into_result().credits.unwrap().remaining, 4.0);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stale_or_disabled_entry_is_ignored() {
+        let dir = temp_dir("stale");
+        let now = Utc::now();
+        store(&dir, Provider::Warp, &result(Provider::Warp), now - Duration::seconds(60)).unwrap();
+
+        assert!(load(&dir, Provider::Warp, 60, now).is_none());
+        assert!(load(&dir, Provider::Warp, 0, now).is_none());
+        assert!(load(&dir, Provider::Warp, 120, now).is_some());
+        // Entries dated in the future are not trusted
+        assert!(load(&dir, Provider::Warp, 120, now - Duration::seconds(90)).is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn entries_are_per_provider() {
+        let dir = temp_dir("per_provider");
+        let now = Utc::now();
+        store(&dir, Provider::Warp, &result(Provider::Warp), now).unwrap();
+
+        assert!(load(&dir, Provider::Kimi, 60, now).is_none());
+        assert!(dir.join("warp.json").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn corrupt_entry_is_ignored() {
+        let dir = temp_dir("corrupt");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("warp.json"), "{not json").unwrap();
+        assert!(load(&dir, Provider::Warp, 60, Utc::now()).is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cache_modes() {
+        assert!(CacheMode::Normal.reads() && CacheMode::Normal.writes());
+        assert!(!CacheMode::Refresh.reads() && CacheMode::Refresh.writes());
+        assert!(!CacheMode::Disabled.reads() && !CacheMode::Disabled.writes());
+    }
+}
@@ -0,0 +1,378 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::core::budget::project_spend;
+use crate::core::currency::{CurrencyConverter, BASE_CURRENCY};
+use crate::core::models::cost::CostSummary;
+use crate::core::providers::Provider;
+
+/// Built-in monthly subscription prices in USD: (provider, plan, price).
+/// Plan names are matched case-insensitively against `ProviderIdentity.plan`.
+const BUILTIN_PLANS: &[(Provider, &str, f64)] = &[
+    (Provider::Claude, "pro", 20.0),
+    (Provider::Claude, "max", 100.0),
+    (Provider::Claude, "max_5x", 100.0),
+    (Provider::Claude, "max_20x", 200.0),
+    (Provider::Claude, "team", 30.0),
+    (Provider::Codex, "plus", 20.0),
+    (Provider::Codex, "pro", 200.0),
+    (Provider::Codex, "team", 30.0),
+    (Provider::Codex, "business", 30.0),
+];
+
+/// A `[[plans]]` config entry. Without `plan` it applies to the provider
+/// whatever plan is reported (or when none is).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanPrice {
+    pub provider: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<String>,
+    /// Price per monthly billing period
+    pub price: f64,
+    /// Currency of `price`; defaults to USD
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Day of the month the billing period starts (1-31, default 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub billing_day: Option<u32>,
+}
+
+impl PlanPrice {
+    pub fn validate(&self) -> Result<(), String> {
+        if Provider::from_id(&self.provider).is_none() {
+            return Err(format!("unknown provider '{}'", self.provider));
+        }
+        if !self.price.is_finite() || self.price < 0.0 {
+            return Err(format!(
+                "'{}': price must be a non-negative number",
+                self.provider
+            ));
+        }
+        if let Some(day) = self.billing_day {
+            if !(1..=31).contains(&day) {
+                return Err(format!(
+                    "'{}': billing_day must be between 1 and 31",
+                    self.provider
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn same_plan(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Find the price of `plan` for `provider`: config entries for that plan,
+/// then built-in prices, then a config entry without a plan.
+pub fn lookup_plan(
+    overrides: &[PlanPrice],
+    provider: Provider,
+    plan: Option<&str>,
+) -> Option<PlanPrice> {
+    let for_provider = || overrides.iter().filter(move |p| p.provider == provider.id());
+
+    if let Some(plan) = plan {
+        if let Some(p) = for_provider().find(|p| p.plan.as_deref().is_some_and(|n| same_plan(n, plan))) {
+            return Some(p.clone());
+        }
+        if let Some((_, name, price)) = BUILTIN_PLANS
+            .iter()
+            .find(|(p, name, _)| *p == provider && same_plan(name, plan))
+        {
+            return Some(PlanPrice {
+                provider: provider.id().to_string(),
+                plan: Some(name.to_string()),
+                price: *price,
+                currency: None,
+                billing_day: None,
+            });
+        }
+    }
+    for_provider().find(|p| p.plan.is_none()).cloned()
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (ny, nm) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(ny, nm, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(28)
+}
+
+fn period_start_in(year: i32, month: u32, billing_day: u32) -> NaiveDate {
+    let day = billing_day.clamp(1, days_in_month(year, month));
+    NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default()
+}
+
+fn shift_month(year: i32, month: u32, by: i32) -> (i32, u32) {
+    let index = year * 12 + month as i32 - 1 + by;
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+}
+
+/// Billing period `back` periods before the one containing `today`, as
+/// (start inclusive, end exclusive). Short months clamp the billing day.
+pub fn billing_period(today: NaiveDate, billing_day: u32, back: u32) -> (NaiveDate, NaiveDate) {
+    let (mut year, mut month) = (today.year(), today.month());
+    if today < period_start_in(year, month, billing_day) {
+        (year, month) = shift_month(year, month, -1);
+    }
+    let (sy, sm) = shift_month(year, month, -(back as i32));
+    let (ey, em) = shift_month(sy, sm, 1);
+    (
+        period_start_in(sy, sm, billing_day),
+        period_start_in(ey, em, billing_day),
+    )
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodValue {
+    pub start: NaiveDate,
+    /// First day of the next period
+    pub end: NaiveDate,
+    /// Token cost at API prices for the period
+    pub api_cost: f64,
+    /// `api_cost / plan price`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ratio: Option<f64>,
+    /// Set for the current, unfinished period
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_cost: Option<f64>,
+}
+
+/// Subscription value of one provider account.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValueReport {
+    pub provider: Provider,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<String>,
+    /// Plan price per period in `currency`, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    pub currency: String,
+    /// Current period first, then previous ones
+    pub periods: Vec<PeriodValue>,
+    /// Change of the current period's projected cost vs. the previous period, in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trend_percent: Option<f64>,
+}
+
+/// Build a value report for `periods` billing periods from a cost summary
+/// covering them. Costs and the plan price are converted with `fx`; the caller
+/// must make sure `fx` has a rate for USD.
+pub fn build_report(
+    provider: Provider,
+    user: Option<String>,
+    plan: Option<&PlanPrice>,
+    fx: &CurrencyConverter,
+    periods: u32,
+    cost: &CostSummary,
+    now: NaiveDateTime,
+) -> ValueReport {
+    let today = now.date();
+    let rate = fx.rate(BASE_CURRENCY).unwrap_or(1.0);
+    let price = plan.and_then(|p| {
+        fx.rate(p.currency.as_deref().unwrap_or(BASE_CURRENCY))
+            .map(|r| p.price * r)
+    });
+    let billing_day = plan.and_then(|p| p.billing_day).unwrap_or(1);
+
+    let periods: Vec<PeriodValue> = (0..periods.max(1))
+        .map(|back| {
+            let (start, end) = billing_period(today, billing_day, back);
+            let api_cost = cost
+                .daily
+                .iter()
+                .filter(|d| d.date >= start && d.date < end)
+                .fold(0.0, |acc, d| acc + d.total_cost)
+                * rate;
+            let projected_cost = (back == 0).then(|| project_spend(api_cost, start, end, now));
+            PeriodValue {
+                start,
+                end,
+                api_cost,
+                ratio: price.filter(|p| *p > 0.0).map(|p| api_cost / p),
+                projected_cost,
+            }
+        })
+        .collect();
+
+    let trend_percent = match (periods.first(), periods.get(1)) {
+        (Some(current), Some(previous)) if previous.api_cost > 0.0 => current
+            .projected_cost
+            .map(|p| (p - previous.api_cost) / previous.api_cost * 100.0),
+        _ => None,
+    };
+
+    ValueReport {
+        provider,
+        user,
+        plan: plan.and_then(|p| p.plan.clone()),
+        price,
+        currency: fx.target().to_string(),
+        periods,
+        trend_percent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::models::cost::DailyReport;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn day(date: NaiveDate, total_cost: f64) -> DailyReport {
+        DailyReport {
+            date,
+            costs: vec![],
+            total_cost,
+        }
+    }
+
+    #[test]
+    fn billing_period_default_day() {
+        let today = date(2026, 10, 18);
+        assert_eq!(billing_period(today, 1, 0), (date(2026, 10, 1), date(2026, 11, 1)));
+        assert_eq!(billing_period(today, 1, 1), (date(2026, 9, 1), date(2026, 10, 1)));
+        assert_eq!(billing_period(today, 1, 10), (date(2025, 12, 1), date(2026, 1, 1)));
+    }
+
+    #[test]
+    fn billing_period_mid_month_and_clamping() {
+        assert_eq!(
+            billing_period(date(2026, 10, 10), 15, 0),
+            (date(2026, 9, 15), date(2026, 10, 15))
+        );
+        // Billing on the 31st clamps to the end of shorter months
+        assert_eq!(
+            billing_period(date(2026, 3, 5), 31, 0),
+            (date(2026, 2, 28), date(2026, 3, 31))
+        );
+    }
+
+    #[test]
+    fn lookup_prefers_config_then_builtin_then_fallback() {
+        let overrides = vec![
+            PlanPrice {
+                provider: "claude".to_string(),
+                plan: Some("MAX".to_string()),
+                price: 180.0,
+                currency: Some("EUR".to_string()),
+                billing_day: Some(5),
+            },
+            PlanPrice {
+                provider: "codex".to_string(),
+                plan: None,
+                price: 25.0,
+                currency: None,
+                billing_day: None,
+            },
+        ];
+        let p = lookup_plan(&overrides, Provider::Claude, Some("max")).unwrap();
+        assert_eq!(p.price, 180.0);
+        let p = lookup_plan(&overrides, Provider::Claude, Some("pro")).unwrap();
+        assert_eq!(p.price, 20.0);
+        assert!(lookup_plan(&overrides, Provider::Claude, Some("enterprise")).is_none());
+        let p = lookup_plan(&overrides, Provider::Codex, Some("enterprise")).unwrap();
+        assert_eq!(p.price, 25.0);
+        let p = lookup_plan(&overrides, Provider::Codex, None).unwrap();
+        assert_eq!(p.price, 25.0);
+    }
+
+    #[test]
+    fn report_computes_ratio_and_trend() {
+        let cost = CostSummary {
+            total_cost: 0.0,
+            today_cost: 0.0,
+            days: 90,
+            by_model: vec![],
+            daily: vec![
+                day(date(2026, 11, 5), 150.0),
+                day(date(2026, 10, 3), 300.0),
+                day(date(2026, 10, 20), 100.0),
+                day(date(2026, 9, 30), 50.0),
+            ],
+            unpriced_models: vec![],
+            converted: None,
+            by_workspace: Vec::new(),
+            by_api_key: Vec::new(),
+        };
+        // Halfway through November (15 of 30 days)
+        let now = date(2026, 11, 16).and_hms_opt(0, 0, 0).unwrap();
+        let plan = lookup_plan(&[], Provider::Claude, Some("max")).unwrap();
+        let fx = CurrencyConverter::new("USD", &Default::default());
+        let report = build_report(
+            Provider::Claude,
+            Some("user@example.com".to_string()),
+            Some(&plan),
+            &fx,
+            3,
+            &cost,
+            now,
+        );
+        assert_eq!(report.periods.len(), 3);
+        let current = &report.periods[0];
+        assert_eq!(current.start, date(2026, 11, 1));
+        assert!((current.api_cost - 150.0).abs() < 1e-9);
+        assert!((current.ratio.unwrap() - 1.5).abs() < 1e-9);
+        assert!((current.projected_cost.unwrap() - 300.0).abs() < 1e-9);
+        assert!((report.periods[1].api_cost - 400.0).abs() < 1e-9);
+        assert!(report.periods[1].projected_cost.is_none());
+        assert!((report.periods[2].api_cost - 50.0).abs() < 1e-9);
+        // Projected 300 vs 400 last period
+        assert!((report.trend_percent.unwrap() + 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn report_converts_price_and_cost() {
+        let cost = CostSummary {
+            total_cost: 0.0,
+            today_cost: 0.0,
+            days: 30,
+            by_model: vec![],
+            daily: vec![day(date(2026, 11, 2), 100.0)],
+            unpriced_models: vec![],
+            converted: None,
+            by_workspace: Vec::new(),
+            by_api_key: Vec::new(),
+        };
+        let plan = PlanPrice {
+            provider: "claude".to_string(),
+            plan: None,
+            price: 90.0,
+            currency: Some("EUR".to_string()),
+            billing_day: None,
+        };
+        let mut rates = std::collections::BTreeMap::new();
+        rates.insert("EUR".to_string(), 0.9);
+        let fx = CurrencyConverter::new("EUR", &rates);
+        let now = date(2026, 11, 16).and_hms_opt(0, 0, 0).unwrap();
+        let report = build_report(Provider::Claude, None, Some(&plan), &fx, 1, &cost, now);
+        assert_eq!(report.currency, "EUR");
+        assert!((report.price.unwrap() - 90.0).abs() < 1e-9);
+        assert!((report.periods[0].api_cost - 90.0).abs() < 1e-9);
+        assert!((report.periods[0].ratio.unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn validate_plan_entries() {
+        let mut p = PlanPrice {
+            provider: "claude".to_string(),
+            plan: None,
+            price: 20.0,
+            currency: None,
+            billing_day: Some(32),
+        };
+        assert!(p.validate().is_err());
+        p.billing_day = Some(31);
+        assert!(p.validate().is_ok());
+        p.provider = "nope".to_string();
+        assert!(p.validate().is_err());
+    }
+}
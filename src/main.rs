@@ -32,6 +32,10 @@ struct Cli {
     /// Verbose logging to stderr
     #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Overall deadline in seconds; slower providers are reported as timed out
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
 }
 
 #[derive(Subcommand)]
@@ -53,12 +57,50 @@ enum Commands {
         /// Show detailed cost breakdown (by-model + recent days)
         #[arg(short, long)]
         all: bool,
+
+        /// Neither read nor update the response cache
+        #[arg(long, conflicts_with = "refresh")]
+        no_cache: bool,
+
+        /// Ignore cached responses and fetch fresh data (the cache is updated)
+        #[arg(long)]
+        refresh: bool,
     },
     /// Manage configuration
     Config {
         #[command(subcommand)]
         action: ConfigAction,
     },
+    /// Show spend against configured budgets (exits 2 if any is exceeded)
+    Budget,
+    /// Compare API-equivalent token cost with subscription price
+    Value {
+        /// Provider to report on (claude or codex; default: enabled ones)
+        #[arg(short, long)]
+        provider: Option<String>,
+
+        /// Number of billing periods to show, current one included
+        #[arg(long, default_value_t = 3)]
+        months: u32,
+    },
+    /// Diagnose credential sources, overrides, session logs and cache
+    Doctor {
+        /// Provider to diagnose (default: all implemented providers)
+        #[arg(short, long)]
+        provider: Option<String>,
+    },
+    /// Run or manage the background daemon that serves `ait usage`
+    Daemon {
+        #[command(subcommand)]
+        action: Option<DaemonAction>,
+    },
+    /// Print the JSON Schema of `ait usage --json` output
+    Schema,
+    /// Manage model pricing data
+    Pricing {
+        #[command(subcommand)]
+        action: PricingAction,
+    },
     /// Install this project's Codex skill into an agents skills directory
     InstallSkill(InstallSkillArgs),
 }
@@ -83,6 +125,40 @@ enum ConfigAction {
     },
 }
 
+#[derive(Subcommand)]
+enum DaemonAction {
+    /// Refresh providers periodically and serve them on a Unix socket (default)
+    Run {
+        /// Seconds between refreshes
+        #[arg(long, default_value_t = 60)]
+        interval: u64,
+    },
+    /// Show whether the daemon is running
+    Status,
+    /// Print a systemd user unit for the daemon
+    Unit {
+        /// Write it to ~/.config/systemd/user/ait.service instead
+        #[arg(long)]
+        install: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PricingAction {
+    /// Import a pricing table (TOML or JSON) without rebuilding
+    Update {
+        /// Path to the pricing table file
+        #[arg(long)]
+        from: std::path::PathBuf,
+    },
+    /// List models seen in session logs that have no pricing entry
+    Missing {
+        /// Only consider the last N days (default: all logs)
+        #[arg(long)]
+        days: Option<u32>,
+    },
+}
+
 #[derive(rust_embed::RustEmbed)]
 #[folder = ".skill"]
 struct SkillAssets;
@@ -103,20 +179,30 @@ async fn main() -> anyhow::Result<()> {
         pretty: cli.pretty,
         use_color: cli::output::detect_color(!cli.no_color),
         verbose: cli.verbose,
+        timeout: cli.timeout.map(std::time::Duration::from_secs),
     };
 
     match cli.command {
         None | Some(Commands::Usage { .. }) => {
-            let (provider, source, status, all) = match cli.command {
+            let (provider, source, status, all, no_cache, refresh) = match cli.command {
                 Some(Commands::Usage {
                     provider,
                     source,
                     status,
                     all,
-                }) => (provider, source, status, all),
-                _ => (None, None, false, false),
+                    no_cache,
+                    refresh,
+                }) => (provider, source, status, all, no_cache, refresh),
+                _ => (None, None, false, false, false, false),
+            };
+            let cache_mode = if no_cache {
+                core::usage_cache::CacheMode::Disabled
+            } else if refresh {
+                core::usage_cache::CacheMode::Refresh
+            } else {
+                core::usage_cache::CacheMode::Normal
             };
-            cli::usage_cmd::run(provider, source, status, all, &output_opts).await?;
+            cli::usage_cmd::run(provider, source, status, all, cache_mode, &output_opts).await?;
         }
         Some(Commands::Config { action }) => match action {
             ConfigAction::Init => cli::config_cmd::init(&output_opts)?,
@@ -127,6 +213,24 @@ async fn main() -> anyhow::Result<()> {
                 cli::config_cmd::remove(&provider, &output_opts)?
             }
         },
+        Some(Commands::Budget) => cli::budget_cmd::run(&output_opts).await?,
+        Some(Commands::Value { provider, months }) => {
+            cli::value_cmd::run(provider, months, &output_opts).await?
+        }
+        Some(Commands::Doctor { provider }) => cli::doctor_cmd::run(provider, &output_opts)?,
+        Some(Commands::Daemon { action }) => match action {
+            None => cli::daemon_cmd::run(60, &output_opts).await?,
+            Some(DaemonAction::Run { interval }) => {
+                cli::daemon_cmd::run(interval, &output_opts).await?
+            }
+            Some(DaemonAction::Status) => cli::daemon_cmd::status(&output_opts).await?,
+            Some(DaemonAction::Unit { install }) => cli::daemon_cmd::unit(install)?,
+        },
+        Some(Commands::Schema) => cli::schema_cmd::run()?,
+        Some(Commands::Pricing { action }) => match action {
+            PricingAction::Update { from } => cli::pricing_cmd::update(&from, &output_opts)?,
+            PricingAction::Missing { days } => cli::pricing_cmd::missing(days, &output_opts)?,
+        },
         Some(Commands::InstallSkill(args)) => {
             let source = load_embedded_skill::<SkillAssets>();
             let result = install_interactive(source, &args)?;